@@ -8,7 +8,7 @@
 use std::sync::Arc;
 
 use jsonrpsee::RpcModule;
-use evercity_runtime::{opaque::Block, AccountId, Balance, Index};
+use evercity_runtime::{opaque::Block, AccountId, AssetBalance, AssetId, Balance, Hash, Index, Moment};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -36,9 +36,13 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_evercity_bonds_rpc::BondsRuntimeApi<Block, AccountId, Moment, Hash>,
+	C::Api: pallet_evercity_carbon_credits_rpc::CarbonCreditsRuntimeApi<Block, AccountId, Moment, AssetId, AssetBalance>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
+	use pallet_evercity_bonds_rpc::{Bonds, BondsApiServer};
+	use pallet_evercity_carbon_credits_rpc::{CarbonCredits, CarbonCreditsApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -46,7 +50,9 @@ where
 	let FullDeps { client, pool, deny_unsafe } = deps;
 
 	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(Bonds::new(client.clone()).into_rpc())?;
+	module.merge(CarbonCredits::new(client).into_rpc())?;
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
@@ -98,6 +98,7 @@
 //!
 //! * `balance` - Get the asset `id` balance of `who`.
 //! * `total_supply` - Get the total supply of an asset `id`.
+//! * `account_assets` - Get the assets held by `who` with their balances.
 //!
 //! Please refer to the [`Module`](./struct.Module.html) struct for details on publicly available functions.
 //!
@@ -124,7 +125,7 @@ use sp_runtime::{
 use codec::{Encode, Decode, HasCompact};
 use frame_support::{
 	ensure,
-	traits::{Currency, ReservableCurrency},
+	traits::{Currency, ReservableCurrency, StorageVersion},
 	dispatch::DispatchError,
 };
 pub use weights::WeightInfo;
@@ -132,6 +133,10 @@ use core::iter::Sum;
 
 pub use pallet::*;
 
+/// Current storage version of the pallet.
+/// Version 1 introduces `AccountAssets` index, filled from `Account` on runtime upgrade.
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 #[frame_support::pallet]
@@ -145,6 +150,7 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -188,7 +194,20 @@ pub mod pallet {
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Self::on_chain_storage_version() >= STORAGE_VERSION {
+				return T::DbWeight::get().reads(1);
+			}
+			let mut indexed: u64 = 0;
+			for (id, who, _) in Account::<T>::iter() {
+				AccountAssets::<T>::insert(&who, id, ());
+				indexed += 1;
+			}
+			STORAGE_VERSION.put::<Self>();
+			T::DbWeight::get().reads_writes(indexed.saturating_add(1), indexed.saturating_add(1))
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -279,6 +298,9 @@ pub mod pallet {
 				T::Currency::unreserve(&details.owner, details.deposit.saturating_add(metadata.deposit));
 
 				*maybe_details = None;
+				for who in Account::<T>::iter_key_prefix(&id) {
+					AccountAssets::<T>::remove(&who, &id);
+				}
 				Account::<T>::remove_prefix(&id, None);
 				Self::deposit_event(Event::Destroyed(id));
 				Ok(().into())
@@ -391,7 +413,7 @@ pub mod pallet {
 					let new_balance = t.balance.saturating_add(amount);
 					ensure!(new_balance >= details.min_balance, Error::<T>::BalanceLow);
 					if t.balance.is_zero() {
-						t.is_zombie = Self::new_account(&beneficiary, details)?;
+						t.is_zombie = Self::new_account(id, &beneficiary, details)?;
 					}
 					t.balance = new_balance;
 					Ok(().into())
@@ -439,7 +461,7 @@ pub mod pallet {
 						account.balance -= burned;
 						*maybe_account = if account.balance < d.min_balance {
 							burned += account.balance;
-							Self::dead_account(&who, d, account.is_zombie);
+							Self::dead_account(id, &who, d, account.is_zombie);
 							None
 						} else {
 							Some(account)
@@ -509,7 +531,7 @@ pub mod pallet {
 					let new_balance = a.balance.saturating_add(amount);
 					ensure!(new_balance >= details.min_balance, Error::<T>::BalanceLow);
 					if a.balance.is_zero() {
-						a.is_zombie = Self::new_account(&dest, details)?;
+						a.is_zombie = Self::new_account(id, &dest, details)?;
 					}
 					a.balance = new_balance;
 					Ok(().into())
@@ -521,7 +543,7 @@ pub mod pallet {
 						Account::<T>::insert(id, &origin, &origin_account)
 					}
 					true => {
-						Self::dead_account(&origin, details, origin_account.is_zombie);
+						Self::dead_account(id, &origin, details, origin_account.is_zombie);
 						Account::<T>::remove(id, &origin);
 					}
 				}
@@ -623,6 +645,17 @@ pub mod pallet {
 		ValueQuery
 	>;
 	#[pallet::storage]
+	/// The assets held by any given account. Secondary index of `Account`.
+	pub(super) type AccountAssets<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AssetId,
+		(),
+		OptionQuery
+	>;
+	#[pallet::storage]
 	/// Metadata of an asset.
 	pub(super) type Metadata<T: Config> = StorageMap<
 		_,
@@ -711,6 +744,13 @@ impl<T: Config> Pallet<T> {
 		Account::<T>::get(id, who).balance
 	}
 
+	/// Get the assets held by `who` with their balances.
+	pub fn account_assets(who: &T::AccountId) -> Vec<(T::AssetId, T::ABalance)> {
+		AccountAssets::<T>::iter_key_prefix(who)
+			.map(|id| (id, Account::<T>::get(id, who).balance))
+			.collect()
+	}
+
 	/// Get the total supply of an asset `id`.
 	pub fn total_supply(id: T::AssetId) -> T::ABalance {
 		Asset::<T>::get(id).map(|x| x.supply).unwrap_or_else(Zero::zero)
@@ -729,6 +769,7 @@ impl<T: Config> Pallet<T> {
 	}
 
 	fn new_account(
+		id: T::AssetId,
 		who: &T::AccountId,
 		d: &mut AssetDetails<T::ABalance, T::AccountId, BalanceOf<T>>,
 	) -> Result<bool, DispatchError> {
@@ -742,6 +783,7 @@ impl<T: Config> Pallet<T> {
 			true
 		});
 		d.accounts = accounts;
+		AccountAssets::<T>::insert(who, id, ());
 		r
 	}
 
@@ -761,6 +803,7 @@ impl<T: Config> Pallet<T> {
 	}
 
 	fn dead_account(
+		id: T::AssetId,
 		who: &T::AccountId,
 		d: &mut AssetDetails<T::ABalance, T::AccountId, BalanceOf<T>>,
 		is_zombie: bool,
@@ -771,6 +814,7 @@ impl<T: Config> Pallet<T> {
 			frame_system::Pallet::<T>::dec_consumers(who);
 		}
 		d.accounts = d.accounts.saturating_sub(1);
+		AccountAssets::<T>::remove(who, id);
 	}
 }
 
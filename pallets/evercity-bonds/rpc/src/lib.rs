@@ -0,0 +1,344 @@
+//! RPC interface for the Evercity bonds pallet.
+//! Exposes `BondApi` runtime calls under the `bonds_*` namespace, so that
+//! DApps can read bond state without decoding raw storage.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use pallet_evercity_bonds::{
+    bond::{
+        BondImpactReportHistoryItem, BondInterest, BondPeriodNumber, BondPortfolioStruct, BondProceedsReport, BondStruct, BondUnitAmount,
+        BondUnitPackage, BondUnitPriceStruct, BondUnitPurchaseBidStruct, BondUnitSaleLotStruct, EverUSDBalance,
+    },
+    token::{EverUSDReconciliationStruct, ReserveAttestationStruct},
+    BondDeadline, BondId, PeriodDataStruct, PeriodYield,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_evercity_bonds::runtime_api::BondApi as BondsRuntimeApi;
+
+#[rpc(client, server)]
+pub trait BondsApi<BlockHash, AccountId, Moment, Hash> {
+    /// Returns impact data and interest rate for every period of the bond
+    #[method(name = "bonds_getImpactReports")]
+    fn get_impact_reports(&self, bond: BondId, at: Option<BlockHash>) -> RpcResult<Vec<PeriodDataStruct>>;
+
+    /// Returns bond structure
+    #[method(name = "bonds_getBond")]
+    fn get_bond(&self, bond: BondId, at: Option<BlockHash>) -> RpcResult<Option<BondStruct<AccountId, Moment, Hash>>>;
+
+    /// Returns accrued coupon yield for every passed period of the bond
+    #[method(name = "bonds_getCouponYields")]
+    fn get_coupon_yields(&self, bond: BondId, at: Option<BlockHash>) -> RpcResult<Vec<PeriodYield>>;
+
+    /// Returns bond unit packages of the bondholder
+    #[method(name = "bonds_getBondUnitPackages")]
+    fn get_bond_unit_packages(
+        &self,
+        bond: BondId,
+        bondholder: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<BondUnitPackage>>;
+
+    /// Returns sale lots of the bond grouped by the seller
+    #[method(name = "bonds_getBondUnitLots")]
+    fn get_bond_unit_lots(
+        &self,
+        bond: BondId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(AccountId, Vec<BondUnitSaleLotStruct<AccountId, Moment>>)>>;
+
+    /// Returns purchase bids of the bond grouped by the bidder
+    #[method(name = "bonds_getBondUnitBids")]
+    fn get_bond_unit_bids(
+        &self,
+        bond: BondId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(AccountId, Vec<BondUnitPurchaseBidStruct<Moment>>)>>;
+
+    /// Returns sent, approved and rejected impact reports of the bond period
+    #[method(name = "bonds_getImpactReportHistory")]
+    fn get_impact_report_history(
+        &self,
+        bond: BondId,
+        period: BondPeriodNumber,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<BondImpactReportHistoryItem<AccountId, Moment>>>;
+
+    /// Returns use of proceeds report of the bond
+    #[method(name = "bonds_getProceedsReport")]
+    fn get_proceeds_report(&self, bond: BondId, at: Option<BlockHash>) -> RpcResult<BondProceedsReport<Moment>>;
+
+    /// Returns upcoming deadlines of the bond
+    #[method(name = "bonds_getBondCalendar")]
+    fn get_bond_calendar(&self, bond: BondId, at: Option<BlockHash>) -> RpcResult<Vec<BondDeadline<Moment>>>;
+
+    /// Returns upcoming deadlines of all bonds, the account participates in
+    #[method(name = "bonds_getAccountCalendar")]
+    fn get_account_calendar(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<BondDeadline<Moment>>>;
+
+    /// Returns accrued interest, clean and dirty price of bond units
+    #[method(name = "bonds_getBondUnitPrice")]
+    fn get_bond_unit_price(
+        &self,
+        bond: BondId,
+        bond_units: BondUnitAmount,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<BondUnitPriceStruct>>;
+
+    /// Returns yield to maturity of bond units bought at the price
+    #[method(name = "bonds_getYieldToMaturity")]
+    fn get_yield_to_maturity(
+        &self,
+        bond: BondId,
+        bond_units: BondUnitAmount,
+        price: EverUSDBalance,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<BondInterest>>;
+
+    /// Returns implied annual yield of zero-coupon bond, bought at issue price
+    #[method(name = "bonds_getImpliedYield")]
+    fn get_implied_yield(&self, bond: BondId, at: Option<BlockHash>) -> RpcResult<Option<BondInterest>>;
+
+    /// Returns EverUSD balances and bond positions of the account
+    #[method(name = "bonds_getPortfolio")]
+    fn get_portfolio(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<BondPortfolioStruct<AccountId, Moment>>;
+
+    /// Returns total supply of EverUSD, reconciled against confirmed mint and burn requests
+    #[method(name = "bonds_getEverUSDReconciliation")]
+    fn get_everusd_reconciliation(&self, at: Option<BlockHash>) -> RpcResult<EverUSDReconciliationStruct>;
+
+    /// Returns signed reserve attestation of the Custodian with the latest period
+    #[method(name = "bonds_getLatestReserveAttestation")]
+    fn get_latest_reserve_attestation(&self, at: Option<BlockHash>) -> RpcResult<Option<ReserveAttestationStruct<AccountId, Moment>>>;
+}
+
+/// Implements the BondsApi RPC trait for interacting with the bonds pallet.
+pub struct Bonds<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Bonds<C, B> {
+    /// Creates a new instance of the Bonds RPC handler.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+    /// The call to runtime failed.
+    RuntimeError,
+}
+
+impl From<Error> for i32 {
+    fn from(e: Error) -> i32 {
+        match e {
+            Error::RuntimeError => 1,
+        }
+    }
+}
+
+fn runtime_error(message: &'static str, e: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+    CallError::Custom(ErrorObject::owned(
+        Error::RuntimeError.into(),
+        message,
+        Some(format!("{:?}", e)),
+    ))
+    .into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Moment, Hash>
+    BondsApiServer<<Block as BlockT>::Hash, AccountId, Moment, Hash> for Bonds<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: BondsRuntimeApi<Block, AccountId, Moment, Hash>,
+    AccountId: Codec + Send + Sync + 'static,
+    Moment: Codec + Send + Sync + 'static,
+    Hash: Codec + Send + Sync + 'static,
+{
+    fn get_impact_reports(
+        &self,
+        bond: BondId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<PeriodDataStruct>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_impact_reports(&at, bond)
+            .map_err(|e| runtime_error("Unable to query impact reports.", e))
+    }
+
+    fn get_bond(
+        &self,
+        bond: BondId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<BondStruct<AccountId, Moment, Hash>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_bond(&at, bond)
+            .map_err(|e| runtime_error("Unable to query bond.", e))
+    }
+
+    fn get_coupon_yields(
+        &self,
+        bond: BondId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<PeriodYield>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_coupon_yields(&at, bond)
+            .map_err(|e| runtime_error("Unable to query coupon yields.", e))
+    }
+
+    fn get_bond_unit_packages(
+        &self,
+        bond: BondId,
+        bondholder: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<BondUnitPackage>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_bond_unit_packages(&at, bond, bondholder)
+            .map_err(|e| runtime_error("Unable to query bond unit packages.", e))
+    }
+
+    fn get_bond_unit_lots(
+        &self,
+        bond: BondId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(AccountId, Vec<BondUnitSaleLotStruct<AccountId, Moment>>)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_bond_unit_lots(&at, bond)
+            .map_err(|e| runtime_error("Unable to query bond unit lots.", e))
+    }
+
+    fn get_bond_unit_bids(
+        &self,
+        bond: BondId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(AccountId, Vec<BondUnitPurchaseBidStruct<Moment>>)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_bond_unit_bids(&at, bond)
+            .map_err(|e| runtime_error("Unable to query bond unit bids.", e))
+    }
+
+    fn get_impact_report_history(
+        &self,
+        bond: BondId,
+        period: BondPeriodNumber,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<BondImpactReportHistoryItem<AccountId, Moment>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_impact_report_history(&at, bond, period)
+            .map_err(|e| runtime_error("Unable to query impact report history.", e))
+    }
+
+    fn get_proceeds_report(
+        &self,
+        bond: BondId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<BondProceedsReport<Moment>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_proceeds_report(&at, bond)
+            .map_err(|e| runtime_error("Unable to query proceeds report.", e))
+    }
+
+    fn get_bond_calendar(
+        &self,
+        bond: BondId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<BondDeadline<Moment>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_bond_calendar(&at, bond)
+            .map_err(|e| runtime_error("Unable to query bond calendar.", e))
+    }
+
+    fn get_account_calendar(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<BondDeadline<Moment>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_account_calendar(&at, account)
+            .map_err(|e| runtime_error("Unable to query account calendar.", e))
+    }
+
+    fn get_bond_unit_price(
+        &self,
+        bond: BondId,
+        bond_units: BondUnitAmount,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<BondUnitPriceStruct>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_bond_unit_price(&at, bond, bond_units)
+            .map_err(|e| runtime_error("Unable to query bond unit price.", e))
+    }
+
+    fn get_yield_to_maturity(
+        &self,
+        bond: BondId,
+        bond_units: BondUnitAmount,
+        price: EverUSDBalance,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<BondInterest>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_yield_to_maturity(&at, bond, bond_units, price)
+            .map_err(|e| runtime_error("Unable to query yield to maturity.", e))
+    }
+
+    fn get_implied_yield(&self, bond: BondId, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Option<BondInterest>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_implied_yield(&at, bond)
+            .map_err(|e| runtime_error("Unable to query implied yield.", e))
+    }
+
+    fn get_portfolio(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<BondPortfolioStruct<AccountId, Moment>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_portfolio(&at, account)
+            .map_err(|e| runtime_error("Unable to query portfolio.", e))
+    }
+
+    fn get_everusd_reconciliation(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<EverUSDReconciliationStruct> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_everusd_reconciliation(&at)
+            .map_err(|e| runtime_error("Unable to query EverUSD reconciliation.", e))
+    }
+
+    fn get_latest_reserve_attestation(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<ReserveAttestationStruct<AccountId, Moment>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_latest_reserve_attestation(&at)
+            .map_err(|e| runtime_error("Unable to query reserve attestation.", e))
+    }
+}
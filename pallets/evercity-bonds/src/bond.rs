@@ -1,12 +1,12 @@
-use crate::period::{PeriodDescr, PeriodIterator};
+use crate::period::{BondDeadline, BondDeadlineKind, PeriodDescr, PeriodIterator};
 use frame_support::{
     codec::{Decode, Encode, EncodeLike},
-    dispatch::{Vec},
+    dispatch::{DispatchResult, Vec},
     sp_runtime::{
         traits::{AtLeast32Bit, SaturatedConversion, UniqueSaturatedInto},
         RuntimeDebug,
     },
-    sp_std::cmp::{min, Eq, PartialEq},
+    sp_std::cmp::{max, min, Eq, PartialEq},
     sp_std::fmt,
     sp_std::ops::Deref,
     sp_std::str::from_utf8_unchecked,
@@ -14,6 +14,7 @@ use frame_support::{
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use scale_info::TypeInfo;
+use pallet_evercity_filesign::file::FileId;
 
 pub trait Expired<Moment> {
     fn is_expired(&self, now: Moment) -> bool;
@@ -84,6 +85,7 @@ impl From<[u8; 16]> for BondId {
 pub enum BondImpactType {
     POWER_GENERATED,
     CO2_EMISSIONS_REDUCTION,
+    WATER_SAVED,
 }
 
 impl Default for BondImpactType {
@@ -102,6 +104,7 @@ pub enum BondState {
     ACTIVE,
     BANKRUPT,
     FINISHED,
+    DEFAULT_SETTLED,
 }
 
 impl Default for BondState {
@@ -118,6 +121,12 @@ pub type BondUnitAmount = u32;
 pub type BondInterest = u32;
 /// Bond period numerator
 pub type BondPeriodNumber = u32;
+/// Unique identifier of the bond unit sale lot
+pub type BondUnitSaleLotId = u64;
+/// Unique identifier of the bond unit purchase bid
+pub type BondUnitPurchaseBidId = u64;
+/// Unique identifier of the bond unit order, placed in bookbuilding
+pub type BondUnitOrderId = u64;
 
 /// Inner part of BondStruct, containing parameters, related to
 /// calculation of coupon interest rate using impact data, sent to bond.
@@ -218,7 +227,345 @@ pub struct BondInnerStruct<Moment, Hash, AccountId> {
     pub bond_units_base_price: EverUSDBalance,
 
     /// Optional Carbon Credits metadata, if Carbon Credits issuance included in bond
-    pub carbon_metadata: Option<CarbonUnitsMetadata<AccountId>>
+    pub carbon_metadata: Option<CarbonUnitsMetadata<AccountId>>,
+
+    /// Optional right of Issuer to redeem the bond before maturity date
+    pub call_schedule: Option<BondCallSchedule>,
+
+    /// Optional amortization schedule: share of Bond Unit base price, repaid to bondholders
+    /// at the end of each payment period together with coupon yield, ppm.
+    /// Must contain bond_duration values, the rest of principal is repaid at maturity date
+    pub principal_schedule: Option<Vec<BondInterest>>,
+
+    /// Issue discount of zero-coupon bond, ppm of Bond Unit base price.
+    /// Zero-coupon bond is sold below par value and pays full par value at maturity date
+    pub discount: Option<BondInterest>,
+
+    /// Additional KPIs of sustainability-linked bond. The first KPI is described by
+    /// impact_data_type, impact_data_baseline and impact_data_max_deviation_cap/floor,
+    /// its weight is the rest of FULL_KPI_WEIGHT after weights of additional KPIs
+    pub impact_kpis: Vec<BondImpactKpi>,
+
+    /// Dependency of interest rate on impact_data of the first KPI:
+    /// linear interpolation between baseline and cap/floor, step thresholds
+    /// or piecewise-linear table
+    pub interest_rate_curve: BondInterestRateCurve,
+
+    /// Optional bookbuilding mode: in BondState::BOOKING Investors place orders instead of
+    /// buying Bond Units, and the orders are filled by the rule at bond activation
+    pub bookbuilding: Option<BondAllocationRule>,
+
+    /// Fees of Evercity and Bond Arrangers: share of the bond fund at activation,
+    /// of coupon yield paid to bondholders and of secondary trade price
+    pub fees: Vec<BondFeeStruct<AccountId>>,
+
+    /// Optional use of proceeds of green bond: eligible categories of spending with caps.
+    /// Proceeds of the bond are reserved on the Issuer's account until allocated to the categories
+    pub use_of_proceeds: Vec<BondProceedsCategoryStruct>,
+}
+
+/// Max number of points in step or piecewise-linear interest rate curve
+pub const MAX_INTEREST_RATE_CURVE_POINTS: usize = 16;
+
+/// Point of interest rate curve: interest rate for given impact_data value
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondInterestRatePoint {
+    #[codec(compact)]
+    pub impact_data: u64,
+    #[codec(compact)]
+    pub interest_rate: BondInterest,
+}
+
+/// How effective interest rate depends on impact_data
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+#[allow(non_camel_case_types)]
+pub enum BondInterestRateCurve {
+    /// Linear interpolation between impact_data_baseline and
+    /// impact_data_max_deviation_cap/floor
+    LINEAR,
+    /// Interest rate of the highest threshold, not greater than impact_data.
+    /// Interest rate equals to interest_rate_margin_cap below the first threshold
+    STEP(Vec<BondInterestRatePoint>),
+    /// Linear interpolation between adjacent points of the table.
+    /// Interest rate of the first (last) point is applied below (above) the table
+    PIECEWISE_LINEAR(Vec<BondInterestRatePoint>),
+}
+
+impl Default for BondInterestRateCurve {
+    fn default() -> Self {
+        BondInterestRateCurve::LINEAR
+    }
+}
+
+impl BondInterestRateCurve {
+    /// Checks if curve points are sorted by impact_data and
+    /// interest rates are in floor-cap bounds
+    pub fn is_valid(&self, floor: BondInterest, cap: BondInterest) -> bool {
+        let (points, min_points) = match self {
+            BondInterestRateCurve::LINEAR => return true,
+            BondInterestRateCurve::STEP(points) => (points, 1),
+            BondInterestRateCurve::PIECEWISE_LINEAR(points) => (points, 2),
+        };
+        points.len() >= min_points
+            && points.len() <= MAX_INTEREST_RATE_CURVE_POINTS
+            && points.windows(2).all(|pair| pair[0].impact_data < pair[1].impact_data)
+            && points.iter().all(|point| point.interest_rate >= floor && point.interest_rate <= cap)
+    }
+}
+
+/// Max number of additional KPIs of the bond
+pub const MAX_IMPACT_KPIS: usize = 8;
+/// Sum of weights of all bond KPIs, ppm (100%)
+pub const FULL_KPI_WEIGHT: BondInterest = 1_000_000;
+
+/// Additional impact KPI of the bond. Each KPI has its own baseline, cap and floor,
+/// interest rates calculated for all KPIs are combined according to their weights
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondImpactKpi {
+    /// Type of data, sent to bond each payment_period for this KPI
+    pub impact_data_type: BondImpactType,
+    /// Weight of the KPI in effective interest rate, ppm
+    #[codec(compact)]
+    pub weight: BondInterest,
+    /// Base value of the KPI for each payment_period
+    pub impact_data_baseline: Vec<Option<u64>>,
+    /// Cap of KPI value. Values more then cap are considered equal to cap
+    #[codec(compact)]
+    pub impact_data_max_deviation_cap: u64,
+    /// Floor of KPI value. Values less then floor are considered equal to floor
+    #[codec(compact)]
+    pub impact_data_max_deviation_floor: u64,
+}
+
+impl BondImpactKpi {
+    /// Checks if KPI has baseline for each period of the bond, and baseline values
+    /// are in cap-floor bounds
+    pub fn is_valid(&self, bond_duration: BondPeriodNumber) -> bool {
+        self.weight > 0
+            && self.impact_data_max_deviation_floor < self.impact_data_max_deviation_cap
+            && self.impact_data_baseline.len() == bond_duration as usize
+            && self.impact_data_baseline.iter().all(|&bl| {
+                bl <= Some(self.impact_data_max_deviation_cap)
+                    && bl >= Some(self.impact_data_max_deviation_floor)
+            })
+    }
+}
+
+/// Max call premium, ppm (100%)
+pub const MAX_CALL_PREMIUM: BondInterest = 1_000_000;
+/// Whole principal of Bond Unit, ppm (100%)
+pub const FULL_PRINCIPAL: BondInterest = 1_000_000;
+
+/// Call schedule of the callable bond: Issuer can redeem the bond early,
+/// paying par value plus call premium to bondholders
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondCallSchedule {
+    /// The first period (see time_passed_after_activation), when the bond can be called
+    #[codec(compact)]
+    pub earliest_call_period: BondPeriodNumber,
+    /// Premium, paid to bondholders above par value of each Bond Unit, ppm
+    #[codec(compact)]
+    pub call_premium: BondInterest,
+}
+
+/// Max number of fee accounts of the bond
+pub const MAX_BOND_FEES: usize = 4;
+/// Max total fee of every kind, ppm (10%)
+pub const MAX_BOND_FEE: BondInterest = 100_000;
+
+/// Operation, the fee is charged on
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, RuntimeDebug, TypeInfo)]
+#[allow(non_camel_case_types)]
+pub enum BondFeeKind {
+    /// Bond fund, transferred to Issuer at bond activation
+    ISSUANCE,
+    /// Coupon yield, paid to bondholders
+    COUPON,
+    /// Price of Bond Units, sold on secondary market
+    TRADE,
+}
+
+/// Fees of the bond, paid to the fee account
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondFeeStruct<AccountId> {
+    /// Evercity or Bond Arranger account, receiving the fees
+    pub account: AccountId,
+    /// Share of the bond fund, withheld at bond activation, ppm
+    #[codec(compact)]
+    pub issuance_fee: BondInterest,
+    /// Share of coupon yield, withheld from payments to bondholders, ppm
+    #[codec(compact)]
+    pub coupon_fee: BondInterest,
+    /// Share of the price, withheld from the seller of Bond Units, ppm
+    #[codec(compact)]
+    pub trade_fee: BondInterest,
+}
+
+/// Max number of use of proceeds categories of the bond
+pub const MAX_BOND_PROCEEDS_CATEGORIES: usize = 8;
+
+/// Eligible project category of green bond proceeds
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, RuntimeDebug, TypeInfo)]
+#[allow(non_camel_case_types)]
+pub enum BondProceedsCategory {
+    RENEWABLE_ENERGY,
+    ENERGY_EFFICIENCY,
+    CLEAN_TRANSPORTATION,
+    GREEN_BUILDINGS,
+    WATER_MANAGEMENT,
+    POLLUTION_PREVENTION,
+    CLIMATE_ADAPTATION,
+    OTHER,
+}
+
+impl Default for BondProceedsCategory {
+    fn default() -> Self {
+        BondProceedsCategory::OTHER
+    }
+}
+
+/// Category of spending, declared in use of proceeds of the bond
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondProceedsCategoryStruct {
+    pub category: BondProceedsCategory,
+    /// Max share of raised proceeds, allocated to the category, ppm. Zero value means no cap
+    #[codec(compact)]
+    pub cap: BondInterest,
+}
+
+impl BondProceedsCategoryStruct {
+    /// Returns max amount of raised proceeds, allocated to the category
+    pub fn cap_amount(&self, raised: EverUSDBalance) -> EverUSDBalance {
+        if self.cap == 0 {
+            0
+        } else {
+            (raised as u128 * self.cap as u128 / FULL_PRINCIPAL as u128) as EverUSDBalance
+        }
+    }
+}
+
+/// Max number of allocations of bond proceeds
+pub const MAX_PROCEEDS_ALLOCATIONS: u32 = 256;
+
+/// Allocation of bond proceeds, released to the Issuer
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondProceedsAllocationStruct<Moment> {
+    pub category: BondProceedsCategory,
+    #[codec(compact)]
+    pub amount: EverUSDBalance,
+    /// Optional file (stored in filesign) with evidence of spending
+    pub evidence: Option<FileId>,
+    #[codec(compact)]
+    pub allocation_date: Moment,
+}
+
+pub type BondProceedsAllocationStructOf<T> = BondProceedsAllocationStruct<<T as pallet_timestamp::Config>::Moment>;
+
+/// Total allocated proceeds of the category
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondProceedsCategoryReport {
+    pub category: BondProceedsCategory,
+    /// Max amount of proceeds, allocated to the category. Zero value means no cap
+    #[codec(compact)]
+    pub cap: EverUSDBalance,
+    #[codec(compact)]
+    pub allocated: EverUSDBalance,
+}
+
+/// Use of proceeds report of the bond, returned by BondApi::get_proceeds_report
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondProceedsReport<Moment> {
+    /// Proceeds, raised by the bond, without issuance fees
+    #[codec(compact)]
+    pub raised: EverUSDBalance,
+    /// Proceeds, released to the Issuer
+    #[codec(compact)]
+    pub allocated: EverUSDBalance,
+    pub categories: Vec<BondProceedsCategoryReport>,
+    pub allocations: Vec<BondProceedsAllocationStruct<Moment>>,
+}
+
+impl<AccountId> BondFeeStruct<AccountId> {
+    /// Returns the fee rate of given kind, ppm
+    pub fn rate(&self, kind: BondFeeKind) -> BondInterest {
+        match kind {
+            BondFeeKind::ISSUANCE => self.issuance_fee,
+            BondFeeKind::COUPON => self.coupon_fee,
+            BondFeeKind::TRADE => self.trade_fee,
+        }
+    }
+
+    /// Returns the fee of given kind, charged on the amount
+    pub fn fee(&self, kind: BondFeeKind, amount: EverUSDBalance) -> EverUSDBalance {
+        (amount as u128 * self.rate(kind) as u128 / FULL_PRINCIPAL as u128) as EverUSDBalance
+    }
+}
+
+pub type BondFeeStructOf<T> = BondFeeStruct<<T as frame_system::Config>::AccountId>;
+
+/// How Bond Units are allocated among bookbuilding orders, if the bond is oversubscribed
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, RuntimeDebug, TypeInfo)]
+#[allow(non_camel_case_types)]
+pub enum BondAllocationRule {
+    /// Every order is filled in proportion to its size, rounding leftovers
+    /// are given to earlier orders
+    PRO_RATA,
+    /// Orders are filled in full in order of placement
+    TIME_PRIORITY,
+}
+
+impl BondAllocationRule {
+    /// Allocates "available" Bond Units among orders, sorted by placement.
+    /// Returns the number of Bond Units for each order
+    pub fn allocate(&self, available: BondUnitAmount, orders: &[BondUnitAmount]) -> Vec<BondUnitAmount> {
+        let total: u64 = orders.iter().map(|&units| units as u64).sum();
+        if total <= available as u64 {
+            return orders.to_vec();
+        }
+        match self {
+            BondAllocationRule::PRO_RATA => {
+                let mut allocation: Vec<BondUnitAmount> = orders
+                    .iter()
+                    .map(|&units| (units as u64 * available as u64 / total) as BondUnitAmount)
+                    .collect();
+                // the rest is less than the number of orders
+                let mut rest = available - allocation.iter().sum::<BondUnitAmount>();
+                for (allocated, &units) in allocation.iter_mut().zip(orders) {
+                    if rest == 0 {
+                        break;
+                    }
+                    if *allocated < units {
+                        *allocated += 1;
+                        rest -= 1;
+                    }
+                }
+                allocation
+            }
+            BondAllocationRule::TIME_PRIORITY => {
+                let mut rest = available;
+                orders
+                    .iter()
+                    .map(|&units| {
+                        let allocated = min(units, rest);
+                        rest -= allocated;
+                        allocated
+                    })
+                    .collect()
+            }
+        }
+    }
 }
 
 pub type BondInnerStructOf<T> =
@@ -247,6 +594,14 @@ impl<Moment, Hash, AccountId> BondInnerStruct<Moment, Hash, AccountId> {
             && self.impact_data_send_period == other.impact_data_send_period
             && self.payment_period == other.payment_period
             && self.bond_finishing_period == other.bond_finishing_period
+            && self.call_schedule == other.call_schedule
+            && self.principal_schedule == other.principal_schedule
+            && self.discount == other.discount
+            && self.impact_kpis == other.impact_kpis
+            && self.interest_rate_curve == other.interest_rate_curve
+            && self.bookbuilding == other.bookbuilding
+            && self.fees == other.fees
+            && self.use_of_proceeds == other.use_of_proceeds
     }
 
     /// Checks if the bond is stable based on the impact data send period - 
@@ -255,6 +610,18 @@ impl<Moment, Hash, AccountId> BondInnerStruct<Moment, Hash, AccountId> {
         self.impact_data_send_period == 0
     }
 
+    /// Checks if the bond is zero-coupon bond, sold at discount
+    pub fn is_zero_coupon(&self) -> bool {
+        self.discount.is_some()
+    }
+
+    /// Returns bond maturity period (start_period + bond_duration * payment_period), sec
+    pub fn maturity_period(&self) -> BondPeriod {
+        self.start_period
+            .unwrap_or(0)
+            .saturating_add(self.payment_period.saturating_mul(self.bond_duration))
+    }
+
     /// Checks common bounds for all bond parameters
     fn are_common_values_valid(&self, time_step: BondPeriod) -> bool {
         match &self.carbon_metadata {
@@ -266,6 +633,40 @@ impl<Moment, Hash, AccountId> BondInnerStruct<Moment, Hash, AccountId> {
             }
         }
 
+        if let Some(call) = &self.call_schedule {
+            if call.earliest_call_period == 0
+                || call.earliest_call_period > self.bond_duration
+                || call.call_premium > MAX_CALL_PREMIUM
+            {
+                return false;
+            }
+        }
+
+        if let Some(schedule) = &self.principal_schedule {
+            if schedule.len() != self.bond_duration as usize
+                || schedule.iter().map(|&share| share as u64).sum::<u64>() > FULL_PRINCIPAL as u64
+            {
+                return false;
+            }
+        }
+
+        if self.fees.len() > MAX_BOND_FEES
+            || [BondFeeKind::ISSUANCE, BondFeeKind::COUPON, BondFeeKind::TRADE].iter().any(|&kind| {
+                self.fees.iter().map(|fee| fee.rate(kind) as u64).sum::<u64>() > MAX_BOND_FEE as u64
+            })
+        {
+            return false;
+        }
+
+        if self.use_of_proceeds.len() > MAX_BOND_PROCEEDS_CATEGORIES
+            || self.use_of_proceeds.iter().enumerate().any(|(i, item)| {
+                item.cap > FULL_PRINCIPAL
+                    || self.use_of_proceeds[..i].iter().any(|other| other.category == item.category)
+            })
+        {
+            return false;
+        }
+
         self.payment_period >= MIN_PAYMENT_PERIOD * time_step &&
         self.bond_duration >= MIN_BOND_DURATION &&
         self.bond_units_base_price > 0
@@ -280,7 +681,35 @@ impl<Moment, Hash, AccountId> BondInnerStruct<Moment, Hash, AccountId> {
             return false;
         }
 
-        if self.is_stable() {
+        if self.is_zero_coupon() {
+            // zero-coupon bond doesn't pay coupon yield, so it has neither
+            // interest rate nor impact data. Principal is paid at once at maturity date
+            self.discount.unwrap_or(0) > 0
+                && self.discount.unwrap_or(0) < FULL_PRINCIPAL
+                && self.is_stable()
+                && self.interest_rate_base_value == 0
+                && self.impact_data_max_deviation_cap.is_none()
+                && self.impact_data_max_deviation_floor.is_none()
+                && self.interest_rate_penalty_for_missed_report.is_none()
+                && self.interest_rate_margin_cap.is_none()
+                && self.interest_rate_margin_floor.is_none()
+                && self.interest_rate_start_period_value.is_none()
+                && self.interest_pay_period.is_none()
+                && self.impact_data_baseline.is_empty()
+                && self.impact_kpis.is_empty()
+                && self.interest_rate_curve == BondInterestRateCurve::LINEAR
+                && self.call_schedule.is_none()
+                && self.principal_schedule.is_none()
+                && self.bond_units_mincap_amount > 0
+                && self.bond_units_maxcap_amount >= self.bond_units_mincap_amount
+                && is_period_muliple_of_time_step(self.payment_period, time_step)
+                && is_period_muliple_of_time_step(self.start_period.unwrap_or(0), time_step)
+                && is_period_muliple_of_time_step(self.bond_finishing_period, time_step)
+                && self
+                    .bond_units_base_price
+                    .saturating_mul(self.bond_units_maxcap_amount as EverUSDBalance)
+                    < EverUSDBalance::MAX
+        } else if self.is_stable() {
             // in case of the stable bond, because the below parameters are optional,
             // they must be None
             self.impact_data_max_deviation_cap.is_none()
@@ -289,6 +718,8 @@ impl<Moment, Hash, AccountId> BondInnerStruct<Moment, Hash, AccountId> {
                 && self.interest_rate_margin_floor.is_none()
                 && self.interest_rate_start_period_value.is_none()
                 && self.interest_pay_period.is_none()
+                && self.impact_kpis.is_empty()
+                && self.interest_rate_curve == BondInterestRateCurve::LINEAR
         } else {
             // ensure that for a non-stable bond the below
             // parameters have values
@@ -318,6 +749,13 @@ impl<Moment, Hash, AccountId> BondInnerStruct<Moment, Hash, AccountId> {
                     bl <= self.impact_data_max_deviation_cap
                         && bl >= self.impact_data_max_deviation_floor
                 })
+                && self.impact_kpis.len() <= MAX_IMPACT_KPIS
+                && self.impact_kpis.iter().map(|kpi| kpi.weight as u64).sum::<u64>() < FULL_KPI_WEIGHT as u64
+                && self.impact_kpis.iter().all(|kpi| kpi.is_valid(self.bond_duration))
+                && self.interest_rate_curve.is_valid(
+                    self.interest_rate_margin_floor.unwrap_or(0),
+                    self.interest_rate_margin_cap.unwrap_or(0),
+                )
         }
     }
 }
@@ -400,7 +838,7 @@ pub struct BondStruct<AccountId, Moment, Hash> {
     /// Moment, when bond became active (moved to BondState::ACTIVE)
     #[codec(compact)]
     pub active_start_date: Moment,
-    /// Bond current state (PREPARE, BOOKING, ACTIVE, BANKRUPT, FINISHED)
+    /// Bond current state (PREPARE, BOOKING, ACTIVE, BANKRUPT, FINISHED, DEFAULT_SETTLED)
     pub state: BondState,
 
     //#Bond ledger
@@ -434,6 +872,101 @@ impl<AccountId, Moment, Hash> BondStruct<AccountId, Moment, Hash> {
     pub fn par_value(&self, unit_amount: BondUnitAmount) -> EverUSDBalance {
         unit_amount as EverUSDBalance * self.inner.bond_units_base_price as EverUSDBalance
     }
+    /// Returns the price of one Bond Unit at the bond sale in BondState::BOOKING:
+    /// par value minus discount for zero-coupon bond, par value otherwise
+    pub fn unit_issue_price(&self) -> EverUSDBalance {
+        let discount = self.inner.discount.unwrap_or(0);
+        (self.inner.bond_units_base_price as u128 * (FULL_PRINCIPAL - min(discount, FULL_PRINCIPAL)) as u128
+            / FULL_PRINCIPAL as u128) as EverUSDBalance
+    }
+
+    /// Returns the price of unit_amount Bond units at the bond sale in BondState::BOOKING
+    #[inline]
+    pub fn issue_price(&self, unit_amount: BondUnitAmount) -> EverUSDBalance {
+        unit_amount as EverUSDBalance * self.unit_issue_price()
+    }
+
+    /// Returns accreted value of one zero-coupon Bond Unit "moment" seconds after activation.
+    /// The value grows linearly from issue price to par value at maturity date
+    pub fn unit_accreted_value(&self, moment: BondPeriod) -> EverUSDBalance {
+        let issue_price = self.unit_issue_price();
+        let maturity = self.inner.maturity_period();
+        if maturity == 0 || moment >= maturity {
+            return self.inner.bond_units_base_price;
+        }
+        let discount = self.inner.bond_units_base_price.saturating_sub(issue_price);
+        issue_price + (discount as u128 * moment as u128 / maturity as u128) as EverUSDBalance
+    }
+
+    /// Returns the price of one Bond Unit "moment" seconds after activation during the period:
+    /// accreted value for zero-coupon bond, outstanding par value otherwise
+    pub fn unit_price(&self, moment: BondPeriod, period: BondPeriodNumber) -> EverUSDBalance {
+        if self.inner.is_zero_coupon() {
+            self.unit_accreted_value(moment)
+        } else {
+            self.unit_par_value(period)
+        }
+    }
+
+    /// Returns implied annual yield of zero-coupon bond, bought at issue price
+    /// and held until maturity date. Measured in the same units as interest rates
+    /// (see interest_rate_base_value). Zero for coupon bonds
+    pub fn implied_yield(&self, time_step: BondPeriod) -> BondInterest {
+        let issue_price = self.unit_issue_price() as u128;
+        let maturity_days = (self.inner.maturity_period() / max(time_step, 1)) as u128;
+        if !self.inner.is_zero_coupon() || issue_price == 0 || maturity_days == 0 {
+            return 0;
+        }
+        let discount = self.inner.bond_units_base_price as u128 - issue_price;
+        (discount * 100_000 * 365 / issue_price / maturity_days).saturated_into::<BondInterest>()
+    }
+
+    /// Returns principal of one Bond Unit, repaid at the end of the period
+    /// according to "principal_schedule". Zero for non-amortizing bond
+    pub fn unit_principal(&self, period: BondPeriodNumber) -> EverUSDBalance {
+        match &self.inner.principal_schedule {
+            Some(schedule) if period > 0 => schedule
+                .get(period as usize - 1)
+                .map_or(0, |&share| {
+                    (self.inner.bond_units_base_price as u128 * share as u128 / FULL_PRINCIPAL as u128)
+                        as EverUSDBalance
+                }),
+            _ => 0,
+        }
+    }
+
+    /// Returns outstanding par value of one Bond Unit during the period:
+    /// base price minus principal repaid in all previous periods
+    pub fn unit_par_value(&self, period: BondPeriodNumber) -> EverUSDBalance {
+        let repaid: EverUSDBalance = (0..period).map(|p| self.unit_principal(p)).sum();
+        self.inner.bond_units_base_price.saturating_sub(repaid)
+    }
+
+    /// Returns outstanding par value of unit_amount Bond units during the period
+    #[inline]
+    pub fn outstanding_par_value(&self, unit_amount: BondUnitAmount, period: BondPeriodNumber) -> EverUSDBalance {
+        unit_amount as EverUSDBalance * self.unit_par_value(period)
+    }
+
+    /// Returns call premium of unit_amount Bond units, called during the period,
+    /// zero for non-callable bond. Premium is calculated on outstanding par value
+    #[inline]
+    pub fn call_premium_value(&self, unit_amount: BondUnitAmount, period: BondPeriodNumber) -> EverUSDBalance {
+        let premium = self.inner.call_schedule.as_ref().map_or(0, |call| call.call_premium);
+        (self.unit_par_value(period) as u128 * premium as u128 / MAX_CALL_PREMIUM as u128) as EverUSDBalance
+            * unit_amount as EverUSDBalance
+    }
+
+    /// Returns Issuer, Manager, Auditor and ImpactReporter of the bond
+    pub fn role_accounts(&self) -> Vec<&AccountId> {
+        let mut accounts = Vec::with_capacity(4);
+        accounts.push(&self.issuer);
+        accounts.extend(self.manager.iter());
+        accounts.extend(self.auditor.iter());
+        accounts.extend(self.impact_reporter.iter());
+        accounts
+    }
+
     /// Returns true if bond has unpaid debt
     #[inline]
     pub fn is_shortage(&self) -> bool {
@@ -467,6 +1000,59 @@ impl<AccountId, Moment, Hash> BondStruct<AccountId, Moment, Hash> {
         self.bond_debit -= amount;
     }
 
+    /// Applies restructuring to bond terms. "period" is the current bond period.
+    /// Returns false if restructuring cannot be applied to the bond.
+    /// Coupon holiday doesn't change bond terms and is only checked here
+    pub fn restructure(&mut self, restructuring: &BondRestructuring, period: BondPeriodNumber) -> bool {
+        match *restructuring {
+            BondRestructuring::MATURITY_EXTENSION(periods) => {
+                if periods == 0 || period >= self.get_periods() {
+                    return false;
+                }
+                let inner = &mut self.inner;
+                inner.bond_duration = match inner.bond_duration.checked_add(periods) {
+                    Some(bond_duration) => bond_duration,
+                    None => return false,
+                };
+                let new_len = inner.bond_duration as usize;
+                let baseline = inner.impact_data_baseline.last().copied().flatten();
+                if !inner.impact_data_baseline.is_empty() {
+                    inner.impact_data_baseline.resize(new_len, baseline);
+                }
+                for kpi in inner.impact_kpis.iter_mut() {
+                    let baseline = kpi.impact_data_baseline.last().copied().flatten();
+                    kpi.impact_data_baseline.resize(new_len, baseline);
+                }
+                if let Some(schedule) = inner.principal_schedule.as_mut() {
+                    schedule.resize(new_len, 0);
+                }
+                true
+            }
+            BondRestructuring::RATE_CHANGE(interest_rate) => {
+                let inner = &mut self.inner;
+                // interest rate of zero-coupon bond, curve points and rates of additional KPIs
+                // don't depend on interest_rate_base_value
+                if inner.is_zero_coupon()
+                    || inner.interest_rate_curve != BondInterestRateCurve::LINEAR
+                    || !inner.impact_kpis.is_empty()
+                {
+                    return false;
+                }
+                if !inner.is_stable()
+                    && (interest_rate < inner.interest_rate_margin_floor.unwrap_or(0)
+                        || interest_rate > inner.interest_rate_margin_cap.unwrap_or(0))
+                {
+                    return false;
+                }
+                inner.interest_rate_base_value = interest_rate;
+                true
+            }
+            BondRestructuring::COUPON_HOLIDAY(periods) => {
+                periods > 0 && period.saturating_add(periods) <= self.get_periods()
+            }
+        }
+    }
+
     #[inline]
     pub fn get_periods(&self) -> BondPeriodNumber {
         if self.inner.start_period.unwrap_or(0) == 0 {
@@ -488,17 +1074,84 @@ impl<AccountId, Moment, Hash> BondStruct<AccountId, Moment, Hash> {
 
     /// Calculate coupon effective interest rate using impact_data.
     /// This method moves interest_rate up and down when good or bad impact_data
-    /// is sent to bond and approved by Auditor
+    /// is sent to bond and approved by Auditor, according to "interest_rate_curve"
     pub fn calc_effective_interest_rate(
         &self,
         impact_data_baseline: u64,
         impact_data: u64,
     ) -> BondInterest {
+        match &self.inner.interest_rate_curve {
+            BondInterestRateCurve::LINEAR => self.calc_kpi_interest_rate(
+                impact_data_baseline,
+                self.inner.impact_data_max_deviation_cap.unwrap_or(0),
+                self.inner.impact_data_max_deviation_floor.unwrap_or(0),
+                impact_data,
+            ),
+            BondInterestRateCurve::STEP(points) => points
+                .iter()
+                .rev()
+                .find(|point| point.impact_data <= impact_data)
+                .map_or(self.inner.interest_rate_margin_cap.unwrap_or(0), |point| point.interest_rate),
+            BondInterestRateCurve::PIECEWISE_LINEAR(points) => {
+                match points.iter().position(|point| point.impact_data > impact_data) {
+                    // below the table
+                    Some(0) => points[0].interest_rate,
+                    Some(index) => {
+                        let (left, right) = (&points[index - 1], &points[index]);
+                        let rate = left.interest_rate as i128
+                            + (right.interest_rate as i128 - left.interest_rate as i128)
+                                * (impact_data - left.impact_data) as i128
+                                / (right.impact_data - left.impact_data) as i128;
+                        rate as BondInterest
+                    }
+                    // above the table
+                    None => points.last().map_or(self.inner.interest_rate_base_value, |point| point.interest_rate),
+                }
+            }
+        }
+    }
+
+    /// Calculate coupon effective interest rate of the period using impact report.
+    /// For the bond with additional KPIs, interest rates of all KPIs are combined
+    /// according to their weights. "index" is the number of the report period
+    pub fn calc_impact_interest_rate(&self, index: usize, report: &BondImpactReportStruct) -> BondInterest {
         let inner = &self.inner;
+        let baseline = inner.impact_data_baseline.get(index).copied().flatten().unwrap_or(0);
+        let interest_rate = self.calc_effective_interest_rate(baseline, report.impact_data);
+        if inner.impact_kpis.is_empty() {
+            return interest_rate;
+        }
+
+        let kpi_weight: u128 = inner.impact_kpis.iter().map(|kpi| kpi.weight as u128).sum();
+        let weighted_rate = inner.impact_kpis.iter().zip(report.kpi_data.iter()).fold(
+            interest_rate as u128 * (FULL_KPI_WEIGHT as u128).saturating_sub(kpi_weight),
+            |acc, (kpi, &impact_data)| {
+                let baseline = kpi.impact_data_baseline.get(index).copied().flatten().unwrap_or(0);
+                let kpi_rate = self.calc_kpi_interest_rate(
+                    baseline,
+                    kpi.impact_data_max_deviation_cap,
+                    kpi.impact_data_max_deviation_floor,
+                    impact_data,
+                );
+                acc + kpi_rate as u128 * kpi.weight as u128
+            },
+        );
+        (weighted_rate / FULL_KPI_WEIGHT as u128) as BondInterest
+    }
 
-        if impact_data >= inner.impact_data_max_deviation_cap.unwrap_or(0) {
+    /// Calculate interest rate for the KPI with given baseline, cap and floor
+    fn calc_kpi_interest_rate(
+        &self,
+        impact_data_baseline: u64,
+        impact_data_max_deviation_cap: u64,
+        impact_data_max_deviation_floor: u64,
+        impact_data: u64,
+    ) -> BondInterest {
+        let inner = &self.inner;
+
+        if impact_data >= impact_data_max_deviation_cap {
             inner.interest_rate_margin_floor.unwrap_or(0)
-        } else if impact_data <= inner.impact_data_max_deviation_floor.unwrap_or(0) {
+        } else if impact_data <= impact_data_max_deviation_floor {
             inner.interest_rate_margin_cap.unwrap_or(0)
         } else if impact_data == impact_data_baseline {
             inner.interest_rate_base_value
@@ -506,13 +1159,13 @@ impl<AccountId, Moment, Hash> BondStruct<AccountId, Moment, Hash> {
             inner.interest_rate_base_value
                 - ((impact_data - impact_data_baseline) as u128
                     * (inner.interest_rate_base_value - inner.interest_rate_margin_floor.unwrap_or(0)) as u128
-                    / (inner.impact_data_max_deviation_cap.unwrap_or(0) - impact_data_baseline) as u128)
+                    / (impact_data_max_deviation_cap - impact_data_baseline) as u128)
                     as BondInterest
         } else {
             inner.interest_rate_base_value
                 + ((impact_data_baseline - impact_data) as u128
                     * (inner.interest_rate_margin_cap.unwrap_or(0) - inner.interest_rate_base_value) as u128
-                    / (impact_data_baseline - inner.impact_data_max_deviation_floor.unwrap_or(0)) as u128)
+                    / (impact_data_baseline - impact_data_max_deviation_floor) as u128)
                     as BondInterest
         }
     }
@@ -548,9 +1201,70 @@ impl<AccountId, Moment: UniqueSaturatedInto<u64> + AtLeast32Bit + Copy, Hash>
             }
         }
     }
+
+    /// Returns true if maturity date and "bond_finishing_period" after it are passed.
+    /// (maturity = active_start_date + start_period + bond_duration * payment_period)
+    pub fn is_finishing_period_expired(&self, now: Moment) -> bool {
+        if !matches!(self.state, BondState::ACTIVE | BondState::BANKRUPT)
+            || now < self.active_start_date
+        {
+            return false;
+        }
+        let moment = (now - self.active_start_date).saturated_into::<u64>() / 1000_u64;
+        let finish = self.inner.start_period.unwrap_or(0) as u64
+            + self.inner.bond_duration as u64 * self.inner.payment_period as u64
+            + self.inner.bond_finishing_period as u64;
+        moment >= finish
+    }
+
+    /// Returns deadlines of the active bond, which are not passed yet, as absolute timestamps
+    /// sorted by deadline. Impact reports are expected for every period of non-stable bond,
+    /// coupon yield is paid at the beginning of every period after the first one
+    pub fn deadlines(&self, bond: &BondId, now: Moment) -> Vec<BondDeadline<Moment>> {
+        let mut deadlines = Vec::new();
+        if !matches!(self.state, BondState::ACTIVE | BondState::BANKRUPT) {
+            return deadlines;
+        }
+        let moment = |period: BondPeriod| self.active_start_date + (period as u64 * 1000).saturated_into::<Moment>();
+        let mut push = |kind, period, opens: BondPeriod, deadline: BondPeriod| {
+            let deadline = moment(deadline);
+            if deadline > now {
+                deadlines.push(BondDeadline { bond: *bond, kind, period, opens: moment(opens), deadline });
+            }
+        };
+
+        // bond without start period has no period 0
+        let first = if self.inner.start_period.unwrap_or(0) == 0 { 1 } else { 0 };
+        for period in first..=self.inner.bond_duration {
+            let desc = match self.period_desc(period) {
+                Some(desc) => desc,
+                None => break,
+            };
+            if !self.inner.is_stable() && period < self.inner.bond_duration {
+                for kind in [BondDeadlineKind::IMPACT_REPORT, BondDeadlineKind::IMPACT_REPORT_APPROVAL] {
+                    push(kind, period, desc.impact_data_send_period, desc.payment_period);
+                }
+            }
+            if period > first && self.inner.interest_pay_period.is_some() {
+                push(BondDeadlineKind::COUPON_PAYMENT, period, desc.start_period, desc.interest_pay_period);
+            }
+        }
+
+        let maturity = self.inner.maturity_period();
+        push(BondDeadlineKind::MATURITY, self.inner.bond_duration, maturity, maturity);
+        push(
+            BondDeadlineKind::FINISHING_PERIOD_END,
+            self.inner.bond_duration,
+            maturity,
+            maturity.saturating_add(self.inner.bond_finishing_period),
+        );
+        deadlines.sort_by_key(|deadline| deadline.deadline);
+        deadlines
+    }
 }
 
 /// Struct, accumulating per-account coupon_yield for each period num
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct AccountYield {
     #[codec(compact)]
@@ -562,6 +1276,7 @@ pub struct AccountYield {
 /// Pack of bond units, bought at given time, belonging to given Bearer.
 /// Created when performed a deal to aquire bond uints (booking, buy from bond, buy from market).
 /// Contains data about amount of bondholder's acquired bond units, aquisition period and coupon_yield
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct BondUnitPackage {
     /// amount of bond units
@@ -578,6 +1293,7 @@ pub struct BondUnitPackage {
 /// Struct with impact_data sent to bond. In the future can become
 /// more complicated for other types of impact_data and processing logic.
 /// Field "signed" is set to true by Auditor, when impact_data is verified.
+/// Field "rejection" keeps the last Auditor's rejection of impact_data for this period.
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct BondImpactReportStruct {
@@ -585,7 +1301,10 @@ pub struct BondImpactReportStruct {
     pub create_period: BondPeriod,
     #[codec(compact)]
     pub impact_data: u64,
+    /// values of additional KPIs (see BondInnerStruct::impact_kpis)
+    pub kpi_data: Vec<u64>,
     pub signed: bool,
+    pub rejection: Option<BondImpactReportRejection>,
 }
 
 impl Default for BondImpactReportStruct {
@@ -593,16 +1312,71 @@ impl Default for BondImpactReportStruct {
         BondImpactReportStruct {
             create_period: 0,
             impact_data: 0,
+            kpi_data: Vec::new(),
             signed: false,
+            rejection: None,
         }
     }
 }
 
+/// Max length of the reason, sent by Auditor with rejection of impact_data
+pub const MAX_REJECTION_REASON_LEN: usize = 256;
+
+/// Auditor's rejection of impact_data, sent to bond
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondImpactReportRejection {
+    /// Rejected value of impact_data
+    #[codec(compact)]
+    pub impact_data: u64,
+    /// Human-readable reason of the rejection
+    pub reason: Vec<u8>,
+    /// Optional file (stored in filesign) with evidence
+    pub evidence: Option<FileId>,
+}
+
+/// Action, performed with impact report
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+#[allow(non_camel_case_types)]
+pub enum BondImpactReportAction {
+    /// impact_data was sent by Issuer or ImpactReporter
+    SENT,
+    /// impact_data was approved by Auditor
+    APPROVED,
+    /// impact_data was rejected by Auditor
+    REJECTED(BondImpactReportRejection),
+}
+
+/// Record of impact report history. History keeps all sent, approved and
+/// rejected impact_data for each period of the bond
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondImpactReportHistoryItem<AccountId, Moment> {
+    /// Account, performed the action
+    pub account: AccountId,
+    #[codec(compact)]
+    pub moment: Moment,
+    #[codec(compact)]
+    pub impact_data: u64,
+    /// values of additional KPIs
+    pub kpi_data: Vec<u64>,
+    pub action: BondImpactReportAction,
+}
+
+pub type BondImpactReportHistoryItemOf<T> = BondImpactReportHistoryItem<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_timestamp::Config>::Moment,
+>;
+
 /// Struct, representing pack of bond units for sale.
 /// Can include target bearer (to sell bond units only to given person)
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct BondUnitSaleLotStruct<AccountId, Moment> {
+    /// Unique lot identifier, assigned by the pallet when the lot is created
+    #[codec(compact)]
+    pub id: BondUnitSaleLotId,
     /// Sale lot is available for buy only before this deadline
     #[codec(compact)]
     pub deadline: Moment,
@@ -630,7 +1404,259 @@ pub type BondUnitSaleLotStructOf<T> = BondUnitSaleLotStruct<
     <T as pallet_timestamp::Config>::Moment,
 >;
 
+/// Struct, representing standing order to buy bond units for given price.
+/// EverUSD of the bidder are reserved until the bid is filled, cancelled or expired
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondUnitPurchaseBidStruct<Moment> {
+    /// Unique bid identifier, assigned by the pallet when the bid is created
+    #[codec(compact)]
+    pub id: BondUnitPurchaseBidId,
+    /// Bid can be filled only before this deadline
+    #[codec(compact)]
+    pub deadline: Moment,
+    /// Amount of bond units to buy
+    #[codec(compact)]
+    pub bond_units: BondUnitAmount,
+    /// Total price of this bid, reserved on the bidder's account
+    #[codec(compact)]
+    pub amount: EverUSDBalance,
+}
+
+impl<Moment: core::cmp::PartialOrd> Expired<Moment> for BondUnitPurchaseBidStruct<Moment> {
+    fn is_expired(&self, now: Moment) -> bool {
+        self.deadline < now
+    }
+}
+
+pub type BondUnitPurchaseBidStructOf<T> =
+    BondUnitPurchaseBidStruct<<T as pallet_timestamp::Config>::Moment>;
 
+/// Max number of bookbuilding orders of the bond
+pub const MAX_BOND_UNIT_ORDERS: u32 = 256;
+
+/// Bookbuilding order to buy bond units at bond activation.
+/// EverUSD of the Investor are reserved until the order is filled or cancelled
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondUnitOrderStruct {
+    /// Unique order identifier, assigned by the pallet. Defines priority of the order
+    #[codec(compact)]
+    pub id: BondUnitOrderId,
+    /// Amount of ordered bond units
+    #[codec(compact)]
+    pub bond_units: BondUnitAmount,
+    /// Issue price of ordered bond units, reserved on the Investor's account
+    #[codec(compact)]
+    pub amount: EverUSDBalance,
+}
+
+/// Share of issued bond units, which must vote for restructuring proposal
+/// to make the decision valid, ppm
+pub const RESTRUCTURING_QUORUM: BondInterest = 500_000;
+
+/// Change of bond terms, proposed by Issuer or Bond Arranger
+/// and approved by bondholders
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+#[allow(non_camel_case_types)]
+pub enum BondRestructuring {
+    /// Extends bond_duration by the number of periods. Baselines of the last period
+    /// are used for new periods, unpaid principal is repaid at new maturity date
+    MATURITY_EXTENSION(BondPeriodNumber),
+    /// Sets new interest_rate_base_value. Not applicable to zero-coupon bonds,
+    /// bonds with step or piecewise-linear interest rate curve and additional KPIs
+    RATE_CHANGE(BondInterest),
+    /// Coupon yield isn't accrued for the number of periods, starting from the current one
+    COUPON_HOLIDAY(BondPeriodNumber),
+}
+
+impl Default for BondRestructuring {
+    fn default() -> Self {
+        BondRestructuring::COUPON_HOLIDAY(0)
+    }
+}
+
+/// Bond restructuring proposal. Bondholders vote for the proposal
+/// until the deadline, weighted by bond units they hold
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondRestructuringProposalStruct<AccountId, Moment> {
+    /// Issuer or Bond Arranger, created the proposal
+    pub proposer: AccountId,
+    /// Proposed change of bond terms
+    pub restructuring: BondRestructuring,
+    /// Bondholders can vote only before this deadline
+    #[codec(compact)]
+    pub deadline: Moment,
+}
+
+impl<AccountId, Moment: core::cmp::PartialOrd> Expired<Moment> for BondRestructuringProposalStruct<AccountId, Moment> {
+    fn is_expired(&self, now: Moment) -> bool {
+        self.deadline < now
+    }
+}
+
+pub type BondRestructuringProposalStructOf<T> = BondRestructuringProposalStruct<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_timestamp::Config>::Moment,
+>;
+
+/// Final ledger snapshot of the bankrupt bond, moved to BondState::DEFAULT_SETTLED.
+/// Recovery fund is distributed among bondholders pro-rata to their claims
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondDefaultSettlementStruct<Moment> {
+    /// Moment, when bond was settled
+    #[codec(compact)]
+    pub settlement_date: Moment,
+    /// The number of periods with accrued coupon yield
+    #[codec(compact)]
+    pub period: BondPeriodNumber,
+    /// Bond fund at the moment of settlement
+    #[codec(compact)]
+    pub bond_debit: EverUSDBalance,
+    /// Accrued bond liabilities at the moment of settlement
+    #[codec(compact)]
+    pub bond_credit: EverUSDBalance,
+    /// Coupon yield, distributed among bondholders before settlement
+    #[codec(compact)]
+    pub coupon_yield: EverUSDBalance,
+    /// Undistributed bond fund, including recovered EverUSD
+    #[codec(compact)]
+    pub recovery_fund: EverUSDBalance,
+    /// Outstanding par value and accrued unpaid coupon yield of all bond units
+    #[codec(compact)]
+    pub total_claim: EverUSDBalance,
+}
+
+impl<Moment> BondDefaultSettlementStruct<Moment> {
+    /// Returns the share of recovery fund, to be paid for the bondholder claim
+    pub fn recovery_amount(&self, claim: EverUSDBalance) -> EverUSDBalance {
+        if self.recovery_fund >= self.total_claim {
+            claim
+        } else {
+            (claim as u128 * self.recovery_fund as u128 / self.total_claim as u128) as EverUSDBalance
+        }
+    }
+}
+
+/// Reference price of bond units at the current moment, returned by BondApi::get_bond_unit_price
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondUnitPriceStruct {
+    /// Effective interest rate of the current period
+    #[codec(compact)]
+    pub interest_rate: BondInterest,
+    /// Coupon yield, accrued since the beginning of the current period
+    #[codec(compact)]
+    pub accrued_interest: EverUSDBalance,
+    /// Outstanding par value, or accreted value of zero-coupon bond
+    #[codec(compact)]
+    pub clean_price: EverUSDBalance,
+    /// Clean price with accrued interest
+    #[codec(compact)]
+    pub dirty_price: EverUSDBalance,
+}
+
+pub type BondDefaultSettlementStructOf<T> = BondDefaultSettlementStruct<<T as pallet_timestamp::Config>::Moment>;
+
+/// Position of the account in the bond: held bond units, coupon yield and open orders
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondPositionStruct<AccountId, Moment> {
+    pub bond: BondId,
+    pub state: BondState,
+    /// Bond units of all BondUnitPackage-s of the account
+    #[codec(compact)]
+    pub bond_units: BondUnitAmount,
+    /// Outstanding par value of the bond units
+    #[codec(compact)]
+    pub par_value: EverUSDBalance,
+    /// Coupon yield of passed periods, which hasn't been paid to the account yet
+    #[codec(compact)]
+    pub unrealised_coupon: EverUSDBalance,
+    /// Coupon yield, already paid to the account
+    pub last_coupon_yield: AccountYield,
+    pub lots: Vec<BondUnitSaleLotStruct<AccountId, Moment>>,
+    pub bids: Vec<BondUnitPurchaseBidStruct<Moment>>,
+    pub order: Option<BondUnitOrderStruct>,
+}
+
+pub type BondPositionStructOf<T> = BondPositionStruct<<T as frame_system::Config>::AccountId, <T as pallet_timestamp::Config>::Moment>;
+
+/// EverUSD balances and bond positions of the account, returned by BondApi::get_portfolio
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondPortfolioStruct<AccountId, Moment> {
+    #[codec(compact)]
+    pub everusd_balance: EverUSDBalance,
+    /// EverUSD, reserved for purchase bids, bookbuilding orders and use of proceeds
+    #[codec(compact)]
+    pub everusd_reserved: EverUSDBalance,
+    pub positions: Vec<BondPositionStruct<AccountId, Moment>>,
+}
+
+pub type BondPortfolioStructOf<T> = BondPortfolioStruct<<T as frame_system::Config>::AccountId, <T as pallet_timestamp::Config>::Moment>;
+
+
+/// Two-letter ISO 3166-1 country code of the investor jurisdiction
+pub type Jurisdiction = [u8; 2];
+
+/// Maximum number of jurisdictions, permitted for the bond
+pub const MAX_BOND_JURISDICTIONS: usize = 32;
+
+/// Restrictions on acquisition of bond units, set by the bond Issuer.
+/// Default value doesn't restrict acquisition
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondTransferRestrictionStruct {
+    /// Only whitelisted investors can acquire bond units
+    pub whitelist_only: bool,
+    /// Jurisdictions of whitelisted investors, permitted to acquire bond units.
+    /// Empty list permits any jurisdiction
+    pub jurisdictions: Vec<Jurisdiction>,
+    /// Maximum number of bond units, held by one investor. Zero value means no limit
+    #[codec(compact)]
+    pub max_holding: BondUnitAmount,
+}
+
+impl BondTransferRestrictionStruct {
+    pub fn is_valid(&self) -> bool {
+        self.jurisdictions.len() <= MAX_BOND_JURISDICTIONS
+    }
+
+    /// Checks if the investor, whitelisted with given jurisdiction (if any),
+    /// is eligible to acquire bond units
+    pub fn is_eligible(&self, jurisdiction: Option<&Jurisdiction>) -> bool {
+        match jurisdiction {
+            Some(jurisdiction) => self.jurisdictions.is_empty() || self.jurisdictions.contains(jurisdiction),
+            None => !self.whitelist_only && self.jurisdictions.is_empty(),
+        }
+    }
+
+    /// Checks if the investor can hold given amount of bond units
+    pub fn is_holding_permitted(&self, bond_units: BondUnitAmount) -> bool {
+        self.max_holding == 0 || bond_units <= self.max_holding
+    }
+}
+
+/// Checks every acquisition of bond units: purchase from the Issuer (`from` is None),
+/// bookbuilding allocation, sale lot and purchase bid settlement
+pub trait BondTransferGuard<AccountId> {
+    fn ensure_can_acquire(
+        bond: &BondId,
+        from: Option<&AccountId>,
+        to: &AccountId,
+        bond_units: BondUnitAmount,
+    ) -> DispatchResult;
+}
+
+impl<AccountId> BondTransferGuard<AccountId> for () {
+    fn ensure_can_acquire(_: &BondId, _: Option<&AccountId>, _: &AccountId, _: BondUnitAmount) -> DispatchResult {
+        Ok(())
+    }
+}
 
 #[impl_trait_for_tuples::impl_for_tuples(30)]
 pub trait OnAddBond<AccountId, Moment, Hash> {
@@ -13,6 +13,10 @@ pub trait WeightInfo {
     fn token_burn_request_revoke_everusd() -> Weight;
     fn token_burn_request_confirm_everusd() -> Weight;
     fn token_burn_request_decline_everusd() -> Weight;
+    fn everusd_transfer() -> Weight;
+    fn reserve_attestation_publish() -> Weight;
+    fn reserve_attestation_sign() -> Weight;
+    fn everusd_limit_set() -> Weight;
     fn bond_add_new() -> Weight;
     fn bond_set() -> Weight;
     fn bond_update() -> Weight;
@@ -20,17 +24,42 @@ pub trait WeightInfo {
     fn bond_unit_package_buy() -> Weight;
     fn bond_unit_package_return() -> Weight;
     fn bond_withdraw() -> Weight;
+    fn bond_unit_order_place() -> Weight;
+    fn bond_unit_order_cancel() -> Weight;
+    fn bond_transfer_restriction_set() -> Weight;
+    fn bond_whitelist_add() -> Weight;
+    fn bond_whitelist_remove() -> Weight;
     fn bond_activate() -> Weight;
     fn bond_impact_report_send() -> Weight;
     fn bond_impact_report_approve() -> Weight;
+    fn bond_impact_report_reject() -> Weight;
+    fn bond_impact_kpi_report_send() -> Weight;
+    fn bond_impact_kpi_report_approve() -> Weight;
     fn bond_redeem() -> Weight;
+    fn bond_call(a: u32) -> Weight;
     fn bond_declare_bankrupt() -> Weight;
+    fn bond_recovery_deposit() -> Weight;
+    fn bond_default_settle(a: u32) -> Weight;
+    fn bond_restructuring_propose() -> Weight;
+    fn bond_restructuring_vote() -> Weight;
+    fn bond_restructuring_finalize(v: u32) -> Weight;
     fn bond_accrue_coupon_yield() -> Weight;
     fn bond_revoke() -> Weight;
     fn bond_withdraw_everusd() -> Weight;
     fn bond_deposit_everusd() -> Weight;
+    fn fee_withdraw_everusd() -> Weight;
+    fn bond_proceeds_withdraw() -> Weight;
     fn bond_unit_lot_bid() -> Weight;
     fn bond_unit_lot_settle() -> Weight;
+    fn bond_unit_lot_cancel() -> Weight;
+    fn bond_unit_lot_update_price() -> Weight;
+    fn bond_unit_purchase_bid() -> Weight;
+    fn bond_unit_purchase_bid_cancel() -> Weight;
+    fn bond_unit_purchase_bid_settle() -> Weight;
+    fn purge_expired_request() -> Weight;
+    fn bond_lifecycle_check() -> Weight;
+    fn bond_unit_package_return_all() -> Weight;
+    fn bond_unit_order_allocate() -> Weight;
 }
 
 #[allow(clippy::unnecessary_cast)]
@@ -56,8 +85,8 @@ impl WeightInfo for () {
     }
     fn token_mint_request_create_everusd() -> Weight {
         (20000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().reads(19_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
     }
     fn token_mint_request_revoke_everusd() -> Weight {
         (10000_u64 as Weight)
@@ -67,7 +96,7 @@ impl WeightInfo for () {
     fn token_mint_request_confirm_everusd() -> Weight {
         (10000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(5_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(4_u64 as Weight))
     }
     fn token_mint_request_decline_everusd() -> Weight {
         (10000_u64 as Weight)
@@ -76,8 +105,8 @@ impl WeightInfo for () {
     }
     fn token_burn_request_create_everusd() -> Weight {
         (10000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().reads(20_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
     }
     fn token_burn_request_revoke_everusd() -> Weight {
         (10000_u64 as Weight)
@@ -86,14 +115,34 @@ impl WeightInfo for () {
     }
     fn token_burn_request_confirm_everusd() -> Weight {
         (10000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(2_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(4_u64 as Weight))
     }
     fn token_burn_request_decline_everusd() -> Weight {
         (10000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
             .saturating_add(DbWeight::get().writes(1_u64 as Weight))
     }
+    fn everusd_transfer() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn reserve_attestation_publish() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(5_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn reserve_attestation_sign() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn everusd_limit_set() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
     fn bond_add_new() -> Weight {
         (10000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
@@ -124,6 +173,31 @@ impl WeightInfo for () {
             .saturating_add(DbWeight::get().reads(4_u64 as Weight))
             .saturating_add(DbWeight::get().writes(3_u64 as Weight))
     }
+    fn bond_unit_order_place() -> Weight {
+        (20000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(4_u64 as Weight))
+    }
+    fn bond_unit_order_cancel() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(2_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(3_u64 as Weight))
+    }
+    fn bond_transfer_restriction_set() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn bond_whitelist_add() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn bond_whitelist_remove() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
     fn bond_withdraw() -> Weight {
         (1000000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(4_u64 as Weight))
@@ -136,19 +210,73 @@ impl WeightInfo for () {
     }
     fn bond_impact_report_send() -> Weight {
         (10000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
     }
     fn bond_impact_report_approve() -> Weight {
         (10000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_impact_report_reject() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_impact_kpi_report_send() -> Weight {
+        (20000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_impact_kpi_report_approve() -> Weight {
+        (20000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
     }
     fn bond_redeem() -> Weight {
         (1000000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
             .saturating_add(DbWeight::get().writes(2_u64 as Weight))
     }
+    fn bond_call(a: u32) -> Weight {
+        (2000000_u64 as Weight)
+            .saturating_add((100000_u64 as Weight).saturating_mul(a as Weight))
+            .saturating_add(DbWeight::get().reads(6_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((3_u64 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(DbWeight::get().writes(5_u64 as Weight))
+            .saturating_add(DbWeight::get().writes((3_u64 as Weight).saturating_mul(a as Weight)))
+    }
+    fn bond_restructuring_propose() -> Weight {
+        (20000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_restructuring_vote() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_restructuring_finalize(v: u32) -> Weight {
+        (2000000_u64 as Weight)
+            .saturating_add((20000_u64 as Weight).saturating_mul(v as Weight))
+            .saturating_add(DbWeight::get().reads(7_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((2_u64 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(DbWeight::get().writes(7_u64 as Weight))
+            .saturating_add(DbWeight::get().writes((1_u64 as Weight).saturating_mul(v as Weight)))
+    }
+    fn bond_recovery_deposit() -> Weight {
+        (20000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_default_settle(a: u32) -> Weight {
+        (2000000_u64 as Weight)
+            .saturating_add((100000_u64 as Weight).saturating_mul(a as Weight))
+            .saturating_add(DbWeight::get().reads(8_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((3_u64 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(DbWeight::get().writes(8_u64 as Weight))
+            .saturating_add(DbWeight::get().writes((3_u64 as Weight).saturating_mul(a as Weight)))
+    }
     fn bond_declare_bankrupt() -> Weight {
         (1000000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
@@ -169,6 +297,16 @@ impl WeightInfo for () {
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
             .saturating_add(DbWeight::get().writes(2_u64 as Weight))
     }
+    fn fee_withdraw_everusd() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(2_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_proceeds_withdraw() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(5_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(3_u64 as Weight))
+    }
     fn bond_deposit_everusd() -> Weight {
         (1000000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
@@ -177,11 +315,55 @@ impl WeightInfo for () {
     fn bond_unit_lot_bid() -> Weight {
         (20000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(4_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
     }
     fn bond_unit_lot_settle() -> Weight {
         (20000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(9_u64 as Weight))
             .saturating_add(DbWeight::get().writes(5_u64 as Weight))
     }
+    fn bond_unit_lot_cancel() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn bond_unit_lot_update_price() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(2_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn bond_unit_purchase_bid() -> Weight {
+        (20000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(6_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(4_u64 as Weight))
+    }
+    fn bond_unit_purchase_bid_cancel() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(3_u64 as Weight))
+    }
+    fn bond_unit_purchase_bid_settle() -> Weight {
+        (20000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(10_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(6_u64 as Weight))
+    }
+    fn purge_expired_request() -> Weight {
+        (5000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn bond_lifecycle_check() -> Weight {
+        (5000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(1_u64 as Weight))
+    }
+    fn bond_unit_package_return_all() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(2_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(3_u64 as Weight))
+    }
+    fn bond_unit_order_allocate() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(4_u64 as Weight))
+    }
 }
@@ -0,0 +1,154 @@
+//! Implementation of the frame fungible traits for EverUSD.
+//! Other pallets can use EverUSD through these traits instead of calling
+//! the bonds pallet directly. Held balance is the balance, reserved by
+//! purchase bids (see ReservedEverUSD).
+
+use crate::bond::EverUSDBalance;
+use crate::pallet::*;
+use frame_support::{
+    dispatch::{DispatchError, DispatchResult},
+    sp_std::cmp::min,
+    traits::tokens::{
+        fungible::{Inspect, InspectHold, Mutate, MutateHold, Transfer},
+        DepositConsequence, WithdrawConsequence,
+    },
+};
+
+impl<T: Config> Inspect<T::AccountId> for Pallet<T> {
+    type Balance = EverUSDBalance;
+
+    fn total_issuance() -> Self::Balance {
+        TotalSupplyEverUSD::<T>::get()
+    }
+
+    /// EverUSD accounts don't have existential deposit
+    fn minimum_balance() -> Self::Balance {
+        0
+    }
+
+    fn balance(who: &T::AccountId) -> Self::Balance {
+        BalanceEverUSD::<T>::get(who)
+    }
+
+    fn reducible_balance(who: &T::AccountId, _keep_alive: bool) -> Self::Balance {
+        BalanceEverUSD::<T>::get(who)
+    }
+
+    fn can_deposit(who: &T::AccountId, amount: Self::Balance, mint: bool) -> DepositConsequence {
+        if mint && TotalSupplyEverUSD::<T>::get().checked_add(amount).is_none() {
+            return DepositConsequence::Overflow;
+        }
+        match BalanceEverUSD::<T>::get(who).checked_add(amount) {
+            Some(_) => DepositConsequence::Success,
+            None => DepositConsequence::Overflow,
+        }
+    }
+
+    fn can_withdraw(who: &T::AccountId, amount: Self::Balance) -> WithdrawConsequence<Self::Balance> {
+        if BalanceEverUSD::<T>::get(who) < amount {
+            return WithdrawConsequence::NoFunds;
+        }
+        if TotalSupplyEverUSD::<T>::get() < amount {
+            return WithdrawConsequence::Underflow;
+        }
+        WithdrawConsequence::Success
+    }
+}
+
+impl<T: Config> Mutate<T::AccountId> for Pallet<T> {
+    fn mint_into(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+        Self::ensure_reserve_covers(amount, pallet_timestamp::Pallet::<T>::get())?;
+        TotalSupplyEverUSD::<T>::try_mutate(|total| -> DispatchResult {
+            *total = total.checked_add(amount).ok_or(Error::<T>::BalanceOverdraft)?;
+            Ok(())
+        })?;
+        ConfirmedMintEverUSD::<T>::mutate(|total| *total = total.saturating_add(amount));
+        Self::balance_add(who, amount)
+    }
+
+    fn burn_from(who: &T::AccountId, amount: Self::Balance) -> Result<Self::Balance, DispatchError> {
+        Self::balance_sub(who, amount)?;
+        TotalSupplyEverUSD::<T>::try_mutate(|total| -> DispatchResult {
+            *total = total.checked_sub(amount).ok_or(Error::<T>::BalanceOverdraft)?;
+            Ok(())
+        })?;
+        ConfirmedBurnEverUSD::<T>::mutate(|total| *total = total.saturating_add(amount));
+        Ok(amount)
+    }
+}
+
+impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
+    fn transfer(
+        source: &T::AccountId,
+        dest: &T::AccountId,
+        amount: Self::Balance,
+        _keep_alive: bool,
+    ) -> Result<Self::Balance, DispatchError> {
+        Self::transfer_everusd(source, dest, amount)?;
+        Ok(amount)
+    }
+}
+
+impl<T: Config> InspectHold<T::AccountId> for Pallet<T> {
+    fn balance_on_hold(who: &T::AccountId) -> Self::Balance {
+        ReservedEverUSD::<T>::get(who)
+    }
+
+    fn can_hold(who: &T::AccountId, amount: Self::Balance) -> bool {
+        BalanceEverUSD::<T>::get(who) >= amount
+    }
+}
+
+impl<T: Config> MutateHold<T::AccountId> for Pallet<T> {
+    fn hold(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+        Self::balance_reserve(who, amount)
+    }
+
+    fn release(
+        who: &T::AccountId,
+        amount: Self::Balance,
+        best_effort: bool,
+    ) -> Result<Self::Balance, DispatchError> {
+        let amount = Self::held_amount(who, amount, best_effort)?;
+        Self::balance_unreserve(who, amount)?;
+        Ok(amount)
+    }
+
+    fn transfer_held(
+        source: &T::AccountId,
+        dest: &T::AccountId,
+        amount: Self::Balance,
+        best_effort: bool,
+        on_hold: bool,
+    ) -> Result<Self::Balance, DispatchError> {
+        let amount = Self::held_amount(source, amount, best_effort)?;
+        Self::reserved_sub(source, amount)?;
+        if on_hold {
+            ReservedEverUSD::<T>::try_mutate(dest, |reserved| -> DispatchResult {
+                *reserved = reserved.checked_add(amount).ok_or(Error::<T>::BalanceOverdraft)?;
+                Ok(())
+            })?;
+        } else {
+            Self::balance_add(dest, amount)?;
+        }
+        Ok(amount)
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns the part of `amount`, that can be taken from the held balance of the account
+    fn held_amount(
+        who: &T::AccountId,
+        amount: EverUSDBalance,
+        best_effort: bool,
+    ) -> Result<EverUSDBalance, DispatchError> {
+        let reserved = ReservedEverUSD::<T>::get(who);
+        if best_effort {
+            Ok(min(amount, reserved))
+        } else if amount <= reserved {
+            Ok(amount)
+        } else {
+            Err(Error::<T>::BalanceOverdraft.into())
+        }
+    }
+}
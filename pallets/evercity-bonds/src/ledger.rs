@@ -5,12 +5,16 @@ pub struct EvercityBalance {
     pub supply: EverUSDBalance,
     /// account balance
     pub account: EverUSDBalance,
+    /// balance reserved by purchase bids
+    pub reserved: EverUSDBalance,
     /// bond fund balance
     pub bond_fund: EverUSDBalance,
+    /// fees, not withdrawn by fee accounts
+    pub fees: EverUSDBalance,
 }
 
 impl EvercityBalance {
     pub fn is_ok(&self) -> bool {
-        self.supply == self.account + self.bond_fund
+        self.supply == self.account + self.reserved + self.bond_fund + self.fees
     }
 }
@@ -43,9 +43,14 @@
 //!      calls) <i>bond_unit_package_buy(BondId, u64, BondUnitAmount)</i>
 //!    - Some of Investors refuse, returning their bought BondUnitsPackage-s: (many calls)
 //!      <i>bond_unit_package_return(BondId, BondUnitAmount)</i>
+//!    - Bond in bookbuilding mode ("bookbuilding" allocation rule is set) accepts orders instead:
+//!      <i>bond_unit_order_place(BondId, BondUnitAmount)</i>, reserving EverUSD of Investors.
+//!      Orders can be cancelled with <i>bond_unit_order_cancel(BondId)</i>. During activation
+//!      Bond Units are allocated pro-rata or by time priority, unfilled orders are refunded
 //!    - If Investors have bought NOT enough BondUnits until "mincap_deadline", Bond Arranger, Issuer or Manager withdraws it back
 //!      to PREPARE state: <i>bond_withdraw(BondId)</i>. Bond cannot be "canceled" until deadline.
-//!      All pre-bought Bond Units can be returned by Investors
+//!      All pre-bought Bond Units can be returned by Investors. If nobody does it, bond is withdrawn
+//!      automatically in "on_idle" hook after the deadline
 //!    - If Investors bought enough BondUnits until deadline, Bond Arranger moves the Bond to ACTIVE state:
 //!      <i>bond_activate(BondId, u64)</i>
 //!    - Date, when bond becomes ACTIVE (BOOKING->ACTIVE) - is a bond start time. All next periods
@@ -66,6 +71,13 @@
 //!      u64)</i>
 //!    - Auditor confirms data, sent by Issuer: <i>bond_impact_report_approve(BondId,
 //!      BondPeriodNumber, u64)</i>
+//!    - or rejects it with a reason and optional evidence file: <i>bond_impact_report_reject(BondId,
+//!      BondPeriodNumber, u64, Vec<u64>, Vec<u8>, Option<FileId>)</i>. Issuer resubmits impact_data while
+//!      "impact_data_send_period" is active. All sent, approved and rejected reports are kept in history
+//!    - Bond with several KPIs ("impact_kpis") receives values of all KPIs at once:
+//!      <i>bond_impact_kpi_report_send(BondId, BondPeriodNumber, u64, Vec<u64>)</i> and
+//!      <i>bond_impact_kpi_report_approve(BondId, BondPeriodNumber, u64, Vec<u64>)</i>.
+//!      Interest rates of KPIs are combined according to their weights
 //!    - Confirmed impact data will later result in change of interest rate for NEXT period
 //!    - First payment_period begins (start period passed). Confirmed impact_data in previous
 //!      period results in calculation of current period coupon yield.
@@ -90,9 +102,39 @@
 //!      increasing bond_debit
 //!    - in BANKRUPT state Investors can withdraw only part of coupon yield, corresponding
 //!      to amount of Bond Units they own
+//!    - Bond Arranger or Custodian deposits EverUSD, recovered from Issuer, with
+//!      <i>bond_recovery_deposit(BondId, EverUSDBalance)</i>
+//!    - Bond Arranger calls <i>bond_default_settle(BondId, u32)</i>, moving bond to terminal
+//!      DEFAULT_SETTLED state. Investors receive pro-rata share of recovery fund, calling
+//!      <i>bond_withdraw_everusd(BondId)</i>
+//!  - Use of proceeds of green bond
+//!    - Bond with declared "use_of_proceeds" categories keeps raised proceeds in the Issuer's reserve
+//!    - Issuer releases proceeds with <i>bond_proceeds_withdraw(BondId, BondProceedsCategory,
+//!      EverUSDBalance, Option<FileId>)</i>, recording spending category and evidence file.
+//!      Allocation of every category is limited by its cap
+//!  - Bond transfer restrictions
+//!    - Issuer restricts acquisition of bond units with <i>bond_transfer_restriction_set(BondId,
+//!      BondTransferRestrictionStruct)</i>: whitelisted investors only, permitted jurisdictions
+//!      and maximum holding per investor
+//!    - Issuer manages whitelist with <i>bond_whitelist_add(BondId, AccountId, Jurisdiction)</i> and
+//!      <i>bond_whitelist_remove(BondId, AccountId)</i>
+//!    - restrictions are checked by "BondTransferGuard" on every acquisition of bond units
+//!  - Bond fees
+//!    - Bond can have fee accounts of Evercity and Bond Arrangers, receiving share of the bond fund
+//!      at activation, of coupon yield paid to Investors and of secondary trade price
+//!    - fee accounts receive charged fees with <i>fee_withdraw_everusd()</i>
+//!  - Bond in ACTIVE or BANKRUPT state(restructuring)
+//!    - Issuer or Bond Arranger proposes maturity extension, interest rate change or coupon holiday
+//!      with <i>bond_restructuring_propose(BondId, BondRestructuring, Moment)</i>
+//!    - Investors vote with <i>bond_restructuring_vote(BondId, bool)</i>, their votes are weighted
+//!      by amount of Bond Units they own
+//!    - <i>bond_restructuring_finalize(BondId, u32)</i> applies approved proposal to bond terms
+//!      or rejects it, if majority or quorum isn't reached
 //!  - Bond in ACTIVE state(finishing period)
 //!    - after all payment_period passed maturity period begins. It's time form Issuer to pay
 //!      full bond debt back to Investors
+//!    - if the debt is not paid off until the end of "bond_finishing_period", bond is moved
+//!      to BANKRUPT state automatically in "on_idle" hook
 //!    - Investor accumulates needed amount of EverUSD on his address and calls
 //!      <i>bond_redeem(BondId)</i> function.
 //!      - function "bond_redeem" recalculates all yields per each period, summarizes them with
@@ -120,10 +162,18 @@
 #![recursion_limit = "256"]
 
 use pallet_evercity_accounts as accounts;
+use accounts::accounts::{RoleMask, INVESTOR_ROLE_MASK, ISSUER_ROLE_MASK};
 
 use crate::bond::{
-    AccountYield, BondInnerStructOf, BondPeriodNumber, BondState,
-    BondUnitAmount, BondUnitSaleLotStructOf, OnAddBond,
+    AccountYield, BondAllocationRule, BondDefaultSettlementStruct, BondDefaultSettlementStructOf,
+    BondFeeKind, BondFeeStructOf,
+    BondImpactReportAction, BondImpactReportHistoryItemOf, BondImpactReportRejection,
+    BondInnerStructOf, BondInterest, BondPeriodNumber, BondProceedsAllocationStruct, BondProceedsAllocationStructOf,
+    BondProceedsCategory, BondProceedsCategoryReport, BondProceedsReport, BondRestructuring, BondRestructuringProposalStruct,
+    BondPortfolioStruct, BondPortfolioStructOf, BondPositionStruct,
+    BondRestructuringProposalStructOf, BondState, BondTransferGuard, BondTransferRestrictionStruct, BondUnitAmount, BondUnitOrderId, BondUnitOrderStruct,
+    BondUnitPriceStruct, BondUnitPurchaseBidId, MAX_BOND_UNIT_ORDERS, BondUnitPurchaseBidStructOf, BondUnitSaleLotId, BondUnitSaleLotStructOf,
+    Jurisdiction, OnAddBond, MAX_PROCEEDS_ALLOCATIONS, MAX_REJECTION_REASON_LEN, RESTRUCTURING_QUORUM,
 
 };
 pub use crate::bond::{
@@ -134,9 +184,13 @@ pub use crate::bond::{
 pub use default_weight::WeightInfo;
 use frame_support::{
     dispatch::Vec,
+    sp_runtime::traits::SaturatedConversion,
     sp_std::cmp::{min,},
+    transactional,
 };
-pub use period::{PeriodDataStruct, PeriodYield};
+pub use period::{BondDeadline, BondDeadlineKind, BondDeadlineOf, PeriodDataStruct, PeriodYield};
+use period::PeriodDescr;
+use pallet_evercity_filesign::file::FileId;
 
 type Timestamp<T> = pallet_timestamp::Pallet<T>;
 
@@ -144,8 +198,8 @@ type Timestamp<T> = pallet_timestamp::Pallet<T>;
 pub const EVERUSD_DECIMALS: u64 = 9;
 /// Bank's year in days
 const INTEREST_RATE_YEAR: u64 = 365;
-/// Gas limit settings for purge mint/burn requests
-const MAX_PURGE_REQUESTS: usize = 100;
+/// Upper bound of yield to maturity, returned by get_yield_to_maturity() (1000%)
+const MAX_YIELD_TO_MATURITY: BondInterest = 1_000_000;
 
 
 /// Evercity project types
@@ -153,9 +207,11 @@ const MAX_PURGE_REQUESTS: usize = 100;
 /// to be correctly presented in DApp
 pub mod bond;
 mod default_weight;
+mod everusd;
 #[cfg(test)]
 pub mod ledger;
 
+pub mod migrations;
 pub mod token;
 pub mod period;
 pub mod runtime_api;
@@ -197,39 +253,94 @@ pub mod pallet {
         type MaxMintAmount: Get<EverUSDBalance>;
         #[pallet::constant]
         type TimeStep: Get<BondPeriod>;
+        /// Time after the end of the attestation period, when the latest signed reserve
+        /// attestation still allows minting of EverUSD
+        #[pallet::constant]
+        type ReserveAttestationTtl: Get<u32>;
+        /// Maximum number of storage entries visited by each lifecycle sweep in a block
+        #[pallet::constant]
+        type MaxLifecycleOpsPerBlock: Get<u32>;
         type WeightInfo: WeightInfo;
         type OnAddBond: OnAddBond<Self::AccountId, Self::Moment, Self::Hash>;    
+        /// Checks every acquisition of bond units. Pallet itself implements
+        /// issuer-managed whitelists and holding limits
+        type BondTransferGuard: BondTransferGuard<Self::AccountId>;
     }
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(migrations::STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {  }
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Translates storage to the current StorageVersion (see migrations)
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate::<T>()
+        }
+
+        /// Removes expired mint and burn requests and expired purchase bids.
+        /// Every block visits at most MaxLifecycleOpsPerBlock requests of each kind,
+        /// continuing from the place where the previous block stopped
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            // Timestamp of the current block is not set yet, previous one is used
+            let now = Timestamp::<T>::get();
+            let limit = T::MaxLifecycleOpsPerBlock::get();
+            let visited = Self::sweep_expired_mint_requests(now, limit)
+                .saturating_add(Self::sweep_expired_burn_requests(now, limit))
+                .saturating_add(Self::sweep_expired_purchase_bids(now, limit));
+            T::WeightInfo::purge_expired_request()
+                .saturating_mul(visited as Weight)
+                .saturating_add(T::DbWeight::get().reads_writes(3, 3))
+        }
+
+        /// Moves bonds through time-driven transitions using the block's spare weight:
+        ///  - BOOKING bond that missed "mincap_deadline" is withdrawn back to PREPARE
+        ///  - ACTIVE bond with unpaid debt after "bond_finishing_period" becomes BANKRUPT
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let now = Timestamp::<T>::get();
+            Self::process_bond_lifecycle(now, remaining_weight)
+        }
+    }
 
 
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        /// \[account, everusd\]
-        MintRequestCreated(T::AccountId, EverUSDBalance),
-        /// \[account, everusd\]
-        MintRequestRevoked(T::AccountId, EverUSDBalance),
-        /// \[custodian, account, everusd\]
-        MintRequestConfirmed(T::AccountId, T::AccountId, EverUSDBalance),
-        /// \[custodian, account, everusd\]
-        MintRequestDeclined(T::AccountId, T::AccountId, EverUSDBalance),
-        /// \[account, everusd\]
-        BurnRequestCreated(T::AccountId, EverUSDBalance),
-        /// \[account, everusd\]
-        BurnRequestRevoked(T::AccountId, EverUSDBalance),
-        /// \[custodian, account, everusd\]
-        BurnRequestConfirmed(T::AccountId, T::AccountId, EverUSDBalance),
-        /// \[custodian,account, everusd\]
-        BurnRequestDeclined(T::AccountId, T::AccountId, EverUSDBalance),
+        /// \[account, request, everusd, payment_reference\]
+        MintRequestCreated(T::AccountId, TokenMintRequestId, EverUSDBalance, Vec<u8>),
+        /// \[account, request, everusd\]
+        MintRequestRevoked(T::AccountId, TokenMintRequestId, EverUSDBalance),
+        /// \[custodian, account, request, everusd\]
+        MintRequestConfirmed(T::AccountId, T::AccountId, TokenMintRequestId, EverUSDBalance),
+        /// \[custodian, account, request, everusd\]
+        MintRequestDeclined(T::AccountId, T::AccountId, TokenMintRequestId, EverUSDBalance),
+        /// \[account, request, everusd, payment_reference\]
+        BurnRequestCreated(T::AccountId, TokenBurnRequestId, EverUSDBalance, Vec<u8>),
+        /// \[account, request, everusd\]
+        BurnRequestRevoked(T::AccountId, TokenBurnRequestId, EverUSDBalance),
+        /// \[custodian, account, request, everusd\]
+        BurnRequestConfirmed(T::AccountId, T::AccountId, TokenBurnRequestId, EverUSDBalance),
+        /// \[custodian, account, request, everusd\]
+        BurnRequestDeclined(T::AccountId, T::AccountId, TokenBurnRequestId, EverUSDBalance),
+        /// \[account, request, everusd\]
+        MintRequestExpired(T::AccountId, TokenMintRequestId, EverUSDBalance),
+        /// \[account, request, everusd\]
+        BurnRequestExpired(T::AccountId, TokenBurnRequestId, EverUSDBalance),
+        /// \[from, to, everusd, memo\]
+        EverUSDTransferred(T::AccountId, T::AccountId, EverUSDBalance, Option<Vec<u8>>),
+        /// \[custodian, attestation, reserve\]
+        ReserveAttestationPublished(T::AccountId, ReserveAttestationId, EverUSDBalance),
+        /// \[auditor, attestation, reserve\]
+        ReserveAttestationSigned(T::AccountId, ReserveAttestationId, EverUSDBalance),
+        /// \[master, limit\]
+        EverUSDGlobalLimitSet(T::AccountId, EverUSDLimitStruct),
+        /// \[master, role, limit\]
+        EverUSDRoleLimitSet(T::AccountId, RoleMask, Option<EverUSDLimitStruct>),
+        /// \[master, account, limit\]
+        EverUSDAccountLimitSet(T::AccountId, T::AccountId, Option<EverUSDLimitStruct>),
         // Bond events
         /// \[issuer,bond\]
         BondAdded(T::AccountId, BondId),
@@ -245,8 +356,16 @@ pub mod pallet {
         BondWithdrawal(T::AccountId, BondId),
         /// \[issuer,bond,bondfund\]
         BondRedeemed(T::AccountId, BondId, EverUSDBalance),
+        /// \[issuer,bond,everusd\]
+        BondCalled(T::AccountId, BondId, EverUSDBalance),
         /// \[sender,bond,credit,debit\]
         BondBankrupted(T::AccountId, BondId, EverUSDBalance, EverUSDBalance),
+        /// \[bond\]
+        BondAutoWithdrawal(BondId),
+        /// \[bond,credit,debit\]
+        BondAutoBankrupted(BondId, EverUSDBalance, EverUSDBalance),
+        /// \[bond,error\]
+        BondAutoTransitionFailed(BondId, DispatchError),
         /// \[sender,bond,everusd\]
         BondWithdrawEverUSD(T::AccountId, BondId, EverUSDBalance),
         /// \[issuer,bond,everusd\]
@@ -259,12 +378,56 @@ pub mod pallet {
         BondImpactReportSent(T::AccountId, BondId, BondPeriodNumber, u64),
         /// \[auditor,bond,period,impact_data\]
         BondImpactReportApproved(T::AccountId, BondId, BondPeriodNumber, u64),
+        /// \[auditor,bond,period,impact_data\]
+        BondImpactReportRejected(T::AccountId, BondId, BondPeriodNumber, u64),
         /// \[bond,everusd\]
         BondCouponYield(BondId, EverUSDBalance),
         /// \[bondholder, bond, lot\]
         BondSaleLotBid(T::AccountId, BondId, BondUnitSaleLotStructOf::<T>),
         /// \[from, to, bond, lot\]
         BondSaleLotSettle(T::AccountId, T::AccountId, BondId, BondUnitSaleLotStructOf::<T>),
+        /// \[bondholder, bond, lot_id\]
+        BondSaleLotCancel(T::AccountId, BondId, BondUnitSaleLotId),
+        /// \[bondholder, bond, lot_id, amount\]
+        BondSaleLotPriceUpdated(T::AccountId, BondId, BondUnitSaleLotId, EverUSDBalance),
+        /// \[bidder, bond, bid\]
+        BondPurchaseBid(T::AccountId, BondId, BondUnitPurchaseBidStructOf::<T>),
+        /// \[bidder, bond, bid_id\]
+        BondPurchaseBidCancel(T::AccountId, BondId, BondUnitPurchaseBidId),
+        /// \[bidder, bond, bid_id\]
+        BondPurchaseBidExpired(T::AccountId, BondId, BondUnitPurchaseBidId),
+        /// \[from, to, bond, bid\]
+        BondPurchaseBidSettle(T::AccountId, T::AccountId, BondId, BondUnitPurchaseBidStructOf::<T>),
+        /// \[proposer, bond, restructuring\]
+        BondRestructuringProposed(T::AccountId, BondId, BondRestructuring),
+        /// \[bondholder, bond, approve\]
+        BondRestructuringVoted(T::AccountId, BondId, bool),
+        /// \[bond, restructuring\]
+        BondRestructuringApproved(BondId, BondRestructuring),
+        /// \[bond, restructuring\]
+        BondRestructuringRejected(BondId, BondRestructuring),
+        /// \[investor, bond, order\]
+        BondUnitOrderPlace(T::AccountId, BondId, BondUnitOrderStruct),
+        /// \[investor, bond, order_id\]
+        BondUnitOrderCancel(T::AccountId, BondId, BondUnitOrderId),
+        /// \[investor, bond, bond_units, refund\]
+        BondUnitOrderAllocated(T::AccountId, BondId, BondUnitAmount, EverUSDBalance),
+        /// \[bond, fee_account, kind, amount\]
+        BondFeeCharged(BondId, T::AccountId, BondFeeKind, EverUSDBalance),
+        /// \[fee_account, amount\]
+        FeeWithdrawEverUSD(T::AccountId, EverUSDBalance),
+        /// \[depositor, bond, amount\]
+        BondRecoveryDeposit(T::AccountId, BondId, EverUSDBalance),
+        /// \[caller, bond, recovery_fund, total_claim\]
+        BondDefaultSettled(T::AccountId, BondId, EverUSDBalance, EverUSDBalance),
+        /// \[issuer, bond, category, amount\]
+        BondProceedsAllocated(T::AccountId, BondId, BondProceedsCategory, EverUSDBalance),
+        /// \[issuer, bond\]
+        BondTransferRestrictionSet(T::AccountId, BondId),
+        /// \[issuer, bond, investor, jurisdiction\]
+        BondWhitelistAdded(T::AccountId, BondId, T::AccountId, Jurisdiction),
+        /// \[issuer, bond, investor\]
+        BondWhitelistRemoved(T::AccountId, BondId, T::AccountId),
     }
 
     /// Old name generated by `decl_event`.
@@ -285,17 +448,19 @@ pub mod pallet {
         AccountNotExist,
         /// Role parameter is invalid (bit mask of available roles includes non-existent role)
         AccountRoleParamIncorrect,
-        /// Account already created one mint request, only one allowed at a time(to be changed in future)
+        /// Account already has MAX_TOKEN_REQUESTS_PER_ACCOUNT outstanding mint requests
         MintRequestAlreadyExist,
         /// Mint request for given account doesnt exist
         MintRequestDoesntExist,
-        /// Incorrect parameters for mint request(miant amount > MAX_MINT_AMOUNT)
+        /// Incorrect parameters for mint request(mint amount > MAX_MINT_AMOUNT, confirmed amount
+        /// exceeds the remaining amount or payment reference is too long)
         MintRequestParamIncorrect,
-        /// Account already created one burn request, only one allowed at a time(to be changed in future)
+        /// Account already has MAX_TOKEN_REQUESTS_PER_ACCOUNT outstanding burn requests
         BurnRequestAlreadyExist,
         /// Mint request for given account doesnt exist
         BurnRequestDoesntExist,
-        /// Incorrect parameters for mint request(mint amount > MAX_MINT_AMOUNT)
+        /// Incorrect parameters for burn request(confirmed amount exceeds the remaining amount
+        /// or payment reference is too long)
         BurnRequestParamIncorrect,
         /// Burn request exists but outdated
         BurnRequestObsolete,
@@ -324,6 +489,42 @@ pub mod pallet {
         LotObsolete,
         /// Incorrect parameter for the bond sale lot
         LotParamIncorrect,
+        /// Purchase bid not found
+        BidNotFound,
+        /// Purchase bid expired
+        BidObsolete,
+        /// Incorrect parameter for the bond purchase bid
+        BidParamIncorrect,
+        /// Restructuring proposal not found
+        ProposalNotFound,
+        /// Restructuring proposal voting is over
+        ProposalObsolete,
+        /// Investor is not eligible to acquire bond units
+        BondTransferNotPermitted,
+        /// Investor would hold more bond units than permitted
+        BondHoldingLimitExceeded,
+        /// Allocation exceeds the cap of use of proceeds category
+        ProceedsCapExceeded,
+        /// Reserve attestation doesn't exist
+        ReserveAttestationNotFound,
+        /// Incorrect attestation period or auditor account
+        ReserveAttestationParamIncorrect,
+        /// Reserve attestation is already signed by the auditor
+        ReserveAttestationAlreadySigned,
+        /// Latest signed reserve attestation is older than ReserveAttestationTtl
+        ReserveAttestationStale,
+        /// Attested reserve doesn't cover the total supply of EverUSD after minting
+        ReserveInsufficient,
+        /// Incorrect window of EverUSD limit or role, the limit is set for
+        EverUSDLimitParamIncorrect,
+        /// Confirmation exceeds the rolling window limit of minted EverUSD
+        MintLimitExceeded,
+        /// Confirmation exceeds the rolling window limit of burned EverUSD
+        BurnLimitExceeded,
+        /// Bond already has MAX_BOND_UNIT_ORDERS bookbuilding orders
+        BondUnitOrderLimitExceeded,
+        /// Bond already has MAX_PROCEEDS_ALLOCATIONS allocations of proceeds
+        ProceedsAllocationLimitExceeded,
     }
 
 
@@ -337,15 +538,94 @@ pub mod pallet {
     #[pallet::getter(fn balances_everusd)]
     pub(super) type BalanceEverUSD<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, EverUSDBalance, ValueQuery>;
 
-    /// Storage map for EverUSD token mint requests (see TokenMintRequestStruct)
+    /// EverUSD reserved by account's purchase bids. Reserved tokens are not
+    /// included in the account's balance
+    #[pallet::storage]
+    #[pallet::getter(fn reserved_everusd)]
+    pub(super) type ReservedEverUSD<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, EverUSDBalance, ValueQuery>;
+
+    /// Bond fees, charged to fee accounts and not withdrawn yet. Fees are not
+    /// included in the account's balance
+    #[pallet::storage]
+    #[pallet::getter(fn fee_everusd)]
+    pub(super) type FeeEverUSD<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, EverUSDBalance, ValueQuery>;
+
+    /// Storage map for EverUSD token mint requests of each account (see TokenMintRequestStruct)
     #[pallet::storage]
     #[pallet::getter(fn mint_request_everusd)]
-    pub(super) type MintRequestEverUSD<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, TokenMintRequestStructOf<T>, ValueQuery>;     
+    pub(super) type MintRequestEverUSD<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, TokenMintRequestId, TokenMintRequestStructOf<T>, OptionQuery>;
 
-    /// Storage map for EverUSD token burn requests (see TokenBurnRequestStruct)
+    /// Storage map for EverUSD token burn requests of each account (see TokenBurnRequestStruct)
     #[pallet::storage]
     #[pallet::getter(fn burn_request_everusd)]
-    pub(super) type BurnRequestEverUSD<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, TokenBurnRequestStructOf<T>, ValueQuery>;     
+    pub(super) type BurnRequestEverUSD<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, TokenBurnRequestId, TokenBurnRequestStructOf<T>, OptionQuery>;
+
+    /// Identifier of the last created mint request
+    #[pallet::storage]
+    #[pallet::getter(fn last_mint_request_id)]
+    pub(super) type LastTokenMintRequestId<T: Config> = StorageValue<_, TokenMintRequestId, ValueQuery>;
+
+    /// Identifier of the last created burn request
+    #[pallet::storage]
+    #[pallet::getter(fn last_burn_request_id)]
+    pub(super) type LastTokenBurnRequestId<T: Config> = StorageValue<_, TokenBurnRequestId, ValueQuery>;
+
+    /// Total amount of EverUSD, minted by confirmed mint requests
+    /// and by other pallets through fungible::Mutate
+    #[pallet::storage]
+    #[pallet::getter(fn confirmed_mint_everusd)]
+    pub(super) type ConfirmedMintEverUSD<T: Config> = StorageValue<_, EverUSDBalance, ValueQuery>;
+
+    /// Total amount of EverUSD, burned by confirmed burn requests
+    /// and by other pallets through fungible::Mutate
+    #[pallet::storage]
+    #[pallet::getter(fn confirmed_burn_everusd)]
+    pub(super) type ConfirmedBurnEverUSD<T: Config> = StorageValue<_, EverUSDBalance, ValueQuery>;
+
+    /// Reserve attestations, published by Custodian (see ReserveAttestationStruct)
+    #[pallet::storage]
+    #[pallet::getter(fn reserve_attestation)]
+    pub(super) type ReserveAttestation<T: Config> = StorageMap<_, Blake2_128Concat, ReserveAttestationId, ReserveAttestationStructOf<T>, OptionQuery>;
+
+    /// Identifier of the last published reserve attestation
+    #[pallet::storage]
+    #[pallet::getter(fn last_reserve_attestation_id)]
+    pub(super) type LastReserveAttestationId<T: Config> = StorageValue<_, ReserveAttestationId, ValueQuery>;
+
+    /// Signed reserve attestation with the latest attestation period. Minting of EverUSD
+    /// is checked against it, once the first attestation is signed
+    #[pallet::storage]
+    #[pallet::getter(fn latest_reserve_attestation)]
+    pub(super) type LatestReserveAttestation<T: Config> = StorageValue<_, ReserveAttestationId, OptionQuery>;
+
+    /// Limit of EverUSD, confirmed by Custodian for all accounts in the rolling window
+    #[pallet::storage]
+    #[pallet::getter(fn everusd_global_limit)]
+    pub(super) type EverUSDGlobalLimit<T: Config> = StorageValue<_, EverUSDLimitStruct, ValueQuery>;
+
+    /// Quota of EverUSD, confirmed for each account with the role (Issuer or Investor)
+    /// in the rolling window. All quotas of the account's roles are applied
+    #[pallet::storage]
+    #[pallet::getter(fn everusd_role_limit)]
+    pub(super) type EverUSDRoleLimit<T: Config> = StorageMap<_, Blake2_128Concat, RoleMask, EverUSDLimitStruct, OptionQuery>;
+
+    /// Limit of EverUSD, confirmed for the account in the rolling window.
+    /// Replaces quotas of the account's roles
+    #[pallet::storage]
+    #[pallet::getter(fn everusd_account_limit)]
+    pub(super) type EverUSDAccountLimit<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, EverUSDLimitStruct, OptionQuery>;
+
+    /// EverUSD, minted and burned by all confirmed requests, for each time step
+    /// of the longest rolling window
+    #[pallet::storage]
+    #[pallet::getter(fn everusd_global_flow)]
+    pub(super) type EverUSDGlobalFlow<T: Config> = StorageValue<_, Vec<EverUSDFlowStruct>, ValueQuery>;
+
+    /// EverUSD, minted and burned by confirmed requests of the account, for each
+    /// time step of the longest rolling window
+    #[pallet::storage]
+    #[pallet::getter(fn everusd_account_flow)]
+    pub(super) type EverUSDAccountFlow<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Vec<EverUSDFlowStruct>, ValueQuery>;
 
     /// Structure for storing all platform bonds.
     /// BondId is now a ticker [u8; 8]: 8-bytes unique identifier like "MUSKPWR1" or "WINDGEN2"
@@ -374,11 +654,116 @@ pub mod pallet {
     #[pallet::getter(fn bond_unit_lots)]
     pub(super) type BondUnitPackageLot<T: Config> = StorageDoubleMap<_, Blake2_128Concat, BondId, Blake2_128Concat, T::AccountId, Vec<BondUnitSaleLotStructOf<T>>, ValueQuery>;
 
+    /// Identifier of the last created bond sale lot
+    #[pallet::storage]
+    #[pallet::getter(fn last_bond_unit_lot_id)]
+    pub(super) type LastBondUnitSaleLotId<T: Config> = StorageValue<_, BondUnitSaleLotId, ValueQuery>;
+
+    /// Bond purchase bids for each bond
+    #[pallet::storage]
+    #[pallet::getter(fn bond_unit_bids)]
+    pub(super) type BondUnitPurchaseBid<T: Config> = StorageDoubleMap<_, Blake2_128Concat, BondId, Blake2_128Concat, T::AccountId, Vec<BondUnitPurchaseBidStructOf<T>>, ValueQuery>;
+
+    /// Identifier of the last created bond purchase bid
+    #[pallet::storage]
+    #[pallet::getter(fn last_bond_unit_bid_id)]
+    pub(super) type LastBondUnitPurchaseBidId<T: Config> = StorageValue<_, BondUnitPurchaseBidId, ValueQuery>;
+
     /// Bond impact report storage
     #[pallet::storage]
     #[pallet::getter(fn impact_reports)]
     pub(super) type BondImpactReport<T: Config> = StorageMap<_, Blake2_128Concat, BondId, Vec<BondImpactReportStruct>, ValueQuery>;
 
+    /// History of sent, approved and rejected impact reports for each bond period
+    #[pallet::storage]
+    #[pallet::getter(fn impact_report_history)]
+    pub(super) type BondImpactReportHistory<T: Config> = StorageDoubleMap<_, Blake2_128Concat, BondId, Blake2_128Concat, BondPeriodNumber, Vec<BondImpactReportHistoryItemOf<T>>, ValueQuery>;
+
+    /// Bookbuilding orders for each bond in BOOKING state
+    #[pallet::storage]
+    #[pallet::getter(fn bond_unit_orders)]
+    pub(super) type BondUnitOrder<T: Config> = StorageDoubleMap<_, Blake2_128Concat, BondId, Blake2_128Concat, T::AccountId, BondUnitOrderStruct, OptionQuery>;
+
+    /// Identifier of the last placed bookbuilding order
+    #[pallet::storage]
+    #[pallet::getter(fn last_bond_unit_order_id)]
+    pub(super) type LastBondUnitOrderId<T: Config> = StorageValue<_, BondUnitOrderId, ValueQuery>;
+
+    /// Number of bookbuilding orders of the bond, limited by MAX_BOND_UNIT_ORDERS
+    #[pallet::storage]
+    #[pallet::getter(fn bond_unit_order_count)]
+    pub(super) type BondUnitOrderCount<T: Config> = StorageMap<_, Blake2_128Concat, BondId, u32, ValueQuery>;
+
+    /// Restructuring proposal of the bond, being voted by bondholders
+    #[pallet::storage]
+    #[pallet::getter(fn bond_restructuring_proposal)]
+    pub(super) type BondRestructuringProposal<T: Config> = StorageMap<_, Blake2_128Concat, BondId, BondRestructuringProposalStructOf<T>, OptionQuery>;
+
+    /// Votes of bondholders for the restructuring proposal of the bond
+    #[pallet::storage]
+    #[pallet::getter(fn bond_restructuring_votes)]
+    pub(super) type BondRestructuringVote<T: Config> = StorageDoubleMap<_, Blake2_128Concat, BondId, Blake2_128Concat, T::AccountId, bool, OptionQuery>;
+
+    /// The number of bondholders, voted for the restructuring proposal of the bond
+    #[pallet::storage]
+    #[pallet::getter(fn bond_restructuring_vote_count)]
+    pub(super) type BondRestructuringVoteCount<T: Config> = StorageMap<_, Blake2_128Concat, BondId, u32, ValueQuery>;
+
+    /// Periods of the bond, when coupon yield isn't accrued
+    #[pallet::storage]
+    #[pallet::getter(fn bond_coupon_holidays)]
+    pub(super) type BondCouponHoliday<T: Config> = StorageMap<_, Blake2_128Concat, BondId, Vec<BondPeriodNumber>, ValueQuery>;
+
+    /// Final ledger snapshot of the bond in DEFAULT_SETTLED state
+    #[pallet::storage]
+    #[pallet::getter(fn bond_default_settlement)]
+    pub(super) type BondDefaultSettlement<T: Config> = StorageMap<_, Blake2_128Concat, BondId, BondDefaultSettlementStructOf<T>, OptionQuery>;
+
+    /// Proceeds of the bond with declared use of proceeds, raised without issuance fees
+    #[pallet::storage]
+    #[pallet::getter(fn bond_proceeds)]
+    pub(super) type BondProceeds<T: Config> = StorageMap<_, Blake2_128Concat, BondId, EverUSDBalance, ValueQuery>;
+
+    /// Allocation ledger of the bond proceeds, limited by MAX_PROCEEDS_ALLOCATIONS
+    #[pallet::storage]
+    #[pallet::getter(fn bond_proceeds_allocations)]
+    pub(super) type BondProceedsAllocation<T: Config> = StorageMap<_, Blake2_128Concat, BondId, Vec<BondProceedsAllocationStructOf<T>>, ValueQuery>;
+
+    /// Restrictions on acquisition of bond units, set by the bond Issuer
+    #[pallet::storage]
+    #[pallet::getter(fn bond_transfer_restriction)]
+    pub(super) type BondTransferRestriction<T: Config> = StorageMap<_, Blake2_128Concat, BondId, BondTransferRestrictionStruct, ValueQuery>;
+
+    /// Investors, whitelisted by the bond Issuer, with their jurisdictions
+    #[pallet::storage]
+    #[pallet::getter(fn bond_whitelist)]
+    pub(super) type BondWhitelist<T: Config> = StorageDoubleMap<_, Blake2_128Concat, BondId, Blake2_128Concat, T::AccountId, Jurisdiction, OptionQuery>;
+
+    /// Secondary index of bonds, where the account holds bond units, sale lots,
+    /// purchase bids or bookbuilding order
+    #[pallet::storage]
+    pub(super) type AccountBondIndex<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, BondId, (), OptionQuery>;
+
+    /// Secondary index of bonds, where the account is Issuer, Manager, Auditor or ImpactReporter
+    #[pallet::storage]
+    pub(super) type AccountBondRoleIndex<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, BondId, (), OptionQuery>;
+
+    /// Raw key of the last mint request visited by the expiration sweep
+    #[pallet::storage]
+    pub(super) type MintRequestSweepCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+    /// Raw key of the last burn request visited by the expiration sweep
+    #[pallet::storage]
+    pub(super) type BurnRequestSweepCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+    /// Raw key of the last (bond, bidder) pair visited by the purchase bid expiration sweep
+    #[pallet::storage]
+    pub(super) type PurchaseBidSweepCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+    /// Raw key of the last bond visited by the lifecycle sweep
+    #[pallet::storage]
+    pub(super) type BondLifecycleCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
 
     #[pallet::call]
     impl<T: Config> Pallet<T>
@@ -386,216 +771,423 @@ pub mod pallet {
         // Token balances manipulation functions
 
         /// <pre>
-        /// Method: token_mint_request_create_everusd(origin: OriginFor<T>, amount_to_mint: EverUSDBalance)
+        /// Method: token_mint_request_create_everusd(origin: OriginFor<T>, amount_to_mint: EverUSDBalance, payment_reference: Vec<u8>)
         /// Arguments:  origin: T::AccountId - transaction caller
         ///             amount_to_mint: EverUSDBalance - amount of tokens to mint
+        ///             payment_reference: Vec<u8> - opaque bank payment reference or hash of the bank statement
         /// Access: Investor or Issuer role
         ///
         /// Creates a request to mint given amount of EverUSD tokens on caller's balance.
         /// Custodian account confirms request after receiving payment in USD from target account's owner
-        /// It's possible to create only one request per account. Mint request has a time-to-live
+        /// Account can have up to MAX_TOKEN_REQUESTS_PER_ACCOUNT outstanding requests, each one
+        /// is identified by unique request id. Mint request has a time-to-live
         /// and becomes invalidated after it.
         /// </pre>
         #[pallet::weight(<T as pallet::Config>::WeightInfo::token_mint_request_create_everusd())]
-        pub fn token_mint_request_create_everusd(origin: OriginFor<T>, #[pallet::compact] amount_to_mint: EverUSDBalance) -> DispatchResult {
+        pub fn token_mint_request_create_everusd(origin: OriginFor<T>, #[pallet::compact] amount_to_mint: EverUSDBalance, payment_reference: Vec<u8>) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(accounts::Pallet::<T>::account_token_mint_burn_allowed(&caller), Error::<T>::AccountNotAuthorized);
-            ensure!(amount_to_mint <= T::MaxMintAmount::get(), Error::<T>::MintRequestParamIncorrect);
-
-            MintRequestEverUSD::<T>::try_mutate(&caller, |request|->DispatchResult{
-                let now = Timestamp::<T>::get();
-                if !request.is_expired(now) {
-                    Err( Error::<T>::MintRequestAlreadyExist.into() )
-                }else{
-                    *request = TokenMintRequestStruct{
-                        amount: amount_to_mint,
-                        deadline: now + T::MintRequestTtl::get().into(),
-                    };
-                    Self::deposit_event(Event::<T>::MintRequestCreated(caller.clone(), amount_to_mint));
-                    Ok(())
-                }
-            })?;
+            ensure!(amount_to_mint > 0 && amount_to_mint <= T::MaxMintAmount::get(), Error::<T>::MintRequestParamIncorrect);
+            ensure!(payment_reference.len() <= MAX_PAYMENT_REFERENCE_LEN, Error::<T>::MintRequestParamIncorrect);
+
+            let now = Timestamp::<T>::get();
+            let outstanding = MintRequestEverUSD::<T>::iter_prefix_values(&caller)
+                .filter(|request| !request.is_expired(now))
+                .count();
+            ensure!(outstanding < MAX_TOKEN_REQUESTS_PER_ACCOUNT, Error::<T>::MintRequestAlreadyExist);
+
+            let id = LastTokenMintRequestId::<T>::mutate(|id| {
+                *id += 1;
+                *id
+            });
+            MintRequestEverUSD::<T>::insert(&caller, id, TokenMintRequestStruct{
+                id,
+                amount: amount_to_mint,
+                confirmed: 0,
+                deadline: now + T::MintRequestTtl::get().into(),
+                payment_reference: payment_reference.clone(),
+            });
+            Self::deposit_event(Event::<T>::MintRequestCreated(caller, id, amount_to_mint, payment_reference));
             Ok(().into())
         }
 
         /// <pre>
-        /// Method: token_mint_request_revoke_everusd(origin)
+        /// Method: token_mint_request_revoke_everusd(origin, request_id: TokenMintRequestId)
         /// Arguments: origin: T::AccountId - transaction caller
+        ///            request_id: TokenMintRequestId - mint request identifier
         /// Access: Investor or Issuer role
         ///
-        /// Revokes and deletes currently existing mint request, created by caller's account
+        /// Revokes and deletes the mint request, created by caller's account.
+        /// Partially confirmed amount remains on the balance
         /// </pre>
         #[pallet::weight(<T as pallet::Config>::WeightInfo::token_mint_request_revoke_everusd())]
-        pub fn token_mint_request_revoke_everusd(origin: OriginFor<T>,) -> DispatchResult {
+        pub fn token_mint_request_revoke_everusd(origin: OriginFor<T>, #[pallet::compact] request_id: TokenMintRequestId) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            ensure!(MintRequestEverUSD::<T>::contains_key(&caller), Error::<T>::MintRequestDoesntExist);
-            let _amount = MintRequestEverUSD::<T>::get(&caller).amount;
-            MintRequestEverUSD::<T>::remove(&caller);
-            Self::deposit_event(Event::<T>::MintRequestRevoked(caller, _amount));
+            let request = MintRequestEverUSD::<T>::take(&caller, request_id).ok_or(Error::<T>::MintRequestDoesntExist)?;
+            Self::deposit_event(Event::<T>::MintRequestRevoked(caller, request_id, request.remaining()));
             Ok(().into())
         }
 
         /// <pre>
-        /// Method: token_mint_request_confirm_everusd(origin: OriginFor<T>, who: T::AccountId, amount: EverUSDBalance)
+        /// Method: token_mint_request_confirm_everusd(origin: OriginFor<T>, who: T::AccountId, request_id: TokenMintRequestId, amount: EverUSDBalance)
         /// Arguments:  origin: T::AccountId - transaction caller
         ///             who: T::AccountId - target account
+        ///             request_id: TokenMintRequestId - mint request identifier
         ///             amount: EverUSDBalance - amount of tokens to mint, confirmed by Custodian
         /// Access: Custodian role
         ///
         /// Confirms the mint request of account, creating "amount" of tokens on its balance.
+        /// Custodian can confirm the request partially, when the payment arrives in several
+        /// transfers. The request is deleted after the whole amount is confirmed.
+        /// Confirmed amount is limited by the global and account's rolling window limits.
         /// (note) Amount of tokens is sent as parameter to avoid data race problem, when
         /// Custodian can confirm unwanted amount of tokens, because attacker is modified mint request
         /// while Custodian makes a decision
         /// </pre>
         #[pallet::weight(<T as pallet::Config>::WeightInfo::token_mint_request_confirm_everusd())]
-        pub fn token_mint_request_confirm_everusd(origin: OriginFor<T>, who: T::AccountId, #[pallet::compact] amount: EverUSDBalance) -> DispatchResult {
+        pub fn token_mint_request_confirm_everusd(origin: OriginFor<T>, who: T::AccountId, #[pallet::compact] request_id: TokenMintRequestId, #[pallet::compact] amount: EverUSDBalance) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(accounts::Pallet::<T>::account_is_custodian(&caller),Error::<T>::AccountNotAuthorized);
-            ensure!(MintRequestEverUSD::<T>::contains_key(&who), Error::<T>::MintRequestDoesntExist);
-            let mint_request = MintRequestEverUSD::<T>::get(&who);
+            let mut mint_request = MintRequestEverUSD::<T>::get(&who, request_id).ok_or(Error::<T>::MintRequestDoesntExist)?;
             let now = Timestamp::<T>::get();
             ensure!(!mint_request.is_expired(now), Error::<T>::MintRequestObsolete);
+            ensure!(amount > 0 && amount <= mint_request.remaining(), Error::<T>::MintRequestParamIncorrect);
+            Self::ensure_reserve_covers(amount, now)?;
+            Self::everusd_limit_check(&who, now, true, amount)?;
 
             // add tokens to user's balance and total supply of EverUSD
-            let amount_to_add = mint_request.amount;
-            ensure!(amount_to_add==amount,Error::<T>::MintRequestParamIncorrect );
-
-            Self::balance_add(&who, amount_to_add)?;
+            Self::balance_add(&who, amount)?;
 
             TotalSupplyEverUSD::<T>::try_mutate(|total|->DispatchResult{
-                *total = total.checked_add(amount_to_add).ok_or( Error::<T>::BalanceOverdraft )?;
+                *total = total.checked_add(amount).ok_or( Error::<T>::BalanceOverdraft )?;
                 Ok(().into())
             })?;
+            ConfirmedMintEverUSD::<T>::mutate(|total| *total = total.saturating_add(amount));
+            Self::everusd_limit_record(&who, now, true, amount);
 
-            MintRequestEverUSD::<T>::remove(&who);
-            Self::deposit_event(Event::<T>::MintRequestConfirmed(caller, who, amount_to_add));
-            Self::purge_expired_mint_requests(now);
+            mint_request.confirmed += amount;
+            if mint_request.remaining() == 0 {
+                MintRequestEverUSD::<T>::remove(&who, request_id);
+            } else {
+                MintRequestEverUSD::<T>::insert(&who, request_id, mint_request);
+            }
+            Self::deposit_event(Event::<T>::MintRequestConfirmed(caller, who, request_id, amount));
             Ok(().into())
         }
 
         /// <pre>
-        /// Method: token_mint_request_decline_everusd(origin: OriginFor<T>, who: T::AccountId)
+        /// Method: token_mint_request_decline_everusd(origin: OriginFor<T>, who: T::AccountId, request_id: TokenMintRequestId)
         /// Arguments:  origin: T::AccountId - transaction caller
         ///             who: T::AccountId - target account
+        ///             request_id: TokenMintRequestId - mint request identifier
         /// Access: Custodian role
         ///
         /// Declines and deletes the mint request of account (Custodian)
         /// </pre>
         #[pallet::weight(<T as pallet::Config>::WeightInfo::token_mint_request_decline_everusd())]
-        pub fn token_mint_request_decline_everusd(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+        pub fn token_mint_request_decline_everusd(origin: OriginFor<T>, who: T::AccountId, #[pallet::compact] request_id: TokenMintRequestId) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(accounts::Pallet::<T>::account_is_custodian(&caller),Error::<T>::AccountNotAuthorized);
-            ensure!(MintRequestEverUSD::<T>::contains_key(&who), Error::<T>::MintRequestDoesntExist);
-            let amount = MintRequestEverUSD::<T>::get(&who).amount;
-            MintRequestEverUSD::<T>::remove(&who);
-            Self::deposit_event(Event::<T>::MintRequestDeclined(caller, who, amount));
+            let request = MintRequestEverUSD::<T>::take(&who, request_id).ok_or(Error::<T>::MintRequestDoesntExist)?;
+            Self::deposit_event(Event::<T>::MintRequestDeclined(caller, who, request_id, request.remaining()));
             Ok(().into())
         }
 
         /// <pre>
-        /// Method: token_burn_request_create_everusd(origin: OriginFor<T>, amount_to_burn: EverUSDBalance)
+        /// Method: token_burn_request_create_everusd(origin: OriginFor<T>, amount_to_burn: EverUSDBalance, payment_reference: Vec<u8>)
         /// Arguments:  origin: T::AccountId - transaction caller
         ///             amount_to_burn: EverUSDBalance - amount of tokens to burn
+        ///             payment_reference: Vec<u8> - opaque bank account reference or hash of the bank details
         /// Access: Investor or Issuer role
         ///
         /// Creates a request to burn given amount of EverUSD tokens on caller's balance.
         /// Custodian account confirms request after sending payment in USD to target account's owner
-        /// It's possible to create only one request per account. Burn request has a time-to-live
+        /// Account can have up to MAX_TOKEN_REQUESTS_PER_ACCOUNT outstanding requests, their total
+        /// amount can't exceed the balance. Burn request has a time-to-live
         /// and becomes invalidated after it.
         /// </pre>
         #[pallet::weight(<T as pallet::Config>::WeightInfo::token_burn_request_create_everusd())]
-        pub fn token_burn_request_create_everusd(origin: OriginFor<T>, #[pallet::compact]  amount_to_burn: EverUSDBalance) -> DispatchResult {
+        pub fn token_burn_request_create_everusd(origin: OriginFor<T>, #[pallet::compact]  amount_to_burn: EverUSDBalance, payment_reference: Vec<u8>) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(accounts::Pallet::<T>::account_token_mint_burn_allowed(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(amount_to_burn > 0, Error::<T>::BurnRequestParamIncorrect);
+            ensure!(payment_reference.len() <= MAX_PAYMENT_REFERENCE_LEN, Error::<T>::BurnRequestParamIncorrect);
+
+            let now = Timestamp::<T>::get();
+            let outstanding: Vec<_> = BurnRequestEverUSD::<T>::iter_prefix_values(&caller)
+                .filter(|request| !request.is_expired(now))
+                .collect();
+            ensure!(outstanding.len() < MAX_TOKEN_REQUESTS_PER_ACCOUNT, Error::<T>::BurnRequestAlreadyExist);
 
+            let requested: EverUSDBalance = outstanding.iter().map(|request| request.remaining()).sum();
             let current_balance = BalanceEverUSD::<T>::get(&caller);
-            ensure!(amount_to_burn <= current_balance, Error::<T>::BalanceOverdraft);
-
-            BurnRequestEverUSD::<T>::try_mutate(&caller,|request|->DispatchResult{
-                let now = Timestamp::<T>::get();
-                if !request.is_expired( now ) {
-                    Err( Error::<T>::BurnRequestAlreadyExist.into() )
-                }else{
-                    *request = TokenBurnRequestStruct {
-                        amount: amount_to_burn,
-                        deadline: now +  T::BurnRequestTtl::get().into(),
-                    };
-                    Self::deposit_event(Event::<T>::BurnRequestCreated(caller.clone(), amount_to_burn));
-                    Ok(())
-                }
-            })?;
+            ensure!(requested.saturating_add(amount_to_burn) <= current_balance, Error::<T>::BalanceOverdraft);
+
+            let id = LastTokenBurnRequestId::<T>::mutate(|id| {
+                *id += 1;
+                *id
+            });
+            BurnRequestEverUSD::<T>::insert(&caller, id, TokenBurnRequestStruct {
+                id,
+                amount: amount_to_burn,
+                confirmed: 0,
+                deadline: now +  T::BurnRequestTtl::get().into(),
+                payment_reference: payment_reference.clone(),
+            });
+            Self::deposit_event(Event::<T>::BurnRequestCreated(caller, id, amount_to_burn, payment_reference));
             Ok(().into())
         }
 
         /// <pre>
-        /// Method: token_burn_request_revoke_everusd(origin)
+        /// Method: token_burn_request_revoke_everusd(origin, request_id: TokenBurnRequestId)
         /// Arguments: origin: T::AccountId - transaction caller
+        ///            request_id: TokenBurnRequestId - burn request identifier
         /// Access: Investor or Issuer role
         ///
-        /// Revokes and deletes currently existing burn request, created by caller's account
+        /// Revokes and deletes the burn request, created by caller's account
         /// </pre>
         #[pallet::weight(<T as pallet::Config>::WeightInfo::token_burn_request_revoke_everusd())]
-        pub fn token_burn_request_revoke_everusd(origin: OriginFor<T>,) -> DispatchResult {
+        pub fn token_burn_request_revoke_everusd(origin: OriginFor<T>, #[pallet::compact] request_id: TokenBurnRequestId) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            ensure!(BurnRequestEverUSD::<T>::contains_key(&caller), Error::<T>::BurnRequestDoesntExist);
-            let amount = BurnRequestEverUSD::<T>::get(&caller).amount;
-            BurnRequestEverUSD::<T>::remove(&caller);
-            Self::deposit_event(Event::<T>::BurnRequestRevoked(caller, amount));
+            let request = BurnRequestEverUSD::<T>::take(&caller, request_id).ok_or(Error::<T>::BurnRequestDoesntExist)?;
+            Self::deposit_event(Event::<T>::BurnRequestRevoked(caller, request_id, request.remaining()));
             Ok(().into())
         }
 
         /// <pre>
-        /// Method: token_burn_request_confirm_everusd(origin: OriginFor<T>, who: T::AccountId, amount: EverUSDBalance)
+        /// Method: token_burn_request_confirm_everusd(origin: OriginFor<T>, who: T::AccountId, request_id: TokenBurnRequestId, amount: EverUSDBalance)
         /// Arguments:  origin: T::AccountId - transaction caller
         ///             who: T::AccountId - target account
-        ///             amount: EverUSDBalance - amount of tokens to mint, confirmed by Custodian
+        ///             request_id: TokenBurnRequestId - burn request identifier
+        ///             amount: EverUSDBalance - amount of tokens to burn, confirmed by Custodian
         /// Access: Custodian role
         ///
         /// Confirms the burn request of account, destroying "amount" of tokens on its balance.
+        /// Custodian can confirm the request partially, when the payment is sent in several
+        /// transfers. The request is deleted after the whole amount is confirmed.
+        /// Confirmed amount is limited by the global and account's rolling window limits.
         /// </pre>
         #[pallet::weight(<T as pallet::Config>::WeightInfo::token_burn_request_confirm_everusd())]
-        pub fn token_burn_request_confirm_everusd(origin: OriginFor<T>, who: T::AccountId, #[pallet::compact]  amount: EverUSDBalance) -> DispatchResult {
+        pub fn token_burn_request_confirm_everusd(origin: OriginFor<T>, who: T::AccountId, #[pallet::compact] request_id: TokenBurnRequestId, #[pallet::compact]  amount: EverUSDBalance) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(accounts::Pallet::<T>::account_is_custodian(&caller),Error::<T>::AccountNotAuthorized);
-            ensure!(BurnRequestEverUSD::<T>::contains_key(&who), Error::<T>::BurnRequestDoesntExist);
-            let burn_request = BurnRequestEverUSD::<T>::get(&who);
+            let mut burn_request = BurnRequestEverUSD::<T>::get(&who, request_id).ok_or(Error::<T>::BurnRequestDoesntExist)?;
             let now = Timestamp::<T>::get();
             ensure!(!burn_request.is_expired(now), Error::<T>::BurnRequestObsolete);
-            // remove tokens from user's balance and decrease total supply of EverUSD
-            let amount_to_sub = burn_request.amount;
             // prevent unacceptable commit
-            ensure!(amount_to_sub==amount, Error::<T>::MintRequestParamIncorrect );
+            ensure!(amount > 0 && amount <= burn_request.remaining(), Error::<T>::BurnRequestParamIncorrect);
+            Self::everusd_limit_check(&who, now, false, amount)?;
 
-            Self::balance_sub(&who, amount_to_sub)?;
+            // remove tokens from user's balance and decrease total supply of EverUSD
+            Self::balance_sub(&who, amount)?;
             TotalSupplyEverUSD::<T>::mutate(|total|{
-                *total-=amount_to_sub;
+                *total-=amount;
             });
+            ConfirmedBurnEverUSD::<T>::mutate(|total| *total = total.saturating_add(amount));
+            Self::everusd_limit_record(&who, now, false, amount);
 
-            BurnRequestEverUSD::<T>::remove(&who);
-            Self::deposit_event(Event::<T>::BurnRequestConfirmed(caller, who, amount_to_sub));
-            Self::purge_expired_burn_requests(now);
+            burn_request.confirmed += amount;
+            if burn_request.remaining() == 0 {
+                BurnRequestEverUSD::<T>::remove(&who, request_id);
+            } else {
+                BurnRequestEverUSD::<T>::insert(&who, request_id, burn_request);
+            }
+            Self::deposit_event(Event::<T>::BurnRequestConfirmed(caller, who, request_id, amount));
             Ok(().into())
         }
 
         /// <pre>
-        /// Method: token_burn_request_decline_everusd(origin: OriginFor<T>, who: T::AccountId)
+        /// Method: token_burn_request_decline_everusd(origin: OriginFor<T>, who: T::AccountId, request_id: TokenBurnRequestId)
         /// Arguments:  origin: T::AccountId - transaction caller
         ///             who: T::AccountId - target account
+        ///             request_id: TokenBurnRequestId - burn request identifier
         /// Access: Custodian role
         ///
         /// Declines and deletes the burn request of account (Custodian)
         /// </pre>
         #[pallet::weight(<T as pallet::Config>::WeightInfo::token_burn_request_decline_everusd())]
-        pub fn token_burn_request_decline_everusd(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+        pub fn token_burn_request_decline_everusd(origin: OriginFor<T>, who: T::AccountId, #[pallet::compact] request_id: TokenBurnRequestId) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(accounts::Pallet::<T>::account_is_custodian(&caller),Error::<T>::AccountNotAuthorized);
-            ensure!(BurnRequestEverUSD::<T>::contains_key(&who), Error::<T>::BurnRequestDoesntExist);
-            let amount = BurnRequestEverUSD::<T>::get(&who).amount;
-            BurnRequestEverUSD::<T>::remove(&who);
-            Self::deposit_event(Event::<T>::BurnRequestDeclined(caller, who, amount));
+            let request = BurnRequestEverUSD::<T>::take(&who, request_id).ok_or(Error::<T>::BurnRequestDoesntExist)?;
+            Self::deposit_event(Event::<T>::BurnRequestDeclined(caller, who, request_id, request.remaining()));
             Ok(().into())
         }
 
+        /// <pre>
+        /// Method: everusd_transfer(origin: OriginFor<T>, to: T::AccountId, amount: EverUSDBalance, memo: Option<Vec<u8>>)
+        /// Arguments:  origin: T::AccountId - transaction caller
+        ///             to: T::AccountId - recipient account
+        ///             amount: EverUSDBalance - amount of tokens to transfer
+        ///             memo: Option<Vec<u8>> - optional payment reference, up to MAX_TRANSFER_MEMO_LEN bytes
+        /// Access: Investor or Issuer role
+        ///
+        /// Transfers given amount of EverUSD tokens from caller's balance to the recipient's balance.
+        /// Recipient should have Investor or Issuer role too. Total supply of EverUSD isn't changed
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::everusd_transfer())]
+        pub fn everusd_transfer(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+            #[pallet::compact] amount: EverUSDBalance,
+            memo: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(accounts::Pallet::<T>::account_token_mint_burn_allowed(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(accounts::Pallet::<T>::account_token_mint_burn_allowed(&to), Error::<T>::AccountNotAuthorized);
+            ensure!(amount > 0 && caller != to, Error::<T>::InvalidAction);
+            ensure!(memo.as_ref().map_or(true, |memo| memo.len() <= MAX_TRANSFER_MEMO_LEN), Error::<T>::InvalidAction);
+            ensure!(amount <= Self::balance_everusd(&caller), Error::<T>::BalanceOverdraft);
+
+            Self::transfer_everusd(&caller, &to, amount)?;
+            Self::deposit_event(Event::<T>::EverUSDTransferred(caller, to, amount, memo));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: reserve_attestation_publish(origin: OriginFor<T>, reserve: EverUSDBalance, period_start: T::Moment, period_end: T::Moment, auditor: T::AccountId, statement: FileId)
+        /// Arguments:  origin: T::AccountId - transaction caller
+        ///             reserve: EverUSDBalance - USD reserve at the end of the period, in EverUSD units
+        ///             period_start: T::Moment - start of the attestation period
+        ///             period_end: T::Moment - end of the attestation period
+        ///             auditor: T::AccountId - auditor, who co-signs the attestation
+        ///             statement: FileId - file (stored in filesign) with the bank statement
+        /// Access: Custodian role
+        ///
+        /// Publishes proof of the USD reserve, backing EverUSD. Attestation becomes effective
+        /// after the auditor signs it. Since the first signed attestation, minting of EverUSD
+        /// requires the latest signed attestation to be fresh (see ReserveAttestationTtl)
+        /// and to cover the total supply
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::reserve_attestation_publish())]
+        pub fn reserve_attestation_publish(
+            origin: OriginFor<T>,
+            #[pallet::compact] reserve: EverUSDBalance,
+            #[pallet::compact] period_start: T::Moment,
+            #[pallet::compact] period_end: T::Moment,
+            auditor: T::AccountId,
+            statement: FileId,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(accounts::Pallet::<T>::account_is_custodian(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(accounts::Pallet::<T>::account_is_auditor(&auditor), Error::<T>::ReserveAttestationParamIncorrect);
+            let now = Timestamp::<T>::get();
+            ensure!(period_start < period_end && period_end <= now, Error::<T>::ReserveAttestationParamIncorrect);
+
+            let id = LastReserveAttestationId::<T>::mutate(|id| {
+                *id += 1;
+                *id
+            });
+            ReserveAttestation::<T>::insert(id, ReserveAttestationStruct {
+                id,
+                custodian: caller.clone(),
+                auditor,
+                reserve,
+                supply: TotalSupplyEverUSD::<T>::get(),
+                period_start,
+                period_end,
+                statement,
+                created_at: now,
+                signed: false,
+            });
+            Self::deposit_event(Event::<T>::ReserveAttestationPublished(caller, id, reserve));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: reserve_attestation_sign(origin: OriginFor<T>, attestation: ReserveAttestationId)
+        /// Arguments:  origin: T::AccountId - transaction caller
+        ///             attestation: ReserveAttestationId - attestation identifier
+        /// Access: auditor, named in the attestation
+        ///
+        /// Co-signs the reserve attestation. Signed attestation with the latest period
+        /// becomes the current proof of reserves
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::reserve_attestation_sign())]
+        pub fn reserve_attestation_sign(origin: OriginFor<T>, #[pallet::compact] attestation: ReserveAttestationId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(accounts::Pallet::<T>::account_is_auditor(&caller), Error::<T>::AccountNotAuthorized);
+            let reserve = ReserveAttestation::<T>::try_mutate(attestation, |item| -> Result<EverUSDBalance, DispatchError> {
+                let item = item.as_mut().ok_or(Error::<T>::ReserveAttestationNotFound)?;
+                ensure!(item.auditor == caller, Error::<T>::AccountNotAuthorized);
+                ensure!(!item.signed, Error::<T>::ReserveAttestationAlreadySigned);
+                item.signed = true;
+
+                let is_latest = LatestReserveAttestation::<T>::get()
+                    .and_then(ReserveAttestation::<T>::get)
+                    .map_or(true, |latest| latest.period_end < item.period_end);
+                if is_latest {
+                    LatestReserveAttestation::<T>::put(attestation);
+                }
+                Ok(item.reserve)
+            })?;
+            Self::deposit_event(Event::<T>::ReserveAttestationSigned(caller, attestation, reserve));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: everusd_limit_set_global(origin: OriginFor<T>, limit: EverUSDLimitStruct)
+        /// Arguments:  origin: T::AccountId - transaction caller
+        ///             limit: EverUSDLimitStruct - rolling window limit, zero amount means no limit
+        /// Access: Master role
+        ///
+        /// Sets limit of EverUSD, minted and burned by Custodian for all accounts
+        /// in the rolling window
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::everusd_limit_set())]
+        pub fn everusd_limit_set_global(origin: OriginFor<T>, limit: EverUSDLimitStruct) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(accounts::Pallet::<T>::account_is_master(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(limit.is_valid(), Error::<T>::EverUSDLimitParamIncorrect);
+
+            EverUSDGlobalLimit::<T>::put(limit.clone());
+            Self::deposit_event(Event::<T>::EverUSDGlobalLimitSet(caller, limit));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: everusd_limit_set_role(origin: OriginFor<T>, role: RoleMask, limit: Option<EverUSDLimitStruct>)
+        /// Arguments:  origin: T::AccountId - transaction caller
+        ///             role: RoleMask - Issuer or Investor role
+        ///             limit: Option<EverUSDLimitStruct> - rolling window quota, None removes the quota
+        /// Access: Master role
+        ///
+        /// Sets quota of EverUSD, minted and burned for each account with the role
+        /// in the rolling window. Quota doesn't apply to accounts with own limit
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::everusd_limit_set())]
+        pub fn everusd_limit_set_role(origin: OriginFor<T>, role: RoleMask, limit: Option<EverUSDLimitStruct>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(accounts::Pallet::<T>::account_is_master(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(role == ISSUER_ROLE_MASK || role == INVESTOR_ROLE_MASK, Error::<T>::EverUSDLimitParamIncorrect);
+            ensure!(limit.as_ref().map_or(true, |limit| limit.is_valid()), Error::<T>::EverUSDLimitParamIncorrect);
+
+            EverUSDRoleLimit::<T>::set(role, limit.clone());
+            Self::deposit_event(Event::<T>::EverUSDRoleLimitSet(caller, role, limit));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: everusd_limit_set_account(origin: OriginFor<T>, who: T::AccountId, limit: Option<EverUSDLimitStruct>)
+        /// Arguments:  origin: T::AccountId - transaction caller
+        ///             who: T::AccountId - target account
+        ///             limit: Option<EverUSDLimitStruct> - rolling window limit, None removes the limit
+        /// Access: Master role
+        ///
+        /// Sets limit of EverUSD, minted and burned for the account in the rolling window.
+        /// The limit replaces quotas of the account's roles
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::everusd_limit_set())]
+        pub fn everusd_limit_set_account(origin: OriginFor<T>, who: T::AccountId, limit: Option<EverUSDLimitStruct>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(accounts::Pallet::<T>::account_is_master(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(limit.as_ref().map_or(true, |limit| limit.is_valid()), Error::<T>::EverUSDLimitParamIncorrect);
+
+            EverUSDAccountLimit::<T>::set(&who, limit.clone());
+            Self::deposit_event(Event::<T>::EverUSDAccountLimitSet(caller, who, limit));
+            Ok(())
+        }
+
         // Bonds handling functions
 
         /// <pre>
@@ -642,6 +1234,7 @@ pub mod pallet {
                     nonce: 0,
             };
             T::OnAddBond::on_add_bond(&bond, &mut item);
+            Self::update_account_bond_role_index(&bond, Some(&item), &caller);
             BondRegistry::<T>::insert(&bond, item);
 
             Self::deposit_event(Event::<T>::BondAdded(caller, bond));
@@ -676,8 +1269,12 @@ pub mod pallet {
                             matches!(item.state, BondState::PREPARE),
                             Error::<T>::BondStateNotPermitAction
                         );
-                        item.manager = Some(acc);
+                        let previous = item.manager.replace(acc.clone());
                         item.nonce += 1;
+                        if let Some(previous) = previous {
+                            Self::update_account_bond_role_index(&bond, Some(&*item), &previous);
+                        }
+                        Self::update_account_bond_role_index(&bond, Some(&*item), &acc);
                         Self::deposit_event(Event::<T>::BondChanged(caller, bond));
                         Ok(().into())
                     },
@@ -712,8 +1309,12 @@ pub mod pallet {
                             matches!(item.state, BondState::PREPARE | BondState::BOOKING),
                             Error::<T>::BondStateNotPermitAction
                         );
-                        item.auditor = Some(acc);
+                        let previous = item.auditor.replace(acc.clone());
                         item.nonce += 1;
+                        if let Some(previous) = previous {
+                            Self::update_account_bond_role_index(&bond, Some(&*item), &previous);
+                        }
+                        Self::update_account_bond_role_index(&bond, Some(&*item), &acc);
                         Self::deposit_event(Event::<T>::BondChanged(caller, bond));
                         Ok(().into())
                     },
@@ -742,8 +1343,12 @@ pub mod pallet {
             BondRegistry::<T>::try_mutate(&bond, |maybe_item|{
                 match maybe_item {
                     Some(item) => {
-                        item.impact_reporter = Some(acc);
+                        let previous = item.impact_reporter.replace(acc.clone());
                         item.nonce += 1;
+                        if let Some(previous) = previous {
+                            Self::update_account_bond_role_index(&bond, Some(&*item), &previous);
+                        }
+                        Self::update_account_bond_role_index(&bond, Some(&*item), &acc);
                         Self::deposit_event(Event::<T>::BondChanged(caller, bond));
                         Ok(().into())
                     },
@@ -863,7 +1468,8 @@ pub mod pallet {
         ///    financial operations with this package are performed
         ///
         /// Bond must be in BOOKING, ACTIVE, BANKRUPT state, amount of Bond Units
-        /// should not except "bond_units_maxcap_amount"
+        /// should not except "bond_units_maxcap_amount". Bond in bookbuilding mode
+        /// accepts orders (bond_unit_order_place) instead in BOOKING state
         /// </pre>
         #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_unit_package_buy())]
         pub fn bond_unit_package_buy(origin: OriginFor<T>, bond: BondId,#[pallet::compact]  nonce: u64,#[pallet::compact] unit_amount: BondUnitAmount ) -> DispatchResult {
@@ -879,6 +1485,11 @@ pub mod pallet {
                         );
                         // issuer cannot buy his own bonds
                         ensure!(item.issuer != caller, Error::<T>::AccountNotAuthorized);
+                        // in bookbuilding mode bond units are allocated at bond activation
+                        ensure!(
+                            item.state != BondState::BOOKING || item.inner.bookbuilding.is_none(),
+                            Error::<T>::BondStateNotPermitAction
+                        );
         
                         let issued_amount = unit_amount.checked_add(item.issued_amount)
                             .ok_or(Error::<T>::BalanceOverdraft)?;
@@ -887,16 +1498,19 @@ pub mod pallet {
                             issued_amount <= item.inner.bond_units_maxcap_amount,
                             Error::<T>::BondParamIncorrect
                         );
-        
-                        let package_value =  item.par_value( unit_amount ) ;
-        
-                        Self::balance_sub(&caller, package_value)?;
+                        T::BondTransferGuard::ensure_can_acquire(&bond, None, &caller, unit_amount)?;
         
                         let now = Timestamp::<T>::get();
         
                         // get the number of seconds after bond activation.
                         // zero value if the bond has not activated yet
-                        let (acquisition,_) = item.time_passed_after_activation( now ).unwrap_or( (0,0) );
+                        let (acquisition, period) = item.time_passed_after_activation( now ).unwrap_or( (0,0) );
+
+                        // amortized bond is sold by outstanding par value,
+                        // zero-coupon bond is sold by accreted value
+                        let package_value = unit_amount as EverUSDBalance * item.unit_price( acquisition, period ) ;
+        
+                        Self::balance_sub(&caller, package_value)?;
                         // @FIXME assess the costs of current array struct for storing packages and
                         // compare them with a more efficient way to store data
                         BondUnitPackageRegistry::<T>::mutate(&bond, &caller, |packages|{
@@ -908,6 +1522,7 @@ pub mod pallet {
                                 }
                             );
                         });
+                        Self::update_account_bond_index(&bond, &caller);
         
                         item.issued_amount = issued_amount;
         
@@ -922,7 +1537,7 @@ pub mod pallet {
                             let free_balance = item.get_free_balance();
                             if free_balance > 0 {
                                 item.bond_debit -= free_balance;
-                                Self::balance_add(&item.issuer, free_balance)?;
+                                Self::pay_proceeds(&bond, item, free_balance)?;
                             }
                         }else{
                             // in BondState::PREPARE just increase assets and liabilities of the Bond
@@ -961,7 +1576,7 @@ pub mod pallet {
                     Some(item) => {
                         ensure!(item.state == BondState::BOOKING, Error::<T>::BondStateNotPermitAction );
                         ensure!(item.issued_amount >= unit_amount, Error::<T>::BondParamIncorrect);
-                        let package_value = item.par_value( unit_amount ) ;
+                        let package_value = item.issue_price( unit_amount ) ;
                         ensure!(item.bond_credit >= package_value, Error::<T>::BondParamIncorrect);
         
                         BondUnitPackageRegistry::<T>::try_mutate(&bond, &caller, |packages|->DispatchResult{
@@ -976,6 +1591,7 @@ pub mod pallet {
                                 Err( Error::<T>::BondParamIncorrect.into() )
                             }
                         })?;
+                        Self::update_account_bond_index(&bond, &caller);
         
                         item.decrease( package_value );
                         item.issued_amount -= unit_amount;
@@ -990,6 +1606,156 @@ pub mod pallet {
             })
         }
 
+        /// <pre>
+        /// Method: bond_unit_order_place(origin: OriginFor<T>, bond: BondId, unit_amount: BondUnitAmount)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            unit_amount: BondUnitAmount - amount of ordered bond units
+        /// Access: only accounts with Investor role
+        ///
+        /// Places bookbuilding order for bond in BOOKING state, configured with "bookbuilding"
+        /// allocation rule. Issue price of ordered Bond Units is reserved on the Investor's account.
+        /// Orders aren't limited by "bond_units_maxcap_amount" in total: Bond Units are allocated
+        /// among orders at bond activation, unfilled amount is refunded. Investor can have
+        /// one order per bond, placing new order replaces the previous one. Bond can have up to
+        /// MAX_BOND_UNIT_ORDERS orders
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_unit_order_place())]
+        #[transactional]
+        pub fn bond_unit_order_place(origin: OriginFor<T>, bond: BondId, #[pallet::compact] unit_amount: BondUnitAmount) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(accounts::Pallet::<T>::account_is_investor(&caller), Error::<T>::AccountNotAuthorized);
+            let item = BondRegistry::<T>::get(&bond).ok_or(Error::<T>::BondNotFound)?;
+            ensure!(item.state == BondState::BOOKING, Error::<T>::BondStateNotPermitAction);
+            ensure!(item.inner.bookbuilding.is_some(), Error::<T>::BondIsNotConfigured);
+            // issuer cannot buy his own bonds
+            ensure!(item.issuer != caller, Error::<T>::AccountNotAuthorized);
+            ensure!(
+                unit_amount > 0 && unit_amount <= item.inner.bond_units_maxcap_amount,
+                Error::<T>::BondParamIncorrect
+            );
+            T::BondTransferGuard::ensure_can_acquire(&bond, None, &caller, unit_amount)?;
+
+            if let Some(order) = BondUnitOrder::<T>::take(&bond, &caller) {
+                Self::balance_unreserve(&caller, order.amount)?;
+            } else {
+                let count = BondUnitOrderCount::<T>::get(&bond);
+                ensure!(count < MAX_BOND_UNIT_ORDERS, Error::<T>::BondUnitOrderLimitExceeded);
+                BondUnitOrderCount::<T>::insert(&bond, count + 1);
+            }
+            let order = BondUnitOrderStruct {
+                id: LastBondUnitOrderId::<T>::mutate(|id| {
+                    *id += 1;
+                    *id
+                }),
+                bond_units: unit_amount,
+                amount: item.issue_price(unit_amount),
+            };
+            Self::balance_reserve(&caller, order.amount)?;
+            BondUnitOrder::<T>::insert(&bond, &caller, &order);
+            Self::update_account_bond_index(&bond, &caller);
+
+            Self::deposit_event(Event::<T>::BondUnitOrderPlace(caller, bond, order));
+            Ok(().into())
+        }
+
+        /// <pre>
+        /// Method: bond_unit_order_cancel(origin: OriginFor<T>, bond: BondId)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        /// Access: Investor, placed the order
+        ///
+        /// Removes bookbuilding order of the caller before bond activation and returns
+        /// reserved EverUSD to the caller's balance
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_unit_order_cancel())]
+        pub fn bond_unit_order_cancel(origin: OriginFor<T>, bond: BondId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let order = BondUnitOrder::<T>::get(&bond, &caller).ok_or(Error::<T>::BondParamIncorrect)?;
+            Self::balance_unreserve(&caller, order.amount)?;
+            BondUnitOrder::<T>::remove(&bond, &caller);
+            BondUnitOrderCount::<T>::mutate(&bond, |count| *count = count.saturating_sub(1));
+            Self::update_account_bond_index(&bond, &caller);
+
+            Self::deposit_event(Event::<T>::BondUnitOrderCancel(caller, bond, order.id));
+            Ok(().into())
+        }
+
+        /// <pre>
+        /// Method: bond_transfer_restriction_set(origin: OriginFor<T>, bond: BondId, restriction: BondTransferRestrictionStruct)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            restriction: BondTransferRestrictionStruct - new restrictions
+        /// Access: bond Issuer
+        ///
+        /// Restricts acquisition of bond units to whitelisted investors, to investors from
+        /// given jurisdictions, and limits the number of bond units held by one investor.
+        /// Restrictions are applied to new acquisitions only, current holdings aren't affected
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_transfer_restriction_set())]
+        pub fn bond_transfer_restriction_set(
+            origin: OriginFor<T>,
+            bond: BondId,
+            restriction: BondTransferRestrictionStruct,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let item = BondRegistry::<T>::get(&bond).ok_or(Error::<T>::BondNotFound)?;
+            ensure!(item.issuer == caller, Error::<T>::BondAccessDenied);
+            ensure!(restriction.is_valid(), Error::<T>::BondParamIncorrect);
+
+            BondTransferRestriction::<T>::insert(&bond, restriction);
+            Self::deposit_event(Event::<T>::BondTransferRestrictionSet(caller, bond));
+            Ok(().into())
+        }
+
+        /// <pre>
+        /// Method: bond_whitelist_add(origin: OriginFor<T>, bond: BondId, investor: T::AccountId, jurisdiction: Jurisdiction)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            investor: T::AccountId - investor account
+        ///            jurisdiction: Jurisdiction - ISO 3166-1 country code of the investor
+        /// Access: bond Issuer
+        ///
+        /// Adds investor to the bond whitelist or updates investor's jurisdiction
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_whitelist_add())]
+        pub fn bond_whitelist_add(
+            origin: OriginFor<T>,
+            bond: BondId,
+            investor: T::AccountId,
+            jurisdiction: Jurisdiction,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let item = BondRegistry::<T>::get(&bond).ok_or(Error::<T>::BondNotFound)?;
+            ensure!(item.issuer == caller, Error::<T>::BondAccessDenied);
+            ensure!(accounts::Pallet::<T>::account_is_investor(&investor), Error::<T>::AccountNotAuthorized);
+
+            BondWhitelist::<T>::insert(&bond, &investor, jurisdiction);
+            Self::deposit_event(Event::<T>::BondWhitelistAdded(caller, bond, investor, jurisdiction));
+            Ok(().into())
+        }
+
+        /// <pre>
+        /// Method: bond_whitelist_remove(origin: OriginFor<T>, bond: BondId, investor: T::AccountId)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            investor: T::AccountId - investor account
+        /// Access: bond Issuer
+        ///
+        /// Removes investor from the bond whitelist. Bond units, held by the investor, remain
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_whitelist_remove())]
+        pub fn bond_whitelist_remove(origin: OriginFor<T>, bond: BondId, investor: T::AccountId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let item = BondRegistry::<T>::get(&bond).ok_or(Error::<T>::BondNotFound)?;
+            ensure!(item.issuer == caller, Error::<T>::BondAccessDenied);
+            ensure!(BondWhitelist::<T>::contains_key(&bond, &investor), Error::<T>::AccountNotExist);
+
+            BondWhitelist::<T>::remove(&bond, &investor);
+            Self::deposit_event(Event::<T>::BondWhitelistRemoved(caller, bond, investor));
+            Ok(().into())
+        }
+
         /// <pre>
         /// Method: bond_withdraw(origin: OriginFor<T>, bond: BondId)
         /// Arguments: origin: T::AccountId - transaction caller
@@ -1001,10 +1767,12 @@ pub mod pallet {
         /// haven't bought enough of BUs), bond managers(Issuer, Manager) or Bond Arranger can return
         /// bond in PREPARE state, denying acquisiton of new bond units, and allowing
         /// team to change parameters of bond and then try to release it with more suitable
-        /// for Investors parameters. Cannot be called until "mincap_deadline"
+        /// for Investors parameters. Cannot be called until "mincap_deadline".
+        /// Bookbuilding orders are cancelled, reserved EverUSD are returned to Investors
         /// </pre>
         // Called after the Bond was released but not raised enough tokens until the deadline
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_withdraw())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_withdraw()
+            .saturating_add(<T as pallet::Config>::WeightInfo::bond_unit_order_allocate().saturating_mul(MAX_BOND_UNIT_ORDERS as Weight)))]
         pub fn bond_withdraw(origin: OriginFor<T>, bond: BondId) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             // Bond issuer, bond Manager, or Bond Arranger can do it
@@ -1013,7 +1781,10 @@ pub mod pallet {
                     Some(item) => {
                         ensure!( item.state == BondState::BOOKING, Error::<T>::BondStateNotPermitAction );
                         // Ensure the Bond raises less then bond_units_mincap_amount bond units
-                        ensure!(item.inner.bond_units_mincap_amount > item.issued_amount, Error::<T>::BondParamIncorrect);
+                        ensure!(
+                            item.inner.bond_units_mincap_amount > Self::subscribed_bond_units(&bond, item),
+                            Error::<T>::BondParamIncorrect
+                        );
                         ensure!(
                             item.issuer == caller || item.manager == Some(caller.clone()) || accounts::Pallet::<T>::account_is_bond_arranger(&caller) ,
                             Error::<T>::BondAccessDenied
@@ -1022,28 +1793,8 @@ pub mod pallet {
                         // Ensure booking deadline is in the future
                         ensure!(item.inner.mincap_deadline <= now, Error::<T>::BondStateNotPermitAction);
         
-                        item.state = BondState::PREPARE;
-                        item.nonce += 1;
-                        assert!(item.bond_credit == item.par_value(item.issued_amount));
-                        // @TODO make it lazy. this implementation do much work to restore balances
-                        // that is too CPU and memory expensive.
-                        // For each bondholder
-                        for (bondholder, package) in BondUnitPackageRegistry::<T>::iter_prefix(&bond){
-                              let bondholder_total_amount: BondUnitAmount = package.iter()
-                              .map(|item| item.bond_units )
-                              .sum();
-        
-                              item.issued_amount -= bondholder_total_amount;
-        
-                              let transfer = item.par_value( bondholder_total_amount ) ;
-                              item.decrease(transfer);
-        
-                              Self::balance_add(&bondholder, transfer)?;
-                        }
-                        assert!(item.bond_credit == 0);
-                        assert!(item.issued_amount == 0);
-        
-                        BondUnitPackageRegistry::<T>::remove_prefix(&bond, None);
+                        Self::return_bond_units(&bond, item)?;
+                        Self::refund_bond_unit_orders(&bond)?;
         
                         Self::deposit_event(Event::<T>::BondWithdrawal(caller, bond));
                         Ok(().into())
@@ -1067,9 +1818,15 @@ pub mod pallet {
         /// BondImpactReportStruct-s with amount of elements, equal to amount of payment periods.
         /// This array will be used to store future impact_report_data and effective
         /// coupon_yield_rate (depending on impact_report_data for each period). Requires that
-        /// "bond_units_mincap_amount" was reached.
+        /// "bond_units_mincap_amount" was reached. Issuance fees of the bond are withheld
+        /// from the sum, transferred to Issuer. Proceeds of the bond with declared use of proceeds
+        /// are reserved on the Issuer's account.
+        /// In bookbuilding mode Bond Units are allocated among orders first, according to
+        /// "bookbuilding" allocation rule. Unfilled amount of each order is refunded
         /// </pre>
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_activate())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_activate()
+            .saturating_add(<T as pallet::Config>::WeightInfo::bond_unit_order_allocate().saturating_mul(MAX_BOND_UNIT_ORDERS as Weight)))]
+        #[transactional]
         pub fn bond_activate(origin: OriginFor<T>, bond: BondId,#[pallet::compact]  nonce: u64) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             //Bond can be activated only by Bond Arranger
@@ -1080,16 +1837,19 @@ pub mod pallet {
                     Some(item) => {
                         ensure!(item.nonce == nonce, Error::<T>::BondNonceObsolete );
                         ensure!(item.state == BondState::BOOKING, Error::<T>::BondStateNotPermitAction);
-                        ensure!(item.inner.bond_units_mincap_amount <= item.issued_amount, Error::<T>::BondParamIncorrect);
                         // auditor should be assigned before
                         ensure!(item.auditor.is_some(), Error::<T>::BondIsNotConfigured);
+                        if let Some(rule) = item.inner.bookbuilding {
+                            Self::allocate_bond_unit_orders(&bond, item, rule)?;
+                        }
+                        ensure!(item.inner.bond_units_mincap_amount <= item.issued_amount, Error::<T>::BondParamIncorrect);
         
                         let now = Timestamp::<T>::get();
                         item.state = BondState::ACTIVE;
                         item.nonce += 1;
                         item.active_start_date = now;
                         // Decrease liabilities by value of fund
-                        assert_eq!(item.bond_credit, item.par_value( item.issued_amount ) );
+                        assert_eq!(item.bond_credit, item.issue_price( item.issued_amount ) );
                         assert!(item.bond_credit == item.bond_debit);
                         item.bond_credit = 0 ;
         
@@ -1103,9 +1863,10 @@ pub mod pallet {
         
                         BondImpactReport::<T>::insert(&bond, &reports);
         
-                        // withdraw all available bond fund
+                        // withdraw all available bond fund, except issuance fees
                         let amount = item.bond_debit;
-                        Self::balance_add(&item.issuer, item.bond_debit)?;
+                        let fee = Self::charge_bond_fees(&bond, &item.inner.fees, BondFeeKind::ISSUANCE, amount);
+                        Self::pay_proceeds(&bond, item, amount - fee)?;
                         item.bond_debit = 0;
         
                         Self::deposit_event(Event::<T>::BondActivated(caller, bond, amount));
@@ -1130,26 +1891,32 @@ pub mod pallet {
         #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_impact_report_send())]
         pub fn bond_impact_report_send(origin: OriginFor<T>, bond: BondId,#[pallet::compact] period: BondPeriodNumber,#[pallet::compact] impact_data: u64 ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            let now = Timestamp::<T>::get();
-            let moment = {
-                let item = BondRegistry::<T>::get(bond).ok_or(Error::<T>::BondNotFound)?;
-                ensure!(item.issuer == caller || item.impact_reporter == Some(caller.clone()), Error::<T>::BondAccessDenied );
-                ensure!(Self::is_report_in_time(&item, now, period), Error::<T>::BondOutOfOrder );
-                item.time_passed_after_activation(now).map(|(moment, _period)| moment ).unwrap()
-            };
-
-            let index: usize = period as usize;
-            BondImpactReport::<T>::try_mutate(&bond, |reports|->DispatchResult {
-
-                ensure!(index < reports.len() && !reports[index].signed, Error::<T>::BondParamIncorrect);
-
-                reports[index].create_period = moment;
-                reports[index].impact_data = impact_data;
+            Self::impact_report_send(caller, bond, period, impact_data, Vec::new())
+        }
 
-                Self::deposit_event(Event::<T>::BondImpactReportSent( caller, bond, period, impact_data));
-                Ok(())
-            })?;
-            Ok(().into())
+        /// <pre>
+        /// Method: bond_impact_kpi_report_send(origin: OriginFor<T>, bond: BondId, period: u32, impact_data: u64, kpi_data: Vec<u64>)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            period: u32 - report period starting from 0
+        ///            impact_data: u64 - report value of the first KPI
+        ///            kpi_data: Vec<u64> - report values of additional KPIs
+        /// Access: bond Issuer or ImpactReporter, assigned to the bond
+        ///
+        /// Saves impact report of the bond with several KPIs, for later confirmation by Auditor.
+        /// "kpi_data" must contain a value for each KPI from "impact_kpis" of the bond.
+        /// Works like bond_impact_report_send otherwise.
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_impact_kpi_report_send())]
+        pub fn bond_impact_kpi_report_send(
+            origin: OriginFor<T>,
+            bond: BondId,
+            #[pallet::compact] period: BondPeriodNumber,
+            #[pallet::compact] impact_data: u64,
+            kpi_data: Vec<u64>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            Self::impact_report_send(caller, bond, period, impact_data, kpi_data)
         }
 
         /// <pre>
@@ -1168,8 +1935,62 @@ pub mod pallet {
         // Auditor signs impact report
         #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_impact_report_approve())]
         pub fn bond_impact_report_approve(origin: OriginFor<T>, bond: BondId,#[pallet::compact] period: BondPeriodNumber,#[pallet::compact]  impact_data: u64 ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            Self::impact_report_approve(caller, bond, period, impact_data, Vec::new())
+        }
+
+        /// <pre>
+        /// Method: bond_impact_kpi_report_approve(origin: OriginFor<T>, bond: BondId, period: u32, impact_data: u64, kpi_data: Vec<u64>)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            period: u32 - report period starting from 0
+        ///            impact_data: u64 - report value of the first KPI
+        ///            kpi_data: Vec<u64> - report values of additional KPIs
+        ///
+        /// Access: only Auditor assigned to the bond
+        /// Confirms impact report of the bond with several KPIs. Auditor sends exact values
+        /// of all KPIs he confirms. Works like bond_impact_report_approve otherwise.
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_impact_kpi_report_approve())]
+        pub fn bond_impact_kpi_report_approve(
+            origin: OriginFor<T>,
+            bond: BondId,
+            #[pallet::compact] period: BondPeriodNumber,
+            #[pallet::compact] impact_data: u64,
+            kpi_data: Vec<u64>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            Self::impact_report_approve(caller, bond, period, impact_data, kpi_data)
+        }
+
+        /// <pre>
+        /// Method: bond_impact_report_reject(origin: OriginFor<T>, bond: BondId, period: u32, impact_data: u64, kpi_data: Vec<u64>, reason: Vec<u8>, evidence: Option<FileId>)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            period: u32 - report period starting from 0
+        ///            impact_data: u64 - rejected report value
+        ///            kpi_data: Vec<u64> - rejected values of additional KPIs
+        ///            reason: Vec<u8> - reason of the rejection, up to MAX_REJECTION_REASON_LEN bytes
+        ///            evidence: Option<FileId> - optional file (stored in filesign) with evidence
+        ///
+        /// Access: only Auditor assigned to the bond
+        /// Rejects "impact_report_data", sent by the bond Issuer or ImpactReporter. Rejection is stored
+        /// in the report and in the report history, and the report becomes unsent. Issuer or ImpactReporter
+        /// can resubmit impact_data while "impact_data_send_period" of this period is active
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_impact_report_reject())]
+        pub fn bond_impact_report_reject(
+            origin: OriginFor<T>,
+            bond: BondId,
+            #[pallet::compact] period: BondPeriodNumber,
+            #[pallet::compact] impact_data: u64,
+            kpi_data: Vec<u64>,
+            reason: Vec<u8>,
+            evidence: Option<FileId>,
+        ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(accounts::Pallet::<T>::account_is_auditor(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(!reason.is_empty() && reason.len() <= MAX_REJECTION_REASON_LEN, Error::<T>::BondParamIncorrect);
             let now = Timestamp::<T>::get();
             {
                 let item = BondRegistry::<T>::get(bond).ok_or(Error::<T>::BondNotFound)?;
@@ -1181,15 +2002,21 @@ pub mod pallet {
             BondImpactReport::<T>::try_mutate(&bond, |reports|->DispatchResult {
 
                 ensure!(index < reports.len(), Error::<T>::BondParamIncorrect );
-                let report = &reports[index];
+                let report = &mut reports[index];
                 ensure!(report.create_period > 0 , Error::<T>::BondParamIncorrect);
-                ensure!(!report.signed && report.impact_data == impact_data,
+                ensure!(!report.signed && report.impact_data == impact_data && report.kpi_data == kpi_data,
                  Error::<T>::BondParamIncorrect
                 );
 
-                reports[index].signed = true;
+                let rejection = BondImpactReportRejection { impact_data, reason, evidence };
+                // report becomes unsent and waits for resubmission
+                report.create_period = 0;
+                report.impact_data = 0;
+                report.kpi_data = Vec::new();
+                report.rejection = Some(rejection.clone());
 
-                Self::deposit_event(Event::<T>::BondImpactReportApproved( caller, bond, period, impact_data));
+                Self::impact_report_history_push(&bond, period, caller.clone(), now, impact_data, kpi_data, BondImpactReportAction::REJECTED(rejection));
+                Self::deposit_event(Event::<T>::BondImpactReportRejected( caller, bond, period, impact_data));
                 Ok(())
             })?;
             Ok(().into())
@@ -1222,8 +2049,8 @@ pub mod pallet {
                     Some(ref mut item) => {
                         ensure!( matches!(item.state, BondState::ACTIVE|BondState::BANKRUPT), Error::<T>::BondStateNotPermitAction );
 
-                        match item.time_passed_after_activation(now){
-                            Some((_, period))  if period == item.get_periods() => (),
+                        let period = match item.time_passed_after_activation(now){
+                            Some((_, period))  if period == item.get_periods() => period,
                             _ => return Err( Error::<T>::BondOutOfOrder.into() ),
                         };
         
@@ -1237,7 +2064,7 @@ pub mod pallet {
         
                         Self::calc_and_store_bond_coupon_yield(&bond, item, now);
                         // now bond_credit has YTM ( yield to mature )
-                        let amount = item.bond_credit + item.par_value( item.issued_amount ) ;
+                        let amount = item.bond_credit + item.outstanding_par_value( item.issued_amount, period ) ;
                         if amount <= item.bond_debit {
                             // withdraw free balance
                             Self::balance_add(&item.issuer, item.bond_debit - amount)?;
@@ -1260,6 +2087,237 @@ pub mod pallet {
             })
         }
 
+        /// <pre>
+        /// Method: bond_call(origin: OriginFor<T>, bond: BondId, accounts: u32)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            accounts: u32 - upper bound of the number of bondholders and bidders of the bond,
+        ///                            transaction weight depends on it
+        ///
+        /// Access: Bond issuer
+        /// Redeems callable bond before maturity date. Bond should be ACTIVE and have "call_schedule",
+        /// current period should be not less than "earliest_call_period" and less than maturity period
+        /// (after maturity bond is redeemed by bond_redeem() call).
+        /// Function calculates and stores coupon yield of all passed periods, transfers needed sum
+        /// (accrued coupon yield, par value and call premium of issued Bond Units) from/to Issuer's
+        /// balance and pays off every bondholder: unpaid coupon yield, par value and call premium of
+        /// its Bond Units. Sale lots and purchase bids of the bond are removed, reserved EverUSD
+        /// are returned to bidders. Bond becomes FINISHED.
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_call(*accounts))]
+        #[transactional]
+        pub fn bond_call(origin: OriginFor<T>, bond: BondId, #[pallet::compact] accounts: u32) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let now = Timestamp::<T>::get();
+            BondRegistry::<T>::try_mutate(&bond, |maybe_item|{
+                match maybe_item {
+                    Some(ref mut item) => {
+                        ensure!(item.issuer == caller, Error::<T>::BondAccessDenied);
+                        ensure!(item.state == BondState::ACTIVE, Error::<T>::BondStateNotPermitAction);
+                        let earliest_call_period = item.inner.call_schedule.as_ref()
+                            .map(|call| call.earliest_call_period)
+                            .ok_or(Error::<T>::BondIsNotConfigured)?;
+
+                        let period = match item.time_passed_after_activation(now){
+                            Some((_, period)) if period >= earliest_call_period && period < item.get_periods() => period,
+                            _ => return Err( Error::<T>::BondOutOfOrder.into() ),
+                        };
+                        let bondholders = BondUnitPackageRegistry::<T>::iter_key_prefix(&bond).count();
+                        let bidders = BondUnitPurchaseBid::<T>::iter_key_prefix(&bond).count();
+                        ensure!(bondholders + bidders <= accounts as usize, Error::<T>::BondParamIncorrect);
+
+                        // if bond has carbon metadata writes accounts investments in it
+                        match &mut item.inner.carbon_metadata {
+                            None => (),
+                            Some(metadata) => {
+                                metadata.account_investments = Self::get_bond_account_investment(&bond);
+                            }
+                        }
+
+                        Self::calc_and_store_bond_coupon_yield(&bond, item, now);
+                        // now bond_credit has accrued coupon yield
+                        let amount = item.bond_credit
+                            + item.outstanding_par_value( item.issued_amount, period )
+                            + item.call_premium_value( item.issued_amount, period );
+                        if amount <= item.bond_debit {
+                            // withdraw free balance
+                            Self::balance_add(&item.issuer, item.bond_debit - amount)?;
+                        }else{
+                            let transfer = amount - item.bond_debit;
+                            // pay off debt
+                            Self::balance_sub(&item.issuer, transfer)?;
+                        }
+                        let accrued = item.bond_credit;
+                        item.bond_credit = amount;
+                        item.bond_debit = amount;
+                        item.state = BondState::FINISHED;
+                        item.nonce += 1;
+
+                        // pay off bondholders
+                        let bondholders: Vec<_> = BondUnitPackageRegistry::<T>::iter_prefix(&bond)
+                            .map(|(bondholder, packages)| {
+                                (bondholder, packages.iter().map(|package| package.bond_units).sum::<BondUnitAmount>())
+                            })
+                            .collect();
+                        for (bondholder, bond_units) in bondholders.iter() {
+                            let premium = item.call_premium_value(*bond_units, period);
+                            Self::redeem_bond_units(&bond, item, bondholder);
+                            Self::balance_add(bondholder, premium)?;
+                            item.coupon_yield += premium;
+                        }
+
+                        // secondary market of the bond is closed
+                        BondUnitPackageLot::<T>::remove_prefix(&bond, None);
+                        let bids: Vec<_> = BondUnitPurchaseBid::<T>::drain_prefix(&bond).collect();
+                        for (bidder, bids) in bids {
+                            let reserved: EverUSDBalance = bids.iter().map(|bid| bid.amount).sum();
+                            Self::balance_unreserve(&bidder, reserved)?;
+                            Self::update_account_bond_index(&bond, &bidder);
+                        }
+                        for (bondholder, _) in bondholders.iter() {
+                            Self::update_account_bond_index(&bond, bondholder);
+                        }
+
+                        Self::deposit_event(Event::<T>::BondCalled(caller, bond, accrued));
+                        Ok(().into())
+                    },
+                    None => Err(Error::<T>::BondNotFound.into())
+                }
+            })
+        }
+
+        /// <pre>
+        /// Method: bond_restructuring_propose(origin: OriginFor<T>, bond: BondId, restructuring: BondRestructuring, deadline: T::Moment)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            restructuring: BondRestructuring - proposed change of bond terms
+        ///            deadline: T::Moment - bondholders can vote until this moment
+        /// Access: Bond issuer or Bond Arranger
+        ///
+        /// Creates restructuring proposal for ACTIVE or BANKRUPT bond: maturity extension,
+        /// interest rate change or coupon holiday. Bond can have only one proposal at a time.
+        /// Bondholders vote for the proposal with bond_restructuring_vote
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_restructuring_propose())]
+        pub fn bond_restructuring_propose(
+            origin: OriginFor<T>,
+            bond: BondId,
+            restructuring: BondRestructuring,
+            #[pallet::compact] deadline: T::Moment,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let now = Timestamp::<T>::get();
+            ensure!(deadline > now, Error::<T>::BondParamIncorrect);
+            let item = BondRegistry::<T>::get(&bond).ok_or(Error::<T>::BondNotFound)?;
+            ensure!(
+                item.issuer == caller || accounts::Pallet::<T>::account_is_bond_arranger(&caller),
+                Error::<T>::BondAccessDenied
+            );
+            ensure!(
+                matches!(item.state, BondState::ACTIVE | BondState::BANKRUPT),
+                Error::<T>::BondStateNotPermitAction
+            );
+            ensure!(!BondRestructuringProposal::<T>::contains_key(&bond), Error::<T>::BondOutOfOrder);
+            Self::ensure_restructuring_valid(&item, &restructuring, now)?;
+
+            // votes are removed together with the previous proposal
+            BondRestructuringProposal::<T>::insert(&bond, BondRestructuringProposalStruct {
+                proposer: caller.clone(),
+                restructuring: restructuring.clone(),
+                deadline,
+            });
+            Self::deposit_event(Event::<T>::BondRestructuringProposed(caller, bond, restructuring));
+            Ok(().into())
+        }
+
+        /// <pre>
+        /// Method: bond_restructuring_vote(origin: OriginFor<T>, bond: BondId, approve: bool)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            approve: bool - vote for (true) or against (false) the proposal
+        /// Access: Bondholder
+        ///
+        /// Votes for restructuring proposal of the bond before the proposal deadline.
+        /// Bondholder can change his vote. Votes are weighted by bond units,
+        /// held by bondholders at the moment of the decision
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_restructuring_vote())]
+        pub fn bond_restructuring_vote(origin: OriginFor<T>, bond: BondId, approve: bool) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let now = Timestamp::<T>::get();
+            let proposal = BondRestructuringProposal::<T>::get(&bond).ok_or(Error::<T>::ProposalNotFound)?;
+            ensure!(!proposal.is_expired(now), Error::<T>::ProposalObsolete);
+            ensure!(Self::bondholder_units(&bond, &caller) > 0, Error::<T>::BondAccessDenied);
+
+            if !BondRestructuringVote::<T>::contains_key(&bond, &caller) {
+                BondRestructuringVoteCount::<T>::mutate(&bond, |count| *count += 1);
+            }
+            BondRestructuringVote::<T>::insert(&bond, &caller, approve);
+            Self::deposit_event(Event::<T>::BondRestructuringVoted(caller, bond, approve));
+            Ok(().into())
+        }
+
+        /// <pre>
+        /// Method: bond_restructuring_finalize(origin: OriginFor<T>, bond: BondId, votes: u32)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            votes: u32 - upper bound of the number of bondholders, voted for the proposal,
+        ///                         transaction weight depends on it
+        /// Access: any
+        ///
+        /// Counts votes for restructuring proposal of the bond. Before the deadline the proposal
+        /// can be approved by bondholders, holding more than half of issued bond units.
+        /// After the deadline the proposal is approved, if bondholders holding at least
+        /// RESTRUCTURING_QUORUM of issued bond units voted, and most of them voted for the proposal.
+        /// Approved proposal is applied to the bond terms, otherwise it's rejected.
+        /// Coupon yield of passed periods is accrued on the old terms
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_restructuring_finalize(*votes))]
+        #[transactional]
+        pub fn bond_restructuring_finalize(
+            origin: OriginFor<T>,
+            bond: BondId,
+            #[pallet::compact] votes: u32,
+        ) -> DispatchResult {
+            let _caller = ensure_signed(origin)?;
+            let now = Timestamp::<T>::get();
+            let proposal = BondRestructuringProposal::<T>::get(&bond).ok_or(Error::<T>::ProposalNotFound)?;
+            let vote_count = BondRestructuringVoteCount::<T>::get(&bond);
+            ensure!(vote_count <= votes, Error::<T>::BondParamIncorrect);
+            BondRegistry::<T>::try_mutate(&bond, |maybe_item|{
+                let item = maybe_item.as_mut().ok_or(Error::<T>::BondNotFound)?;
+                let (votes_for, votes_against) = Self::restructuring_votes(&bond);
+                let issued_amount = item.issued_amount as u64;
+
+                let approved = if proposal.is_expired(now) {
+                    (votes_for + votes_against) as u128 * 1_000_000
+                        >= issued_amount as u128 * RESTRUCTURING_QUORUM as u128
+                        && votes_for > votes_against
+                } else {
+                    // voting is in progress, but the majority has been reached
+                    ensure!(votes_for * 2 > issued_amount, Error::<T>::BondOutOfOrder);
+                    true
+                };
+
+                BondRestructuringProposal::<T>::remove(&bond);
+                BondRestructuringVote::<T>::remove_prefix(&bond, Some(vote_count));
+                BondRestructuringVoteCount::<T>::remove(&bond);
+
+                // bond could finish or become unfit for the restructuring during the voting
+                if approved
+                    && matches!(item.state, BondState::ACTIVE | BondState::BANKRUPT)
+                    && Self::ensure_restructuring_valid(item, &proposal.restructuring, now).is_ok()
+                {
+                    Self::restructure_bond(&bond, item, &proposal.restructuring, now);
+                    item.nonce += 1;
+                    Self::deposit_event(Event::<T>::BondRestructuringApproved(bond, proposal.restructuring));
+                } else {
+                    Self::deposit_event(Event::<T>::BondRestructuringRejected(bond, proposal.restructuring));
+                }
+                Ok(())
+            })
+        }
+
         /// <pre>
         /// Method: bond_declare_bankrupt(origin: OriginFor<T>, bond: BondId)
         /// Arguments: origin: T::AccountId - transaction caller
@@ -1296,6 +2354,129 @@ pub mod pallet {
             })
         }
 
+        /// <pre>
+        /// Method: bond_recovery_deposit(origin: OriginFor<T>, bond: BondId, amount: EverUSDBalance)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            amount: EverUSDBalance - the number of recovered EverUSD
+        /// Access: Bond Arranger or Custodian
+        ///
+        /// Transfers `amount` of EverUSD, recovered from bankrupt Issuer, from caller's balance
+        /// to the fund of BANKRUPT bond. Unlike bond_deposit_everusd(), bond stays BANKRUPT:
+        /// recovered EverUSD are distributed among Investors after bond_default_settle() call
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_recovery_deposit())]
+        pub fn bond_recovery_deposit(origin: OriginFor<T>, bond: BondId, #[pallet::compact] amount: EverUSDBalance) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(
+                accounts::Pallet::<T>::account_is_bond_arranger(&caller) || accounts::Pallet::<T>::account_is_custodian(&caller),
+                Error::<T>::AccountNotAuthorized
+            );
+            ensure!(amount > 0, Error::<T>::BondParamIncorrect);
+            BondRegistry::<T>::try_mutate(&bond, |maybe_item|{
+                match maybe_item {
+                    Some(ref mut item) => {
+                        ensure!(item.state == BondState::BANKRUPT, Error::<T>::BondStateNotPermitAction);
+
+                        Self::balance_sub(&caller, amount)?;
+                        item.bond_debit = item.bond_debit.checked_add(amount)
+                            .ok_or( Error::<T>::BondParamIncorrect )?;
+                        item.nonce += 1;
+
+                        Self::deposit_event(Event::<T>::BondRecoveryDeposit(caller, bond, amount));
+                        Ok(().into())
+                    },
+                    None => Err(Error::<T>::BondNotFound.into())
+                }
+            })
+        }
+
+        /// <pre>
+        /// Method: bond_default_settle(origin: OriginFor<T>, bond: BondId, accounts: u32)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            accounts: u32 - upper bound of the number of bondholders, sellers, bidders
+        ///                            and restructuring voters of the bond, transaction weight depends on it
+        /// Access: Bond Arranger
+        ///
+        /// Closes BANKRUPT bond to terminal DEFAULT_SETTLED state. Coupon yield is accrued
+        /// for all passed periods and final ledger snapshot is stored. Undistributed bond fund
+        /// becomes recovery fund, which Investors claim with bond_withdraw_everusd() pro-rata
+        /// to outstanding par value and accrued unpaid coupon yield of their Bond Units.
+        /// The rest of recovery fund above total claim can be withdrawn by Issuer.
+        /// Sale lots and purchase bids of the bond are removed, reserved EverUSD
+        /// are returned to bidders.
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_default_settle(*accounts))]
+        #[transactional]
+        pub fn bond_default_settle(origin: OriginFor<T>, bond: BondId, #[pallet::compact] accounts: u32) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(accounts::Pallet::<T>::account_is_bond_arranger(&caller), Error::<T>::AccountNotAuthorized);
+            let now = Timestamp::<T>::get();
+            BondRegistry::<T>::try_mutate(&bond, |maybe_item|{
+                match maybe_item {
+                    Some(ref mut item) => {
+                        ensure!(item.state == BondState::BANKRUPT, Error::<T>::BondStateNotPermitAction);
+                        let bondholders = BondUnitPackageRegistry::<T>::iter_key_prefix(&bond).count();
+                        let sellers = BondUnitPackageLot::<T>::iter_key_prefix(&bond).count();
+                        let bidders = BondUnitPurchaseBid::<T>::iter_key_prefix(&bond).count();
+                        let voters = BondRestructuringVoteCount::<T>::get(&bond) as usize;
+                        ensure!(
+                            bondholders + sellers + bidders + voters <= accounts as usize,
+                            Error::<T>::BondParamIncorrect
+                        );
+
+                        Self::calc_and_store_bond_coupon_yield(&bond, item, now);
+                        let bond_yields = BondCouponYield::<T>::get(&bond);
+                        ensure!(!bond_yields.is_empty(), Error::<T>::BondOutOfOrder);
+
+                        let total_claim: EverUSDBalance = BondUnitPackageRegistry::<T>::iter_prefix_values(&bond)
+                            .map(|packages| Self::packages_claim(item, &packages, &bond_yields).0)
+                            .sum();
+                        let recovery_fund = item.bond_debit
+                            .checked_sub(item.coupon_yield)
+                            .ok_or(Error::<T>::BalanceOverdraft)?;
+                        let settlement = BondDefaultSettlementStruct {
+                            settlement_date: now,
+                            period: bond_yields.len() as BondPeriodNumber,
+                            bond_debit: item.bond_debit,
+                            bond_credit: item.bond_credit,
+                            coupon_yield: item.coupon_yield,
+                            recovery_fund,
+                            total_claim,
+                        };
+                        // bond liabilities are limited to recovery fund,
+                        // the rest of the fund is free balance of the issuer
+                        item.bond_credit = item.coupon_yield + min(settlement.recovery_fund, total_claim);
+                        item.state = BondState::DEFAULT_SETTLED;
+                        item.nonce += 1;
+
+                        // secondary market of the bond is closed
+                        BondUnitPackageLot::<T>::remove_prefix(&bond, None);
+                        let bids: Vec<_> = BondUnitPurchaseBid::<T>::drain_prefix(&bond).collect();
+                        for (bidder, bids) in bids {
+                            let reserved: EverUSDBalance = bids.iter().map(|bid| bid.amount).sum();
+                            Self::balance_unreserve(&bidder, reserved)?;
+                            Self::update_account_bond_index(&bond, &bidder);
+                        }
+                        BondRestructuringProposal::<T>::remove(&bond);
+                        BondRestructuringVote::<T>::remove_prefix(&bond, None);
+                        BondRestructuringVoteCount::<T>::remove(&bond);
+
+                        Self::deposit_event(Event::<T>::BondDefaultSettled(
+                            caller,
+                            bond,
+                            settlement.recovery_fund,
+                            settlement.total_claim,
+                        ));
+                        BondDefaultSettlement::<T>::insert(&bond, settlement);
+                        Ok(().into())
+                    },
+                    None => Err(Error::<T>::BondNotFound.into())
+                }
+            })
+        }
+
         /// <pre>
         /// Method: bond_revoke(origin: OriginFor<T>, bond: BondId)
         /// Arguments: origin: T::AccountId - transaction caller
@@ -1315,6 +2496,9 @@ pub mod pallet {
             ensure!(item.state == BondState::PREPARE, Error::<T>::BondStateNotPermitAction);
             assert!( BondRegistry::<T>::contains_key(bond) );
             BondRegistry::<T>::remove( &bond );
+            for account in item.role_accounts() {
+                Self::update_account_bond_role_index(&bond, None, account);
+            }
 
             Self::deposit_event(Event::<T>::BondRevoked(caller, bond));
             Ok(().into())
@@ -1329,12 +2513,14 @@ pub mod pallet {
         ///
         /// This function is mainly used by Investors to recieve accrued coupon yield in any moment.
         /// Also, this function is used by bond Issuer to withdraw EverUSD from `free bond balance`.
-        /// Available only in ACTIVE, BANKRUPT of FINISHED states.
+        /// Available only in ACTIVE, BANKRUPT, FINISHED or DEFAULT_SETTLED states.
         /// If caller is Issuer of this bond, "get_free_balance()" amount of EverUSD tokens are transfered
         /// to bond.issuer address. If caller is the Investor of bond, then, in FINISHED state he
-        /// receives all bond debt (principal value + coupon yield), or coupon yield only
+        /// receives all bond debt (principal value + coupon yield), in DEFAULT_SETTLED state he
+        /// receives pro-rata share of recovery fund, or coupon yield only
         /// (by calling "request_coupon_yield()") if bond still ACTIVE or BANKRUPT. If amount
         /// of EverUSD on bond's balance is not enough to pay to Investors, bond moves to BANKRUPT state.
+        /// Proceeds of the bond with declared use of proceeds are released by bond_proceeds_withdraw()
         /// </pre>
         //  @TODO add parameter beneficiary:T::AccountId  who will receive coupon yield
         //  @TODO consider separate functions for Issuer and Investor
@@ -1344,7 +2530,10 @@ pub mod pallet {
             BondRegistry::<T>::try_mutate(&bond, |maybe_item|{
                 match maybe_item {
                     Some(ref mut item) => {
-                        ensure!( matches!(item.state , BondState::ACTIVE | BondState::BANKRUPT | BondState::FINISHED), Error::<T>::BondStateNotPermitAction);
+                        ensure!(
+                            matches!(item.state , BondState::ACTIVE | BondState::BANKRUPT | BondState::FINISHED | BondState::DEFAULT_SETTLED),
+                            Error::<T>::BondStateNotPermitAction
+                        );
 
                         let now = Timestamp::<T>::get();
                         Self::calc_and_store_bond_coupon_yield(&bond, item, now);
@@ -1361,6 +2550,9 @@ pub mod pallet {
                         }else if item.state == BondState::FINISHED {
                             // investor (bondholder) withdraw principal value
                             Self::redeem_bond_units(&bond, item, &caller)
+                        }else if item.state == BondState::DEFAULT_SETTLED {
+                            // investor (bondholder) withdraw his share of recovery fund
+                            Self::recover_bond_units(&bond, item, &caller)?
                         }else{
                             // investor (bondholder) withdraw coupon yield
                             // set bankrupt state if bond fund cannot pay off
@@ -1427,27 +2619,130 @@ pub mod pallet {
         }
 
         /// <pre>
-        /// Method: bond_unit_lot_bid(origin: OriginFor<T>, bond: BondId, lot: BondUnitSaleLotStruct)
+        /// Method: bond_proceeds_withdraw(origin: OriginFor<T>, bond: BondId, category: BondProceedsCategory, amount: EverUSDBalance, evidence: Option<FileId>)
+        /// Arguments: origin: T::AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            category: BondProceedsCategory - spending category, declared in use of proceeds
+        ///            amount: EverUSDBalance - the number of EverUSD to release
+        ///            evidence: Option<FileId> - optional file (stored in filesign) with evidence of spending
+        /// Access: Bond issuer
+        ///
+        /// Releases "amount" of bond proceeds, reserved on the Issuer's account, and records
+        /// the allocation in the bond ledger. Total allocation of the category cannot exceed
+        /// its cap, declared at issuance, as share of raised proceeds.
+        /// Bond should be activated and have less than MAX_PROCEEDS_ALLOCATIONS allocations
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_proceeds_withdraw())]
+        pub fn bond_proceeds_withdraw(
+            origin: OriginFor<T>,
+            bond: BondId,
+            category: BondProceedsCategory,
+            #[pallet::compact] amount: EverUSDBalance,
+            evidence: Option<FileId>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let item = BondRegistry::<T>::get(&bond).ok_or(Error::<T>::BondNotFound)?;
+            ensure!(item.issuer == caller, Error::<T>::BondAccessDenied);
+            ensure!(
+                !matches!(item.state, BondState::PREPARE | BondState::BOOKING),
+                Error::<T>::BondStateNotPermitAction
+            );
+            let declared = item.inner.use_of_proceeds.iter()
+                .find(|declared| declared.category == category)
+                .ok_or(Error::<T>::BondParamIncorrect)?;
+            ensure!(amount > 0, Error::<T>::BondParamIncorrect);
+
+            let raised = BondProceeds::<T>::get(&bond);
+            BondProceedsAllocation::<T>::try_mutate(&bond, |allocations| -> DispatchResult {
+                ensure!(
+                    allocations.len() < MAX_PROCEEDS_ALLOCATIONS as usize,
+                    Error::<T>::ProceedsAllocationLimitExceeded
+                );
+                let allocated: EverUSDBalance = allocations.iter().map(|allocation| allocation.amount).sum();
+                let allocated = allocated.checked_add(amount).ok_or(Error::<T>::BalanceOverdraft)?;
+                ensure!(allocated <= raised, Error::<T>::BalanceOverdraft);
+                let category_allocated = Self::proceeds_allocated(allocations, category)
+                    .checked_add(amount)
+                    .ok_or(Error::<T>::BalanceOverdraft)?;
+                ensure!(
+                    declared.cap == 0 || category_allocated <= declared.cap_amount(raised),
+                    Error::<T>::ProceedsCapExceeded
+                );
+                Self::balance_unreserve(&caller, amount)?;
+                allocations.push(BondProceedsAllocationStruct {
+                    category,
+                    amount,
+                    evidence,
+                    allocation_date: Timestamp::<T>::get(),
+                });
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::BondProceedsAllocated(caller, bond, category, amount));
+            Ok(().into())
+        }
+
+        /// <pre>
+        /// Method: fee_withdraw_everusd(origin: OriginFor<T>)
+        /// Arguments: origin: T::AccountId - transaction caller
+        /// Access: fee account of any bond
+        ///
+        /// Transfers all EverUSD fees, charged to the caller as fee account of bonds,
+        /// to the caller's balance
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::fee_withdraw_everusd())]
+        pub fn fee_withdraw_everusd(origin: OriginFor<T>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let amount = FeeEverUSD::<T>::take(&caller);
+            ensure!(amount > 0, Error::<T>::BalanceOverdraft);
+            Self::balance_add(&caller, amount)?;
+
+            Self::deposit_event(Event::<T>::FeeWithdrawEverUSD(caller, amount));
+            Ok(().into())
+        }
+
+        /// <pre>
+        /// Method: bond_unit_lot_bid(
+        ///     origin: OriginFor<T>,
+        ///     bond: BondId,
+        ///     new_bondholder: Option<T::AccountId>,
+        ///     bond_units: BondUnitAmount,
+        ///     amount: EverUSDBalance,
+        ///     deadline: T::Moment,
+        /// )
         /// Arguments: origin: T::AccountId - bond unit bondholder
         ///            bond: BondId - bond identifier
-        ///            lot: BondUnitSaleLotStruct - lot data
+        ///            new_bondholder: Option<T::AccountId> - (optional) target buyer(to restrict sale of this lot to given buyer)
+        ///            bond_units: BondUnitAmount - amount of selling Bond Units
+        ///            amount: EverUSDBalance - price of whole lot in EverUSD
+        ///            deadline: T::Moment - moment, after that lot cannot be sold
         /// Access: any account, holding Bond Units (having BondUnitsPackage-s)
         ///
-        /// Creates sale lot: pack of Bond Units to sale for given price. Accepts BondUnitSaleLotStruct,
-        /// containing:
-        ///   - "bond_units": amount of selling Bond Units
-        ///   - "amount": price of whole lot in EverUSD
-        ///   - "deadline": moment, after that lot cannot be sold
-        ///   - "new_bondholder": (optional) target buyer(to restrict sale of this lot to given buyer)
+        /// Creates sale lot: pack of Bond Units to sale for given price. New unique lot id is assigned
+        /// by the pallet and returned in BondSaleLotBid event.
         /// Function checks, that seller have BondUnitsPackage-s, containing enough BUs for lot creation,
         /// expiration date. Then, creates new lot in BondUnitPackageLot registry. Later, buyers can
-        /// choose this lot and buy it.
+        /// choose this lot by its id and buy it.
         /// Also, function purges expired lots for this bond and seller from BondUnitPackageLot storage.
         /// </pre>
         #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_unit_lot_bid())]
-        pub fn bond_unit_lot_bid(origin: OriginFor<T>, bond: BondId, lot: BondUnitSaleLotStructOf<T>) -> DispatchResultWithPostInfo{
+        pub fn bond_unit_lot_bid(
+            origin: OriginFor<T>,
+            bond: BondId,
+            new_bondholder: Option<T::AccountId>,
+            #[pallet::compact] bond_units: BondUnitAmount,
+            #[pallet::compact] amount: EverUSDBalance,
+            #[pallet::compact] deadline: T::Moment,
+        ) -> DispatchResultWithPostInfo {
             let caller = ensure_signed(origin)?;
             let now = Timestamp::<T>::get();
+            let lot = BondUnitSaleLotStructOf::<T> {
+                id: LastBondUnitSaleLotId::<T>::get() + 1,
+                deadline,
+                new_bondholder,
+                bond_units,
+                amount,
+            };
             // @TODO - maybe restrict this operation only to Investors?
             ensure!(!lot.is_expired(now), Error::<T>::LotParamIncorrect);
 
@@ -1468,6 +2763,7 @@ pub mod pallet {
             // prevent new bid if the caller doesn't have enough bond units
             ensure!(total_bond_units>= total_bond_units_inlot+lot.bond_units, Error::<T>::BalanceOverdraft);
 
+            LastBondUnitSaleLotId::<T>::put(lot.id);
             lots.push(
                 lot.clone()
             );
@@ -1478,74 +2774,349 @@ pub mod pallet {
         }
 
         /// <pre>
-        /// Method: bond_unit_lot_settle(origin: OriginFor<T>, bond: BondId,bondholder: T::AccountId, lot: BondUnitSaleLotStruct)
-        /// Arguments: origin: T::AccountId - bond unit bondholder
+        /// Method: bond_unit_lot_settle(origin: OriginFor<T>, bond: BondId, bondholder: T::AccountId, lot_id: BondUnitSaleLotId, bond_units: BondUnitAmount, amount: EverUSDBalance)
+        /// Arguments: origin: T::AccountId - bond unit buyer
         ///            bond: BondId - bond identifier
         ///            bondholder: Current bondholder of of bond
-        ///            lot: BondUnitSaleLotStruct - lot data
+        ///            lot_id: BondUnitSaleLotId - lot identifier
+        ///            bond_units: BondUnitAmount - amount of Bond Units to buy from the lot
+        ///            amount: EverUSDBalance - price of the bought part, expected by the buyer
         /// Access: any account with Investor role
         ///
-        /// Buy the lot created by bond_unit_lot_bid() call. Lot should not be expired.
+        /// Buy the whole lot created by bond_unit_lot_bid() call or its part. Lot should not be expired.
         /// If "new_bondholder" field of lot is set, only given account can buy this lot.
-        /// Buyer should have enough EverUSD to buy the lot. When lot is sold,
-        /// expired lots (fixed amount) are purged from BondUnitPackageLot registry.
+        /// Price of the part is proportional to its share in the lot: amount * bond_units / lot.bond_units.
+        /// "amount" should be equal to this price - this prevents data race when the seller changes
+        /// the price of the lot. Buyer should have enough EverUSD to buy the lot. When the lot is sold out,
+        /// it's removed, otherwise the rest of the lot remains available for sale.
+        /// Expired lots (fixed amount) are purged from BondUnitPackageLot registry.
         ///
         /// Before transfer of EverUSD, function caluclates and stores bond coupon yield for bondholder(seller)
         /// and caller(buyer), because this deal changes BondUnitsPackages of buyer and seller
-        /// (buyer receives "newer" BondUnitsPackage, that buyer owned)
+        /// (buyer receives "newer" BondUnitsPackage, that buyer owned).
+        /// Trade fees of the bond are withheld from the price, paid to the seller
         /// </pre>
         #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_unit_lot_settle())]
-        pub fn bond_unit_lot_settle(origin: OriginFor<T>, bond: BondId, bondholder: T::AccountId, lot: BondUnitSaleLotStructOf<T>)->DispatchResultWithPostInfo{
+        pub fn bond_unit_lot_settle(
+            origin: OriginFor<T>,
+            bond: BondId,
+            bondholder: T::AccountId,
+            #[pallet::compact] lot_id: BondUnitSaleLotId,
+            #[pallet::compact] bond_units: BondUnitAmount,
+            #[pallet::compact] amount: EverUSDBalance,
+        )->DispatchResultWithPostInfo{
             let caller = ensure_signed(origin)?;
             ensure!(accounts::Pallet::<T>::account_is_investor(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(caller != bondholder, Error::<T>::LotParamIncorrect);
+            T::BondTransferGuard::ensure_can_acquire(&bond, Some(&bondholder), &caller, bond_units)?;
             let now = Timestamp::<T>::get();
-            // prevent expired lots sales
-            ensure!(!lot.is_expired( now ), Error::<T>::LotObsolete);
-
-            ensure!(lot.new_bondholder.is_none() || lot.new_bondholder == Some(caller.clone()), Error::<T>::LotNotFound);
-            let balance = Self::balance_everusd(&caller);
-            // ensure caller has enough tokens on its balance
-            ensure!(lot.amount <= balance , Error::<T>::BalanceOverdraft);
 
             BondUnitPackageLot::<T>::try_mutate(&bond, &bondholder, |lots|->DispatchResult{
-                if let Some(index) = lots.iter().position(|item| item==&lot ){
-                     lots.remove( index );
-                     if !lots.is_empty() {
-                        // purge expired lots
-                        lots.retain( |item| !item.is_expired( now ) );
-                     }
-                     // @TODO optimize out access to balances
-                     BondRegistry::<T>::try_mutate(bond, |maybe_item| -> DispatchResult {
-                        match maybe_item {
-                            Some(ref mut item) => {
-                                Self::calc_and_store_bond_coupon_yield(&bond, item, now);
-                                Self::request_coupon_yield(&bond, item, &bondholder);
-                                Self::request_coupon_yield(&bond, item, &caller);
-                                Ok(())
-                            },
-                            None => Err(Error::<T>::BondNotFound.into())
-                        }
-                     })?;
-
-                     let mut from_packages = BondUnitPackageRegistry::<T>::get(&bond, &bondholder);
-                     let mut to_packages = BondUnitPackageRegistry::<T>::get(&bond, &caller);
-                     // transfer lot.bond_units from bondholder to caller
-                     Self::transfer_bond_units(&mut from_packages, &mut to_packages, lot.bond_units)?;
-                     // store new packages
-                     BondUnitPackageRegistry::<T>::insert(&bond, &bondholder, from_packages);
-                     BondUnitPackageRegistry::<T>::insert(&bond, &caller, to_packages);
-
-                     // pay off deal
-                     Self::balance_sub(&caller, lot.amount)?;
-                     Self::balance_add(&bondholder, lot.amount)?;
-                     Self::deposit_event(Event::<T>::BondSaleLotSettle(caller, bondholder.clone(), bond, lot));
-                     Ok(())
-                }else{
-                    Err(Error::<T>::BondParamIncorrect.into())
+                let index = lots.iter().position(|item| item.id == lot_id ).ok_or(Error::<T>::LotNotFound)?;
+                let lot = &mut lots[index];
+                // prevent expired lots sales
+                ensure!(!lot.is_expired( now ), Error::<T>::LotObsolete);
+                ensure!(lot.new_bondholder.is_none() || lot.new_bondholder == Some(caller.clone()), Error::<T>::LotNotFound);
+                ensure!(bond_units > 0 && bond_units <= lot.bond_units, Error::<T>::LotParamIncorrect);
+
+                let price = if bond_units == lot.bond_units {
+                    lot.amount
+                } else {
+                    (lot.amount as u128 * bond_units as u128 / lot.bond_units as u128) as EverUSDBalance
+                };
+                // the price of the lot has been changed by the seller
+                ensure!(price == amount, Error::<T>::LotParamIncorrect);
+                // ensure caller has enough tokens on its balance
+                ensure!(price <= Self::balance_everusd(&caller), Error::<T>::BalanceOverdraft);
+
+                let mut sold = lot.clone();
+                sold.bond_units = bond_units;
+                sold.amount = price;
+
+                lot.bond_units -= bond_units;
+                lot.amount -= price;
+                if lot.bond_units == 0 {
+                    lots.remove( index );
                 }
+                // purge expired lots
+                lots.retain( |item| !item.is_expired( now ) );
+
+                // @TODO optimize out access to balances
+                let fees = BondRegistry::<T>::try_mutate(bond, |maybe_item| -> Result<_, DispatchError> {
+                    match maybe_item {
+                        Some(ref mut item) => {
+                            Self::calc_and_store_bond_coupon_yield(&bond, item, now);
+                            Self::request_coupon_yield(&bond, item, &bondholder);
+                            Self::request_coupon_yield(&bond, item, &caller);
+                            Ok(item.inner.fees.clone())
+                        },
+                        None => Err(Error::<T>::BondNotFound.into())
+                    }
+                })?;
+
+                let mut from_packages = BondUnitPackageRegistry::<T>::get(&bond, &bondholder);
+                let mut to_packages = BondUnitPackageRegistry::<T>::get(&bond, &caller);
+                // transfer bond_units from bondholder to caller
+                Self::transfer_bond_units(&mut from_packages, &mut to_packages, bond_units)?;
+                // store new packages
+                BondUnitPackageRegistry::<T>::insert(&bond, &bondholder, from_packages);
+                BondUnitPackageRegistry::<T>::insert(&bond, &caller, to_packages);
+                Self::update_account_bond_index(&bond, &bondholder);
+                Self::update_account_bond_index(&bond, &caller);
+
+                // pay off deal, trade fees are withheld from the seller
+                Self::balance_sub(&caller, price)?;
+                let fee = Self::charge_bond_fees(&bond, &fees, BondFeeKind::TRADE, price);
+                Self::balance_add(&bondholder, price - fee)?;
+                Self::deposit_event(Event::<T>::BondSaleLotSettle(caller, bondholder.clone(), bond, sold));
+                Ok(())
             })?;
             Ok(().into())
         }
+
+        /// <pre>
+        /// Method: bond_unit_lot_cancel(origin: OriginFor<T>, bond: BondId, lot_id: BondUnitSaleLotId)
+        /// Arguments: origin: T::AccountId - bond unit bondholder
+        ///            bond: BondId - bond identifier
+        ///            lot_id: BondUnitSaleLotId - lot identifier
+        /// Access: seller of the lot
+        ///
+        /// Removes the lot, created by bond_unit_lot_bid() call, from BondUnitPackageLot registry.
+        /// Bond Units of the lot become available for new lots.
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_unit_lot_cancel())]
+        pub fn bond_unit_lot_cancel(origin: OriginFor<T>, bond: BondId, #[pallet::compact] lot_id: BondUnitSaleLotId) -> DispatchResultWithPostInfo {
+            let caller = ensure_signed(origin)?;
+
+            BondUnitPackageLot::<T>::try_mutate_exists(&bond, &caller, |maybe_lots|->DispatchResult{
+                let lots = maybe_lots.as_mut().ok_or(Error::<T>::LotNotFound)?;
+                let index = lots.iter().position(|item| item.id == lot_id ).ok_or(Error::<T>::LotNotFound)?;
+                lots.remove( index );
+                if lots.is_empty() {
+                    *maybe_lots = None;
+                }
+                Ok(())
+            })?;
+            Self::update_account_bond_index(&bond, &caller);
+            Self::deposit_event(Event::<T>::BondSaleLotCancel(caller, bond, lot_id));
+            Ok(().into())
+        }
+
+        /// <pre>
+        /// Method: bond_unit_lot_update_price(origin: OriginFor<T>, bond: BondId, lot_id: BondUnitSaleLotId, amount: EverUSDBalance)
+        /// Arguments: origin: T::AccountId - bond unit bondholder
+        ///            bond: BondId - bond identifier
+        ///            lot_id: BondUnitSaleLotId - lot identifier
+        ///            amount: EverUSDBalance - new price of whole lot in EverUSD
+        /// Access: seller of the lot
+        ///
+        /// Sets new price of the lot, created by bond_unit_lot_bid() call. Lot should not be expired.
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_unit_lot_update_price())]
+        pub fn bond_unit_lot_update_price(
+            origin: OriginFor<T>,
+            bond: BondId,
+            #[pallet::compact] lot_id: BondUnitSaleLotId,
+            #[pallet::compact] amount: EverUSDBalance,
+        ) -> DispatchResultWithPostInfo {
+            let caller = ensure_signed(origin)?;
+            let now = Timestamp::<T>::get();
+
+            BondUnitPackageLot::<T>::try_mutate(&bond, &caller, |lots|->DispatchResult{
+                let lot = lots.iter_mut().find(|item| item.id == lot_id ).ok_or(Error::<T>::LotNotFound)?;
+                ensure!(!lot.is_expired( now ), Error::<T>::LotObsolete);
+                lot.amount = amount;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::<T>::BondSaleLotPriceUpdated(caller, bond, lot_id, amount));
+            Ok(().into())
+        }
+
+        /// <pre>
+        /// Method: bond_unit_purchase_bid(origin: OriginFor<T>, bond: BondId, bid: BondUnitPurchaseBidStruct)
+        /// Arguments: origin: T::AccountId - bidder
+        ///            bond: BondId - bond identifier
+        ///            bid: BondUnitPurchaseBidStruct - bid data
+        /// Access: any account with Investor role
+        ///
+        /// Creates standing order to buy Bond Units of active bond for given price. Accepts BondUnitPurchaseBidStruct,
+        /// containing:
+        ///   - "bond_units": amount of Bond Units to buy
+        ///   - "amount": price of whole bid in EverUSD
+        ///   - "deadline": moment, after that bid cannot be filled
+        /// The "id" field of the bid is ignored: new unique bid id is assigned by the pallet
+        /// and returned in BondPurchaseBid event.
+        /// "amount" EverUSD is moved from the caller's balance to the reserve and stays there until
+        /// the bid is filled, cancelled or expired. Any bondholder can sell Bond Units to the bid
+        /// calling bond_unit_purchase_bid_settle().
+        /// Also, function purges expired bids of the caller for this bond, releasing their reserve.
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_unit_purchase_bid())]
+        #[transactional]
+        pub fn bond_unit_purchase_bid(origin: OriginFor<T>, bond: BondId, mut bid: BondUnitPurchaseBidStructOf<T>) -> DispatchResultWithPostInfo {
+            let caller = ensure_signed(origin)?;
+            ensure!(accounts::Pallet::<T>::account_is_investor(&caller), Error::<T>::AccountNotAuthorized);
+            let now = Timestamp::<T>::get();
+            ensure!(!bid.is_expired(now), Error::<T>::BidParamIncorrect);
+            ensure!(bid.bond_units > 0 && bid.amount > 0, Error::<T>::BidParamIncorrect);
+
+            let item = BondRegistry::<T>::get(&bond).ok_or(Error::<T>::BondNotFound)?;
+            ensure!(item.state == BondState::ACTIVE, Error::<T>::BondStateNotPermitAction);
+
+            let mut bids = BondUnitPurchaseBid::<T>::get(&bond, &caller);
+            // purge expired bids
+            let released: EverUSDBalance = bids.iter()
+                .filter(|bid| bid.is_expired(now))
+                .map(|bid| bid.amount)
+                .sum();
+            bids.retain(|bid| !bid.is_expired(now));
+            Self::balance_unreserve(&caller, released)?;
+
+            ensure!(bid.amount <= Self::balance_everusd(&caller), Error::<T>::BalanceOverdraft);
+            Self::balance_reserve(&caller, bid.amount)?;
+
+            bid.id = LastBondUnitPurchaseBidId::<T>::mutate(|id| {
+                *id += 1;
+                *id
+            });
+            bids.push(
+                bid.clone()
+            );
+            BondUnitPurchaseBid::<T>::insert(&bond, &caller, bids);
+            Self::update_account_bond_index(&bond, &caller);
+            Self::deposit_event(Event::<T>::BondPurchaseBid(caller, bond, bid));
+            Ok(().into())
+        }
+
+        /// <pre>
+        /// Method: bond_unit_purchase_bid_cancel(origin: OriginFor<T>, bond: BondId, bid_id: BondUnitPurchaseBidId)
+        /// Arguments: origin: T::AccountId - bidder
+        ///            bond: BondId - bond identifier
+        ///            bid_id: BondUnitPurchaseBidId - bid identifier
+        /// Access: creator of the bid
+        ///
+        /// Removes the bid, created by bond_unit_purchase_bid() call, and returns EverUSD,
+        /// reserved for the rest of the bid, to the caller's balance. Expired bids can be cancelled too.
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_unit_purchase_bid_cancel())]
+        #[transactional]
+        pub fn bond_unit_purchase_bid_cancel(origin: OriginFor<T>, bond: BondId, #[pallet::compact] bid_id: BondUnitPurchaseBidId) -> DispatchResultWithPostInfo {
+            let caller = ensure_signed(origin)?;
+
+            let bid = BondUnitPurchaseBid::<T>::try_mutate_exists(&bond, &caller, |maybe_bids|->Result<BondUnitPurchaseBidStructOf<T>, DispatchError>{
+                let bids = maybe_bids.as_mut().ok_or(Error::<T>::BidNotFound)?;
+                let index = bids.iter().position(|item| item.id == bid_id ).ok_or(Error::<T>::BidNotFound)?;
+                let bid = bids.remove( index );
+                if bids.is_empty() {
+                    *maybe_bids = None;
+                }
+                Ok(bid)
+            })?;
+            Self::balance_unreserve(&caller, bid.amount)?;
+            Self::update_account_bond_index(&bond, &caller);
+            Self::deposit_event(Event::<T>::BondPurchaseBidCancel(caller, bond, bid_id));
+            Ok(().into())
+        }
+
+        /// <pre>
+        /// Method: bond_unit_purchase_bid_settle(origin: OriginFor<T>, bond: BondId, bidder: T::AccountId, bid_id: BondUnitPurchaseBidId, bond_units: BondUnitAmount, amount: EverUSDBalance)
+        /// Arguments: origin: T::AccountId - bondholder, selling Bond Units
+        ///            bond: BondId - bond identifier
+        ///            bidder: T::AccountId - creator of the bid
+        ///            bid_id: BondUnitPurchaseBidId - bid identifier
+        ///            bond_units: BondUnitAmount - amount of Bond Units to sell to the bid
+        ///            amount: EverUSDBalance - price of the sold part, expected by the seller
+        /// Access: any account, holding Bond Units (having BondUnitsPackage-s)
+        ///
+        /// Sells Bond Units to the bid created by bond_unit_purchase_bid() call. Bid should not be expired.
+        /// Bid can be filled partially: price of the part is proportional to its share in the bid,
+        /// amount * bond_units / bid.bond_units, and should be equal to "amount".
+        /// Bond Units, offered by the caller in sale lots, cannot be sold to the bid.
+        /// Price is paid from the bidder's reserve. When the bid is filled, it's removed.
+        ///
+        /// Before transfer of Bond Units, function caluclates and stores bond coupon yield for
+        /// the caller(seller) and bidder(buyer), the same way as bond_unit_lot_settle() does.
+        /// Trade fees of the bond are withheld from the price, paid to the caller
+        /// </pre>
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_unit_purchase_bid_settle())]
+        #[transactional]
+        pub fn bond_unit_purchase_bid_settle(
+            origin: OriginFor<T>,
+            bond: BondId,
+            bidder: T::AccountId,
+            #[pallet::compact] bid_id: BondUnitPurchaseBidId,
+            #[pallet::compact] bond_units: BondUnitAmount,
+            #[pallet::compact] amount: EverUSDBalance,
+        ) -> DispatchResultWithPostInfo {
+            let caller = ensure_signed(origin)?;
+            ensure!(caller != bidder, Error::<T>::BidParamIncorrect);
+            T::BondTransferGuard::ensure_can_acquire(&bond, Some(&caller), &bidder, bond_units)?;
+            let now = Timestamp::<T>::get();
+
+            let sold = BondUnitPurchaseBid::<T>::try_mutate(&bond, &bidder, |bids|->Result<BondUnitPurchaseBidStructOf<T>, DispatchError>{
+                let index = bids.iter().position(|item| item.id == bid_id ).ok_or(Error::<T>::BidNotFound)?;
+                let bid = &mut bids[index];
+                ensure!(!bid.is_expired( now ), Error::<T>::BidObsolete);
+                ensure!(bond_units > 0 && bond_units <= bid.bond_units, Error::<T>::BidParamIncorrect);
+
+                let price = if bond_units == bid.bond_units {
+                    bid.amount
+                } else {
+                    (bid.amount as u128 * bond_units as u128 / bid.bond_units as u128) as EverUSDBalance
+                };
+                // the bid has been changed
+                ensure!(price == amount, Error::<T>::BidParamIncorrect);
+
+                let mut sold = bid.clone();
+                sold.bond_units = bond_units;
+                sold.amount = price;
+
+                bid.bond_units -= bond_units;
+                bid.amount -= price;
+                if bid.bond_units == 0 {
+                    bids.remove( index );
+                }
+                Ok(sold)
+            })?;
+
+            let mut from_packages = BondUnitPackageRegistry::<T>::get(&bond, &caller);
+            let total_bond_units: BondUnitAmount = from_packages.iter().map(|package| package.bond_units).sum();
+            // bond units, offered for sale in lots, cannot be sold
+            let total_bond_units_inlot: BondUnitAmount = BondUnitPackageLot::<T>::get(&bond, &caller).iter()
+                .filter(|lot| !lot.is_expired(now))
+                .map(|lot| lot.bond_units)
+                .sum();
+            ensure!(total_bond_units >= total_bond_units_inlot + bond_units, Error::<T>::BalanceOverdraft);
+
+            let fees = BondRegistry::<T>::try_mutate(bond, |maybe_item| -> Result<_, DispatchError> {
+                match maybe_item {
+                    Some(ref mut item) => {
+                        ensure!(item.state == BondState::ACTIVE, Error::<T>::BondStateNotPermitAction);
+                        Self::calc_and_store_bond_coupon_yield(&bond, item, now);
+                        Self::request_coupon_yield(&bond, item, &caller);
+                        Self::request_coupon_yield(&bond, item, &bidder);
+                        Ok(item.inner.fees.clone())
+                    },
+                    None => Err(Error::<T>::BondNotFound.into())
+                }
+            })?;
+
+            let mut to_packages = BondUnitPackageRegistry::<T>::get(&bond, &bidder);
+            // transfer bond_units from caller to bidder
+            Self::transfer_bond_units(&mut from_packages, &mut to_packages, bond_units)?;
+            // store new packages
+            BondUnitPackageRegistry::<T>::insert(&bond, &caller, from_packages);
+            BondUnitPackageRegistry::<T>::insert(&bond, &bidder, to_packages);
+            Self::update_account_bond_index(&bond, &caller);
+            Self::update_account_bond_index(&bond, &bidder);
+
+            // pay off deal from the reserve, trade fees are withheld from the seller
+            Self::reserved_sub(&bidder, sold.amount)?;
+            let fee = Self::charge_bond_fees(&bond, &fees, BondFeeKind::TRADE, sold.amount);
+            Self::balance_add(&caller, sold.amount - fee)?;
+            Self::deposit_event(Event::<T>::BondPurchaseBidSettle(caller, bidder, bond, sold));
+            Ok(().into())
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -1650,100 +3221,1015 @@ pub mod pallet {
         pub fn bond_holder_packages(bond: &BondId, bondholder: &T::AccountId) -> Vec<BondUnitPackage> {
             BondUnitPackageRegistry::<T>::get(bond, bondholder)
         }
-    
-        pub fn bond_impact_data(bond: &BondId) -> Vec<BondImpactReportStruct> {
-            BondImpactReport::<T>::get(bond)
+    
+        pub fn bond_impact_data(bond: &BondId) -> Vec<BondImpactReportStruct> {
+            BondImpactReport::<T>::get(bond)
+        }
+    
+        #[cfg(test)]
+        pub fn bond_packages(id: &BondId) -> std::collections::HashMap<T::AccountId, Vec<BondUnitPackage>>
+        where
+            <T as frame_system::Config>::AccountId: std::hash::Hash,
+        {
+            BondUnitPackageRegistry::<T>::iter_prefix(id).collect()
+        }
+    
+        /// <pre>
+        /// Increase account balance by `amount` EverUSD
+        /// </pre>
+        pub fn balance_add(who: &T::AccountId, amount: EverUSDBalance) -> DispatchResult {
+            BalanceEverUSD::<T>::try_mutate(who, |balance| -> DispatchResult {
+                *balance = balance
+                    .checked_add(amount)
+                    .ok_or(Error::<T>::BalanceOverdraft)?;
+                Ok(())
+            })
+        }
+    
+        /// <pre>
+        /// Decrease account balance by `amount` EverUSD
+        /// </pre>
+        pub fn balance_sub(who: &T::AccountId, amount: EverUSDBalance) -> DispatchResult {
+            BalanceEverUSD::<T>::try_mutate(who, |balance| -> DispatchResult {
+                *balance = balance
+                    .checked_sub(amount)
+                    .ok_or(Error::<T>::BalanceOverdraft)?;
+                Ok(())
+            })
+        }
+    
+        /// <pre>
+        /// Decrease reserved balance of the account by `amount` EverUSD
+        /// </pre>
+        pub fn reserved_sub(who: &T::AccountId, amount: EverUSDBalance) -> DispatchResult {
+            ReservedEverUSD::<T>::try_mutate_exists(who, |reserved| -> DispatchResult {
+                let rest = reserved
+                    .unwrap_or_default()
+                    .checked_sub(amount)
+                    .ok_or(Error::<T>::BalanceOverdraft)?;
+                *reserved = if rest == 0 { None } else { Some(rest) };
+                Ok(())
+            })
+        }
+
+        /// <pre>
+        /// Moves `amount` EverUSD from account balance to its reserve
+        /// </pre>
+        pub fn balance_reserve(who: &T::AccountId, amount: EverUSDBalance) -> DispatchResult {
+            Self::balance_sub(who, amount)?;
+            ReservedEverUSD::<T>::try_mutate(who, |reserved| -> DispatchResult {
+                *reserved = reserved
+                    .checked_add(amount)
+                    .ok_or(Error::<T>::BalanceOverdraft)?;
+                Ok(())
+            })
+        }
+
+        /// <pre>
+        /// Returns `amount` EverUSD from account reserve to its balance
+        /// </pre>
+        pub fn balance_unreserve(who: &T::AccountId, amount: EverUSDBalance) -> DispatchResult {
+            if amount == 0 {
+                return Ok(());
+            }
+            Self::reserved_sub(who, amount)?;
+            Self::balance_add(who, amount)
+        }
+
+        /// <pre>
+        /// Checks that minting of `amount` EverUSD is backed by the latest signed reserve
+        /// attestation: it isn't stale and its reserve covers the total supply after minting.
+        /// Minting isn't restricted until the first attestation is signed
+        /// </pre>
+        pub fn ensure_reserve_covers(amount: EverUSDBalance, now: T::Moment) -> DispatchResult {
+            let attestation = match LatestReserveAttestation::<T>::get().and_then(ReserveAttestation::<T>::get) {
+                Some(attestation) => attestation,
+                None => return Ok(()),
+            };
+            ensure!(
+                attestation.period_end + T::ReserveAttestationTtl::get().into() >= now,
+                Error::<T>::ReserveAttestationStale
+            );
+            let supply = TotalSupplyEverUSD::<T>::get()
+                .checked_add(amount)
+                .ok_or(Error::<T>::BalanceOverdraft)?;
+            ensure!(supply <= attestation.reserve, Error::<T>::ReserveInsufficient);
+            Ok(())
+        }
+
+        /// <pre>
+        /// Returns the number of the time step (day), used to account EverUSD limits
+        /// </pre>
+        fn everusd_limit_step(now: T::Moment) -> u64 {
+            now.saturated_into::<u64>() / 1000 / T::TimeStep::get() as u64
+        }
+
+        /// <pre>
+        /// Checks that confirmation of `amount` EverUSD for the account doesn't exceed
+        /// the global limit and the account's limit (or quotas of its roles)
+        /// </pre>
+        pub fn everusd_limit_check(who: &T::AccountId, now: T::Moment, mint: bool, amount: EverUSDBalance) -> DispatchResult {
+            let step = Self::everusd_limit_step(now);
+            let error = || if mint { Error::<T>::MintLimitExceeded } else { Error::<T>::BurnLimitExceeded };
+
+            let global_flow = EverUSDGlobalFlow::<T>::get();
+            ensure!(EverUSDGlobalLimit::<T>::get().allows(&global_flow, step, mint, amount), error());
+
+            let limits: Vec<EverUSDLimitStruct> = match EverUSDAccountLimit::<T>::get(who) {
+                Some(limit) => Vec::from([limit]),
+                None => {
+                    let roles = accounts::Pallet::<T>::account_registry(who).roles;
+                    [ISSUER_ROLE_MASK, INVESTOR_ROLE_MASK].iter()
+                        .filter(|role| roles & **role != 0)
+                        .filter_map(|role| EverUSDRoleLimit::<T>::get(role))
+                        .collect()
+                }
+            };
+            let account_flow = EverUSDAccountFlow::<T>::get(who);
+            for limit in limits {
+                ensure!(limit.allows(&account_flow, step, mint, amount), error());
+            }
+            Ok(())
+        }
+
+        /// <pre>
+        /// Records `amount` EverUSD, confirmed for the account, in the rolling window flows
+        /// </pre>
+        pub fn everusd_limit_record(who: &T::AccountId, now: T::Moment, mint: bool, amount: EverUSDBalance) {
+            let step = Self::everusd_limit_step(now);
+            EverUSDGlobalFlow::<T>::mutate(|flows| record_everusd_flow(flows, step, mint, amount));
+            EverUSDAccountFlow::<T>::mutate(who, |flows| record_everusd_flow(flows, step, mint, amount));
+        }
+
+        /// Transfers everusd to an account
+        #[inline]
+        pub fn transfer_everusd(from: &T::AccountId, to: &T::AccountId, amount: EverUSDBalance) -> DispatchResult{ 
+            Self::balance_sub(from, amount)?;
+            Self::balance_add(to, amount)?;
+            Ok(())
+        }
+    
+        /// Gets bon unit investmets per account
+        #[inline]
+        pub fn get_bond_account_investment(bond_id: &BondId) -> Vec<(T::AccountId, u32)> {
+            BondUnitPackageRegistry::<T>::iter()
+                                        .filter(|(x, _, _)|{ x == bond_id })
+                                        .map(|(_, y, z)| 
+                                                {(y, z.iter()
+                                                        .map(|x| x.bond_units)
+                                                        .reduce(|a, b| a + b)
+                                                        .unwrap_or(0))}
+                                        )
+                                        .collect::<Vec<(T::AccountId, u32)>>()
+        }
+
+        /// <pre>
+        /// Returns the number of bond units, held by the bondholder
+        /// </pre>
+        pub fn bondholder_units(bond: &BondId, bondholder: &T::AccountId) -> BondUnitAmount {
+            BondUnitPackageRegistry::<T>::get(bond, bondholder)
+                .iter()
+                .map(|package| package.bond_units)
+                .sum()
+        }
+
+        /// <pre>
+        /// Fills bookbuilding orders of the bond according to the allocation rule:
+        /// creates BondUnitsPackage-s for allocated Bond Units, pays their issue price
+        /// from the reserve and refunds the rest of the reserve
+        /// </pre>
+        fn allocate_bond_unit_orders(bond: &BondId, item: &mut BondStructOf<T>, rule: BondAllocationRule) -> DispatchResult {
+            let mut orders: Vec<(T::AccountId, BondUnitOrderStruct)> = BondUnitOrder::<T>::drain_prefix(bond).collect();
+            BondUnitOrderCount::<T>::remove(bond);
+            orders.sort_by_key(|(_, order)| order.id);
+            let available = item.inner.bond_units_maxcap_amount.saturating_sub(item.issued_amount);
+            let requested: Vec<BondUnitAmount> = orders
+                .iter()
+                .map(|(investor, order)| Self::order_eligible_units(bond, investor, order))
+                .collect();
+            let allocation = rule.allocate(available, &requested);
+
+            for ((investor, order), bond_units) in orders.into_iter().zip(allocation) {
+                let package_value = item.issue_price(bond_units);
+                Self::reserved_sub(&investor, order.amount)?;
+                let refund = order.amount - package_value;
+                Self::balance_add(&investor, refund)?;
+                if bond_units > 0 {
+                    BondUnitPackageRegistry::<T>::mutate(bond, &investor, |packages| {
+                        packages.push(BondUnitPackage {
+                            bond_units,
+                            acquisition: 0,
+                            coupon_yield: 0,
+                        });
+                    });
+                    item.issued_amount += bond_units;
+                    item.increase(package_value);
+                }
+                Self::update_account_bond_index(bond, &investor);
+                Self::deposit_event(Event::<T>::BondUnitOrderAllocated(investor, *bond, bond_units, refund));
+            }
+            Ok(())
+        }
+
+        /// <pre>
+        /// Cancels all bookbuilding orders of the bond, returning reserved EverUSD to Investors
+        /// </pre>
+        fn refund_bond_unit_orders(bond: &BondId) -> DispatchResult {
+            let orders: Vec<(T::AccountId, BondUnitOrderStruct)> = BondUnitOrder::<T>::drain_prefix(bond).collect();
+            BondUnitOrderCount::<T>::remove(bond);
+            for (investor, order) in orders {
+                Self::balance_unreserve(&investor, order.amount)?;
+                Self::update_account_bond_index(bond, &investor);
+            }
+            Ok(())
+        }
+
+        /// <pre>
+        /// Returns the number of bond units of the order, which can be filled at activation.
+        /// Orders of investors, which became ineligible after placement, are refunded
+        /// </pre>
+        fn order_eligible_units(bond: &BondId, investor: &T::AccountId, order: &BondUnitOrderStruct) -> BondUnitAmount {
+            match T::BondTransferGuard::ensure_can_acquire(bond, None, investor, order.bond_units) {
+                Ok(_) => order.bond_units,
+                Err(_) => 0,
+            }
+        }
+
+        /// <pre>
+        /// Returns the number of bond units, the bond would issue if activated now:
+        /// issued bond units and bookbuilding orders, which can be filled within maxcap
+        /// </pre>
+        fn subscribed_bond_units(bond: &BondId, item: &BondStructOf<T>) -> BondUnitAmount {
+            if item.inner.bookbuilding.is_none() {
+                return item.issued_amount;
+            }
+            let available = item.inner.bond_units_maxcap_amount.saturating_sub(item.issued_amount);
+            let ordered: u64 = BondUnitOrder::<T>::iter_prefix(bond)
+                .map(|(investor, order)| Self::order_eligible_units(bond, &investor, &order) as u64)
+                .sum();
+            item.issued_amount + min(ordered, available as u64) as BondUnitAmount
+        }
+
+        /// <pre>
+        /// Returns the number of bond units, voted for and against restructuring proposal.
+        /// Votes are weighted by bond units, currently held by bondholders
+        /// </pre>
+        fn restructuring_votes(bond: &BondId) -> (u64, u64) {
+            BondRestructuringVote::<T>::iter_prefix(bond).fold((0, 0), |(votes_for, votes_against), (bondholder, approve)| {
+                let units = Self::bondholder_units(bond, &bondholder) as u64;
+                if approve {
+                    (votes_for + units, votes_against)
+                } else {
+                    (votes_for, votes_against + units)
+                }
+            })
+        }
+
+        /// <pre>
+        /// Checks if restructuring can be applied to the bond in the current period,
+        /// and restructured bond has valid terms
+        /// </pre>
+        fn ensure_restructuring_valid(
+            item: &BondStructOf<T>,
+            restructuring: &BondRestructuring,
+            now: T::Moment,
+        ) -> DispatchResult {
+            let (_, period) = item.time_passed_after_activation(now).ok_or(Error::<T>::BondOutOfOrder)?;
+            let mut restructured = item.clone();
+            ensure!(
+                restructured.restructure(restructuring, period) && restructured.inner.is_valid(T::TimeStep::get()),
+                Error::<T>::BondParamIncorrect
+            );
+            Ok(())
+        }
+
+        /// <pre>
+        /// Applies approved restructuring to the bond. Coupon yield of passed periods
+        /// is accrued before bond terms change. Restructuring must be checked with
+        /// ensure_restructuring_valid before
+        /// </pre>
+        fn restructure_bond(bond: &BondId, item: &mut BondStructOf<T>, restructuring: &BondRestructuring, now: T::Moment) {
+            let period = item.time_passed_after_activation(now).map_or(0, |(_, period)| period);
+            Self::calc_and_store_bond_coupon_yield(bond, item, now);
+            item.restructure(restructuring, period);
+            match *restructuring {
+                BondRestructuring::MATURITY_EXTENSION(_) => {
+                    BondImpactReport::<T>::mutate(bond, |reports| {
+                        reports.resize(item.inner.bond_duration as usize, Default::default())
+                    });
+                }
+                BondRestructuring::COUPON_HOLIDAY(periods) => {
+                    BondCouponHoliday::<T>::mutate(bond, |holidays| {
+                        holidays.extend(period..period + periods);
+                        holidays.sort_unstable();
+                        holidays.dedup();
+                    });
+                }
+                BondRestructuring::RATE_CHANGE(_) => (),
+            }
+        }
+    
+        /// <pre>
+        /// Removes expired mint requests, visiting at most "limit" requests starting
+        /// after MintRequestSweepCursor. Returns the number of visited requests
+        /// </pre>
+        pub fn sweep_expired_mint_requests(now: T::Moment, limit: u32) -> u32 {
+            let mut iter = match MintRequestSweepCursor::<T>::take() {
+                Some(key) => MintRequestEverUSD::<T>::iter_from(key),
+                None => MintRequestEverUSD::<T>::iter(),
+            };
+            let mut visited = 0;
+            let mut expired = Vec::new();
+            while visited < limit {
+                match iter.next() {
+                    Some((acc, id, request)) => {
+                        visited += 1;
+                        if request.is_expired(now) {
+                            expired.push((acc, id, request.remaining()));
+                        }
+                    },
+                    None => break,
+                }
+            }
+            if visited == limit {
+                MintRequestSweepCursor::<T>::put(iter.last_raw_key().to_vec());
+            }
+
+            for (acc, id, amount) in expired {
+                MintRequestEverUSD::<T>::remove(&acc, id);
+                Self::deposit_event(Event::<T>::MintRequestExpired(acc, id, amount));
+            }
+            visited
+        }
+
+        /// <pre>
+        /// Removes expired burn requests, visiting at most "limit" requests starting
+        /// after BurnRequestSweepCursor. Returns the number of visited requests
+        /// </pre>
+        pub fn sweep_expired_burn_requests(now: T::Moment, limit: u32) -> u32 {
+            let mut iter = match BurnRequestSweepCursor::<T>::take() {
+                Some(key) => BurnRequestEverUSD::<T>::iter_from(key),
+                None => BurnRequestEverUSD::<T>::iter(),
+            };
+            let mut visited = 0;
+            let mut expired = Vec::new();
+            while visited < limit {
+                match iter.next() {
+                    Some((acc, id, request)) => {
+                        visited += 1;
+                        if request.is_expired(now) {
+                            expired.push((acc, id, request.remaining()));
+                        }
+                    },
+                    None => break,
+                }
+            }
+            if visited == limit {
+                BurnRequestSweepCursor::<T>::put(iter.last_raw_key().to_vec());
+            }
+
+            for (acc, id, amount) in expired {
+                BurnRequestEverUSD::<T>::remove(&acc, id);
+                Self::deposit_event(Event::<T>::BurnRequestExpired(acc, id, amount));
+            }
+            visited
+        }
+
+        /// <pre>
+        /// Removes expired purchase bids and releases their reserve, visiting at most "limit"
+        /// (bond, bidder) pairs starting after PurchaseBidSweepCursor. Returns the number of visited pairs
+        /// </pre>
+        pub fn sweep_expired_purchase_bids(now: T::Moment, limit: u32) -> u32 {
+            let mut iter = match PurchaseBidSweepCursor::<T>::take() {
+                Some(key) => BondUnitPurchaseBid::<T>::iter_from(key),
+                None => BondUnitPurchaseBid::<T>::iter(),
+            };
+            let mut visited = 0;
+            let mut expired = Vec::new();
+            while visited < limit {
+                match iter.next() {
+                    Some((bond, bidder, bids)) => {
+                        visited += 1;
+                        if bids.iter().any(|bid| bid.is_expired(now)) {
+                            expired.push((bond, bidder));
+                        }
+                    },
+                    None => break,
+                }
+            }
+            if visited == limit {
+                PurchaseBidSweepCursor::<T>::put(iter.last_raw_key().to_vec());
+            }
+
+            for (bond, bidder) in expired {
+                // bids are kept, if their reserve can't be released
+                let _ = Self::expire_purchase_bids(&bond, &bidder, now);
+            }
+            visited
+        }
+
+        /// <pre>
+        /// Releases the reserve of expired purchase bids of the bidder and removes them
+        /// </pre>
+        #[transactional]
+        fn expire_purchase_bids(bond: &BondId, bidder: &T::AccountId, now: T::Moment) -> DispatchResult {
+            let mut bids = BondUnitPurchaseBid::<T>::get(bond, bidder);
+            let released: EverUSDBalance = bids.iter()
+                .filter(|bid| bid.is_expired(now))
+                .map(|bid| bid.amount)
+                .fold(0, |sum, amount| sum.saturating_add(amount));
+            Self::balance_unreserve(bidder, released)?;
+
+            for bid in bids.iter().filter(|bid| bid.is_expired(now)) {
+                Self::deposit_event(Event::<T>::BondPurchaseBidExpired(bidder.clone(), *bond, bid.id));
+            }
+            bids.retain(|bid| !bid.is_expired(now));
+            if bids.is_empty() {
+                BondUnitPurchaseBid::<T>::remove(bond, bidder);
+            } else {
+                BondUnitPurchaseBid::<T>::insert(bond, bidder, bids);
+            }
+            Self::update_account_bond_index(bond, bidder);
+            Ok(())
+        }
+
+        /// <pre>
+        /// Visits bonds starting after BondLifecycleCursor and applies time-driven transitions
+        /// while "remaining_weight" allows the most expensive transition, but no more than
+        /// MaxLifecycleOpsPerBlock bonds. Withdrawal is charged for every bondholder, bond which
+        /// withdrawal doesn't fit into the whole "remaining_weight" is left to Bond Arranger.
+        /// Returns consumed weight
+        /// </pre>
+        pub fn process_bond_lifecycle(now: T::Moment, remaining_weight: Weight) -> Weight {
+            let check_weight = T::WeightInfo::bond_lifecycle_check();
+            let withdraw_weight = T::WeightInfo::bond_withdraw();
+            let bankrupt_weight = T::WeightInfo::bond_declare_bankrupt();
+            let return_weight = T::WeightInfo::bond_unit_package_return_all();
+            let order_weight = T::WeightInfo::bond_unit_order_allocate();
+            let step_weight = check_weight.saturating_add(withdraw_weight.max(bankrupt_weight));
+
+            let mut consumed: Weight = 0;
+            let mut visited = 0;
+            let mut finished = false;
+            let mut to_withdraw = Vec::new();
+            let mut to_bankrupt = Vec::new();
+            let mut iter = match BondLifecycleCursor::<T>::get() {
+                Some(key) => BondRegistry::<T>::iter_from(key),
+                None => BondRegistry::<T>::iter(),
+            };
+            let mut cursor = BondLifecycleCursor::<T>::get();
+            while visited < T::MaxLifecycleOpsPerBlock::get()
+                && consumed.saturating_add(step_weight) <= remaining_weight
+            {
+                match iter.next() {
+                    Some((id, item)) => {
+                        match item.state {
+                            BondState::BOOKING
+                                if item.inner.mincap_deadline <= now
+                                    && item.inner.bond_units_mincap_amount > item.issued_amount =>
+                            {
+                                // bookbuilding orders are counted and refunded on withdrawal
+                                let orders = BondUnitOrderCount::<T>::get(&id) as Weight;
+                                let check = check_weight.saturating_add(order_weight.saturating_mul(orders));
+                                if item.inner.bond_units_mincap_amount <= Self::subscribed_bond_units(&id, &item) {
+                                    // bond waits for activation
+                                    consumed = consumed.saturating_add(check);
+                                } else {
+                                    let holders = BondUnitPackageRegistry::<T>::iter_key_prefix(&id).count() as Weight;
+                                    let weight = check
+                                        .saturating_add(withdraw_weight)
+                                        .saturating_add(return_weight.saturating_mul(holders));
+                                    if consumed.saturating_add(weight) > remaining_weight {
+                                        if consumed > 0 {
+                                            // retry in the next block
+                                            break;
+                                        }
+                                        consumed = consumed.saturating_add(check);
+                                    } else {
+                                        consumed = consumed.saturating_add(weight);
+                                        to_withdraw.push(id);
+                                    }
+                                }
+                            },
+                            BondState::ACTIVE
+                                if item.is_finishing_period_expired(now) && !Self::bond_paid_in_full(&item) =>
+                            {
+                                consumed = consumed.saturating_add(check_weight).saturating_add(bankrupt_weight);
+                                to_bankrupt.push(id);
+                            },
+                            _ => consumed = consumed.saturating_add(check_weight),
+                        }
+                        visited += 1;
+                        cursor = Some(iter.last_raw_key().to_vec());
+                    },
+                    None => {
+                        finished = true;
+                        break;
+                    },
+                }
+            }
+            if visited == 0 && !finished {
+                // not enough weight even for one bond
+                return consumed;
+            }
+            match cursor {
+                Some(key) if !finished => BondLifecycleCursor::<T>::put(key),
+                _ => BondLifecycleCursor::<T>::kill(),
+            }
+
+            for id in to_withdraw {
+                if let Err(error) = Self::bond_auto_withdraw(&id, now) {
+                    Self::deposit_event(Event::<T>::BondAutoTransitionFailed(id, error));
+                }
+            }
+            for id in to_bankrupt {
+                if let Err(error) = Self::bond_auto_bankrupt(&id, now) {
+                    Self::deposit_event(Event::<T>::BondAutoTransitionFailed(id, error));
+                }
+            }
+            consumed.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+        }
+
+        /// <pre>
+        /// Returns BOOKING bond, that missed "mincap_deadline", back to PREPARE state.
+        /// Acts like bond_withdraw() called by Bond Arranger
+        /// </pre>
+        #[transactional]
+        fn bond_auto_withdraw(bond: &BondId, now: T::Moment) -> DispatchResult {
+            BondRegistry::<T>::try_mutate(bond, |maybe_item| {
+                let item = maybe_item.as_mut().ok_or(Error::<T>::BondNotFound)?;
+                ensure!(item.state == BondState::BOOKING, Error::<T>::BondStateNotPermitAction);
+                ensure!(
+                    item.inner.bond_units_mincap_amount > Self::subscribed_bond_units(bond, item),
+                    Error::<T>::BondParamIncorrect
+                );
+                ensure!(item.inner.mincap_deadline <= now, Error::<T>::BondStateNotPermitAction);
+
+                Self::return_bond_units(bond, item)?;
+                Self::refund_bond_unit_orders(bond)?;
+                Self::deposit_event(Event::<T>::BondAutoWithdrawal(*bond));
+                Ok(())
+            })
+        }
+
+        /// <pre>
+        /// Moves ACTIVE bond to BANKRUPT state if Issuer didn't pay off the debt
+        /// (accrued coupon yield and principal) until the end of "bond_finishing_period"
+        /// </pre>
+        #[transactional]
+        fn bond_auto_bankrupt(bond: &BondId, now: T::Moment) -> DispatchResult {
+            BondRegistry::<T>::try_mutate(bond, |maybe_item| {
+                let item = maybe_item.as_mut().ok_or(Error::<T>::BondNotFound)?;
+                ensure!(item.state == BondState::ACTIVE, Error::<T>::BondStateNotPermitAction);
+                ensure!(item.is_finishing_period_expired(now), Error::<T>::BondOutOfOrder);
+                Self::calc_and_store_bond_coupon_yield(bond, item, now);
+
+                if !Self::bond_paid_in_full(item) {
+                    item.state = BondState::BANKRUPT;
+                    item.nonce += 1;
+                    Self::deposit_event(Event::<T>::BondAutoBankrupted(*bond, item.bond_credit, item.bond_debit));
+                }
+                Ok(())
+            })
+        }
+
+        /// <pre>
+        /// Returns true if bond balance covers calculated coupon yield and principal
+        /// </pre>
+        fn bond_paid_in_full(item: &BondStructOf<T>) -> bool {
+            let liabilities = item
+                .bond_credit
+                .saturating_add(item.outstanding_par_value(item.issued_amount, item.get_periods()));
+            liabilities <= item.bond_debit
+        }
+
+        /// <pre>
+        /// Moves bond to PREPARE state and returns par value of all bond units to bondholders.
+        /// Used when bond haven't raised "bond_units_mincap_amount" until "mincap_deadline"
+        /// </pre>
+        fn return_bond_units(bond: &BondId, item: &mut BondStructOf<T>) -> DispatchResult {
+            item.state = BondState::PREPARE;
+            item.nonce += 1;
+            ensure!(item.bond_credit == item.issue_price(item.issued_amount), Error::<T>::BondOutOfOrder);
+            // @TODO make it lazy. this implementation do much work to restore balances
+            // that is too CPU and memory expensive.
+            // For each bondholder
+            for (bondholder, package) in BondUnitPackageRegistry::<T>::drain_prefix(bond){
+                let bondholder_total_amount: BondUnitAmount = package.iter()
+                    .map(|item| item.bond_units )
+                    .sum();
+
+                item.issued_amount = item.issued_amount
+                    .checked_sub(bondholder_total_amount)
+                    .ok_or(Error::<T>::BondOutOfOrder)?;
+
+                let transfer = item.issue_price( bondholder_total_amount ) ;
+                item.decrease(transfer);
+
+                Self::balance_add(&bondholder, transfer)?;
+                Self::update_account_bond_index(bond, &bondholder);
+            }
+            ensure!(item.bond_credit == 0 && item.issued_amount == 0, Error::<T>::BondOutOfOrder);
+
+            Ok(())
+        }
+
+        /// <pre>
+        /// Appends a record to the impact report history of the bond period
+        /// </pre>
+        fn impact_report_history_push(
+            bond: &BondId,
+            period: BondPeriodNumber,
+            account: T::AccountId,
+            moment: T::Moment,
+            impact_data: u64,
+            kpi_data: Vec<u64>,
+            action: BondImpactReportAction,
+        ) {
+            BondImpactReportHistory::<T>::mutate(bond, period, |history| {
+                history.push(BondImpactReportHistoryItemOf::<T> { account, moment, impact_data, kpi_data, action })
+            });
+        }
+
+        /// <pre>
+        /// Saves impact report, sent by the bond Issuer or ImpactReporter.
+        /// "kpi_data" must contain a value for each additional KPI of the bond
+        /// </pre>
+        fn impact_report_send(
+            caller: T::AccountId,
+            bond: BondId,
+            period: BondPeriodNumber,
+            impact_data: u64,
+            kpi_data: Vec<u64>,
+        ) -> DispatchResult {
+            let now = Timestamp::<T>::get();
+            let moment = {
+                let item = BondRegistry::<T>::get(bond).ok_or(Error::<T>::BondNotFound)?;
+                ensure!(item.issuer == caller || item.impact_reporter == Some(caller.clone()), Error::<T>::BondAccessDenied);
+                ensure!(kpi_data.len() == item.inner.impact_kpis.len(), Error::<T>::BondParamIncorrect);
+                ensure!(Self::is_report_in_time(&item, now, period), Error::<T>::BondOutOfOrder);
+                item.time_passed_after_activation(now).map(|(moment, _period)| moment).unwrap()
+            };
+
+            let index: usize = period as usize;
+            BondImpactReport::<T>::try_mutate(&bond, |reports| -> DispatchResult {
+                ensure!(index < reports.len() && !reports[index].signed, Error::<T>::BondParamIncorrect);
+
+                reports[index].create_period = moment;
+                reports[index].impact_data = impact_data;
+                reports[index].kpi_data = kpi_data.clone();
+
+                Self::impact_report_history_push(&bond, period, caller.clone(), now, impact_data, kpi_data, BondImpactReportAction::SENT);
+                Self::deposit_event(Event::<T>::BondImpactReportSent(caller, bond, period, impact_data));
+                Ok(())
+            })
+        }
+
+        /// <pre>
+        /// Confirms impact report by Auditor. Auditor must send exact values
+        /// of all KPIs of the report
+        /// </pre>
+        fn impact_report_approve(
+            caller: T::AccountId,
+            bond: BondId,
+            period: BondPeriodNumber,
+            impact_data: u64,
+            kpi_data: Vec<u64>,
+        ) -> DispatchResult {
+            ensure!(accounts::Pallet::<T>::account_is_auditor(&caller), Error::<T>::AccountNotAuthorized);
+            let now = Timestamp::<T>::get();
+            {
+                let item = BondRegistry::<T>::get(bond).ok_or(Error::<T>::BondNotFound)?;
+                ensure!(item.auditor == Some(caller.clone()), Error::<T>::BondAccessDenied);
+                ensure!(Self::is_report_in_time(&item, now, period), Error::<T>::BondOutOfOrder);
+            }
+
+            let index: usize = period as usize;
+            BondImpactReport::<T>::try_mutate(&bond, |reports| -> DispatchResult {
+                ensure!(index < reports.len(), Error::<T>::BondParamIncorrect);
+                let report = &reports[index];
+                ensure!(report.create_period > 0, Error::<T>::BondParamIncorrect);
+                ensure!(
+                    !report.signed && report.impact_data == impact_data && report.kpi_data == kpi_data,
+                    Error::<T>::BondParamIncorrect
+                );
+
+                reports[index].signed = true;
+                Self::impact_report_history_push(&bond, period, caller.clone(), now, impact_data, kpi_data, BondImpactReportAction::APPROVED);
+
+                Self::deposit_event(Event::<T>::BondImpactReportApproved(caller, bond, period, impact_data));
+                Ok(())
+            })
+        }
+
+        /// <pre>
+        /// Returns accrued coupon yield and effective interest rate for each passed period of the bond.
+        /// </pre>
+        pub fn get_coupon_yields(bond: &BondId) -> Vec<PeriodYield> {
+            BondCouponYield::<T>::get(bond)
+        }
+
+        /// <pre>
+        /// Returns all sale lots of the bond, grouped by the seller account.
+        /// </pre>
+        pub fn get_bond_unit_lots(bond: &BondId) -> Vec<(T::AccountId, Vec<BondUnitSaleLotStructOf<T>>)> {
+            BondUnitPackageLot::<T>::iter_prefix(bond).collect()
+        }
+
+        /// <pre>
+        /// Returns upcoming deadlines of the bond as absolute timestamps.
+        /// Deadlines of approved impact reports are omitted.
+        /// </pre>
+        pub fn get_bond_calendar(bond: &BondId) -> Vec<BondDeadlineOf<T>> {
+            match BondRegistry::<T>::get(bond) {
+                Some(item) => Self::bond_deadlines(bond, &item, Timestamp::<T>::get()),
+                None => Vec::new(),
+            }
+        }
+
+        /// <pre>
+        /// Returns upcoming deadlines of all bonds, the account participates in as
+        /// Issuer, Manager, Auditor, ImpactReporter or bondholder, sorted by deadline.
+        /// Bonds are found by AccountBondRoleIndex and AccountBondIndex
+        /// </pre>
+        pub fn get_account_calendar(account: &T::AccountId) -> Vec<BondDeadlineOf<T>> {
+            let now = Timestamp::<T>::get();
+            let mut bonds: Vec<BondId> = AccountBondRoleIndex::<T>::iter_key_prefix(account).collect();
+            for bond in AccountBondIndex::<T>::iter_key_prefix(account) {
+                if !bonds.contains(&bond) {
+                    bonds.push(bond);
+                }
+            }
+            let mut deadlines: Vec<BondDeadlineOf<T>> = bonds
+                .iter()
+                .filter_map(|bond| BondRegistry::<T>::get(bond).map(|item| Self::bond_deadlines(bond, &item, now)))
+                .flatten()
+                .collect();
+            deadlines.sort_by_key(|deadline| deadline.deadline);
+            deadlines
+        }
+
+        fn bond_deadlines(bond: &BondId, item: &BondStructOf<T>, now: T::Moment) -> Vec<BondDeadlineOf<T>> {
+            let reports = BondImpactReport::<T>::get(bond);
+            item.deadlines(bond, now)
+                .into_iter()
+                .filter(|deadline| {
+                    !matches!(deadline.kind, BondDeadlineKind::IMPACT_REPORT | BondDeadlineKind::IMPACT_REPORT_APPROVAL)
+                        || !reports.get(deadline.period as usize).map(|report| report.signed).unwrap_or(false)
+                })
+                .collect()
         }
-    
-        #[cfg(test)]
-        pub fn bond_packages(id: &BondId) -> std::collections::HashMap<T::AccountId, Vec<BondUnitPackage>>
-        where
-            <T as frame_system::Config>::AccountId: std::hash::Hash,
-        {
-            BondUnitPackageRegistry::<T>::iter_prefix(id).collect()
+
+        /// <pre>
+        /// Returns interest rates of all periods up to the current one, including
+        /// the periods, which coupon yield hasn't been calculated yet
+        /// </pre>
+        fn interest_rates(bond: &BondId, item: &BondStructOf<T>, period: BondPeriodNumber) -> Vec<BondInterest> {
+            let reports = BondImpactReport::<T>::get(bond);
+            let holidays = BondCouponHoliday::<T>::get(bond);
+            let mut rates: Vec<BondInterest> = BondCouponYield::<T>::get(bond)
+                .iter()
+                .map(|period_yield| period_yield.interest_rate)
+                .collect();
+            while rates.len() <= period as usize {
+                let rate = Self::period_interest_rate(item, rates.len(), &rates, &reports, &holidays);
+                rates.push(rate);
+            }
+            rates.truncate(period as usize + 1);
+            rates
         }
-    
+
         /// <pre>
-        /// Increase account balance by `amount` EverUSD
+        /// Returns reference price of "bond_units" of the active bond: interest accrued since
+        /// the beginning of the current period, clean price and dirty price.
+        /// None if the bond isn't active
         /// </pre>
-        pub fn balance_add(who: &T::AccountId, amount: EverUSDBalance) -> DispatchResult {
-            BalanceEverUSD::<T>::try_mutate(who, |balance| -> DispatchResult {
-                *balance = balance
-                    .checked_add(amount)
-                    .ok_or(Error::<T>::BalanceOverdraft)?;
-                Ok(())
+        pub fn get_bond_unit_price(bond: &BondId, bond_units: BondUnitAmount) -> Option<BondUnitPriceStruct> {
+            let item = BondRegistry::<T>::get(bond)?;
+            let (moment, period) = item.time_passed_after_activation(Timestamp::<T>::get())?;
+            let clean_price = bond_units as EverUSDBalance * item.unit_price(moment, period);
+            // coupon yield isn't accrued after maturity date
+            if period > item.inner.bond_duration {
+                return Some(BondUnitPriceStruct { interest_rate: 0, accrued_interest: 0, clean_price, dirty_price: clean_price });
+            }
+            let desc = item.period_desc(period)?;
+            let interest_rate = *Self::interest_rates(bond, &item, period).last()?;
+            let days = (moment.saturating_sub(desc.start_period) / T::TimeStep::get()) as EverUSDBalance;
+            let accrued_interest = Self::coupon_yield(&item, period, interest_rate, bond_units, days);
+            Some(BondUnitPriceStruct {
+                interest_rate,
+                accrued_interest,
+                clean_price,
+                dirty_price: clean_price + accrued_interest,
             })
         }
-    
+
         /// <pre>
-        /// Decrease account balance by `amount` EverUSD
+        /// Returns yield to maturity of "bond_units" of the active bond, bought at "price".
+        /// Future coupon yield is projected with the interest rate of the current period,
+        /// except coupon holidays. Cash flows are discounted at the end of every period.
+        /// Measured in the same units as interest rates (see interest_rate_base_value).
+        /// None if the bond isn't active, or yield is negative
         /// </pre>
-        pub fn balance_sub(who: &T::AccountId, amount: EverUSDBalance) -> DispatchResult {
-            BalanceEverUSD::<T>::try_mutate(who, |balance| -> DispatchResult {
-                *balance = balance
-                    .checked_sub(amount)
-                    .ok_or(Error::<T>::BalanceOverdraft)?;
-                Ok(())
+        pub fn get_yield_to_maturity(bond: &BondId, bond_units: BondUnitAmount, price: EverUSDBalance) -> Option<BondInterest> {
+            if bond_units == 0 || price == 0 {
+                return None;
+            }
+            let item = BondRegistry::<T>::get(bond)?;
+            let (moment, period) = item.time_passed_after_activation(Timestamp::<T>::get())?;
+            if period > item.inner.bond_duration {
+                return None;
+            }
+            let time_step = T::TimeStep::get();
+            let holidays = BondCouponHoliday::<T>::get(bond);
+            let rates = Self::interest_rates(bond, &item, period);
+            let projected_rate = Self::last_interest_rate(&item, &rates, &holidays);
+
+            // cash flows: (days after the previous cash flow, amount)
+            let mut cash_flows: Vec<(EverUSDBalance, EverUSDBalance)> = Vec::new();
+            let mut from = moment;
+            for index in period..=item.inner.bond_duration {
+                let desc = item.period_desc(index)?;
+                let interest_rate = if index == period {
+                    rates[index as usize]
+                } else if holidays.contains(&index) {
+                    0
+                } else {
+                    projected_rate
+                };
+                // buyer receives coupon yield of the whole current period
+                let days = ((desc.payment_period - desc.start_period) / time_step) as EverUSDBalance;
+                let mut amount = Self::coupon_yield(&item, index, interest_rate, bond_units, days)
+                    + item.unit_principal(index) * bond_units as EverUSDBalance;
+                if index == item.inner.bond_duration {
+                    amount += item.outstanding_par_value(bond_units, index + 1);
+                }
+                cash_flows.push(((desc.payment_period.saturating_sub(from) / time_step) as EverUSDBalance, amount));
+                from = desc.payment_period;
+            }
+
+            let price = price as u128;
+            if Self::present_value(&cash_flows, 0) < price {
+                return None;
+            }
+            // the largest yield, which present value of cash flows is not less than the price
+            let (mut low, mut high) = (0, MAX_YIELD_TO_MATURITY);
+            while low < high {
+                let mid = low + (high - low + 1) / 2;
+                if Self::present_value(&cash_flows, mid) >= price {
+                    low = mid;
+                } else {
+                    high = mid - 1;
+                }
+            }
+            Some(low)
+        }
+
+        /// <pre>
+        /// Returns implied annual yield of zero-coupon bond, bought at issue price and held
+        /// until maturity date. None if the bond isn't found or isn't zero-coupon bond
+        /// </pre>
+        pub fn get_implied_yield(bond: &BondId) -> Option<BondInterest> {
+            let item = BondRegistry::<T>::get(bond)?;
+            if !item.inner.is_zero_coupon() {
+                return None;
+            }
+            Some(item.implied_yield(T::TimeStep::get()))
+        }
+
+        /// <pre>
+        /// Returns present value of cash flows, discounted with the yield at the end of every period
+        /// </pre>
+        fn present_value(cash_flows: &[(EverUSDBalance, EverUSDBalance)], ytm: BondInterest) -> u128 {
+            const SCALE: u128 = 1_000_000_000_000_000_000;
+            // interest rate 100_000 is 100% per year
+            let year = 100_000 * INTEREST_RATE_YEAR as u128;
+            let mut discount = SCALE;
+            cash_flows.iter().fold(0, |value, &(days, amount)| {
+                discount = discount * year / (year + ytm as u128 * days as u128);
+                value + amount as u128 * discount / SCALE
             })
         }
-    
-        /// Transfers everusd to an account
-        #[inline]
-        pub fn transfer_everusd(from: &T::AccountId, to: &T::AccountId, amount: EverUSDBalance) -> DispatchResult{ 
-            Self::balance_sub(from, amount)?;
-            Self::balance_add(to, amount)?;
-            Ok(())
+
+        /// <pre>
+        /// Adds the bond to AccountBondIndex of the account, if the account holds bond units,
+        /// sale lots, purchase bids or bookbuilding order of the bond, and removes it otherwise.
+        /// Should be called after every change of these registries
+        /// </pre>
+        fn update_account_bond_index(bond: &BondId, account: &T::AccountId) {
+            if !BondUnitPackageRegistry::<T>::get(bond, account).is_empty()
+                || !BondUnitPackageLot::<T>::get(bond, account).is_empty()
+                || !BondUnitPurchaseBid::<T>::get(bond, account).is_empty()
+                || BondUnitOrder::<T>::contains_key(bond, account)
+            {
+                AccountBondIndex::<T>::insert(account, bond, ());
+            } else {
+                AccountBondIndex::<T>::remove(account, bond);
+            }
         }
-    
-        /// Gets bon unit investmets per account
-        #[inline]
-        pub fn get_bond_account_investment(bond_id: &BondId) -> Vec<(T::AccountId, u32)> {
-            BondUnitPackageRegistry::<T>::iter()
-                                        .filter(|(x, _, _)|{ x == bond_id })
-                                        .map(|(_, y, z)| 
-                                                {(y, z.iter()
-                                                        .map(|x| x.bond_units)
-                                                        .reduce(|a, b| a + b)
-                                                        .unwrap_or(0))}
-                                        )
-                                        .collect::<Vec<(T::AccountId, u32)>>()
+
+        /// <pre>
+        /// Adds the bond to AccountBondRoleIndex of the account, if the account is Issuer, Manager,
+        /// Auditor or ImpactReporter of the bond, and removes it otherwise.
+        /// Should be called after every change of bond roles, "item" is None for removed bond
+        /// </pre>
+        fn update_account_bond_role_index(bond: &BondId, item: Option<&BondStructOf<T>>, account: &T::AccountId) {
+            if item.map_or(false, |item| item.role_accounts().contains(&account)) {
+                AccountBondRoleIndex::<T>::insert(account, bond, ());
+            } else {
+                AccountBondRoleIndex::<T>::remove(account, bond);
+            }
         }
-    
+
         /// <pre>
-        /// Deletes expired burn requests.
-        /// Process less or equal than MAX_PURGE_REQUESTS expired requests
+        /// Returns EverUSD balances of the account and its positions in all bonds,
+        /// found by AccountBondIndex. Unrealised coupon includes coupon yield of passed
+        /// periods, which hasn't been calculated yet
         /// </pre>
-        pub fn purge_expired_burn_requests(before: T::Moment) {
-            let to_purge: Vec<_> = BurnRequestEverUSD::<T>::iter()
-                .filter(|(_, request)| request.is_expired(before))
-                .map(|(acc, _)| acc)
-                .take(MAX_PURGE_REQUESTS)
+        pub fn get_portfolio(account: &T::AccountId) -> BondPortfolioStructOf<T> {
+            let now = Timestamp::<T>::get();
+            let positions = AccountBondIndex::<T>::iter_key_prefix(account)
+                .filter_map(|bond| {
+                    let item = BondRegistry::<T>::get(bond)?;
+                    let packages = BondUnitPackageRegistry::<T>::get(bond, account);
+                    let bond_units: BondUnitAmount = packages.iter().map(|package| package.bond_units).sum();
+                    let mut bond_yields = BondCouponYield::<T>::get(bond);
+                    if let Some((_, period)) = item.time_passed_after_activation(now) {
+                        if period as usize > bond_yields.len() {
+                            let rates = Self::interest_rates(&bond, &item, period - 1);
+                            bond_yields.extend(rates[bond_yields.len()..].iter().map(|&interest_rate| PeriodYield {
+                                interest_rate,
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                    let unrealised_coupon = if packages.is_empty() || bond_yields.is_empty() {
+                        0
+                    } else {
+                        Self::packages_claim(&item, &packages, &bond_yields).1
+                    };
+                    Some(BondPositionStruct {
+                        bond,
+                        state: item.state.clone(),
+                        bond_units,
+                        par_value: item.outstanding_par_value(bond_units, bond_yields.len() as BondPeriodNumber),
+                        unrealised_coupon,
+                        last_coupon_yield: BondLastCouponYield::<T>::get(bond, account),
+                        lots: BondUnitPackageLot::<T>::get(bond, account),
+                        bids: BondUnitPurchaseBid::<T>::get(bond, account),
+                        order: BondUnitOrder::<T>::get(bond, account),
+                    })
+                })
                 .collect();
-    
-            for acc in to_purge {
-                BurnRequestEverUSD::<T>::remove(acc);
+
+            BondPortfolioStruct {
+                everusd_balance: BalanceEverUSD::<T>::get(account),
+                everusd_reserved: ReservedEverUSD::<T>::get(account),
+                positions,
             }
         }
-    
+
         /// <pre>
-        /// Deletes expired mint requests from the queue.
-        /// Process less or equal than MAX_PURGE_REQUESTS expired requests
+        /// Reconciles mint and burn requests, confirmed by Custodian, against
+        /// TotalSupplyEverUSD. Pending amounts include outstanding requests,
+        /// which are not expired yet
         /// </pre>
-        pub fn purge_expired_mint_requests(before: T::Moment) {
-            let to_purge: Vec<_> = MintRequestEverUSD::<T>::iter()
-                .filter(|(_, request)| request.is_expired(before))
-                .map(|(acc, _)| acc)
-                .take(MAX_PURGE_REQUESTS)
-                .collect();
-    
-            for acc in to_purge {
-                MintRequestEverUSD::<T>::remove(acc);
+        pub fn get_everusd_reconciliation() -> EverUSDReconciliationStruct {
+            let now = Timestamp::<T>::get();
+            let pending_mint: EverUSDBalance = MintRequestEverUSD::<T>::iter_values()
+                .filter(|request| !request.is_expired(now))
+                .map(|request| request.remaining())
+                .sum();
+            let pending_burn: EverUSDBalance = BurnRequestEverUSD::<T>::iter_values()
+                .filter(|request| !request.is_expired(now))
+                .map(|request| request.remaining())
+                .sum();
+            let total_supply = TotalSupplyEverUSD::<T>::get();
+            let confirmed_mint = ConfirmedMintEverUSD::<T>::get();
+            let confirmed_burn = ConfirmedBurnEverUSD::<T>::get();
+
+            EverUSDReconciliationStruct {
+                total_supply,
+                confirmed_mint,
+                confirmed_burn,
+                pending_mint,
+                pending_burn,
+                reconciled: confirmed_mint.checked_sub(confirmed_burn) == Some(total_supply),
             }
         }
-    
-        #[cfg(test)]
-        pub fn get_coupon_yields(bond: &BondId) -> Vec<PeriodYield> {
-            BondCouponYield::<T>::get(bond)
+
+        /// <pre>
+        /// Returns signed reserve attestation with the latest attestation period
+        /// </pre>
+        pub fn get_latest_reserve_attestation() -> Option<ReserveAttestationStructOf<T>> {
+            LatestReserveAttestation::<T>::get().and_then(ReserveAttestation::<T>::get)
+        }
+
+        /// <pre>
+        /// Returns all purchase bids of the bond, grouped by the bidder account.
+        /// </pre>
+        pub fn get_bond_unit_bids(bond: &BondId) -> Vec<(T::AccountId, Vec<BondUnitPurchaseBidStructOf<T>>)> {
+            BondUnitPurchaseBid::<T>::iter_prefix(bond).collect()
         }
     
         /// <pre>
@@ -1808,38 +4294,16 @@ pub mod pallet {
     
             let reports = BondImpactReport::<T>::get(id);
             assert!(reports.len() + 1 >= period);
+            let holidays = BondCouponHoliday::<T>::get(id);
+            let mut rates: Vec<BondInterest> = bond_yields.iter().map(|period_yield| period_yield.interest_rate).collect();
     
             let mut processed: usize = 0;
             while bond_yields.len() < period {
                 // index - accrued period number
                 let index = bond_yields.len();
     
-                let interest_rate = if bond.inner.is_stable() {
-                    bond.inner.interest_rate_base_value
-                } else {
-                    if index == 0 {
-                        // There is no periods and data yet, set start period interest rate value
-                        bond.inner.interest_rate_start_period_value.unwrap_or(0)
-                    } else if reports[index - 1].signed {
-                        // There is confirmed impact_data about this period
-                        // Calculate interest rate, based on impact_data and baseline,min,max parameters of bond
-                        bond.calc_effective_interest_rate(
-                            bond.inner.impact_data_baseline[index - 1].unwrap_or(0),
-                            reports[index - 1].impact_data,
-                        )
-                    } else {
-                        // Report is missed, apply penalty for missed report(but not more than interest_rate_margin_cap)
-                        min(
-                            bond_yields[index - 1].interest_rate
-                                + bond.inner.interest_rate_penalty_for_missed_report.unwrap_or(0),
-                            bond.inner.interest_rate_margin_cap.unwrap_or(0),
-                        )
-                    }
-                };
-    
-                let package_yield = bond.inner.bond_units_base_price / 1000
-                    * interest_rate as EverUSDBalance
-                    / INTEREST_RATE_YEAR;
+                let interest_rate = Self::period_interest_rate(bond, index, &rates, &reports, &holidays);
+                rates.push(interest_rate);
     
                 // calculate yield for period equal to bond_yields.len()
                 let period_coupon_yield: EverUSDBalance = match bond
@@ -1855,11 +4319,14 @@ pub mod pallet {
                                     .iter()
                                     .map(|package| {
                                         // @TODO use checked arithmetics
-                                        package_yield
-                                            * package.bond_units as EverUSDBalance
-                                            * (period_desc.duration(package.acquisition) / time_step)
-                                                as EverUSDBalance
-                                            / 100
+                                        Self::package_period_payment(
+                                            bond,
+                                            index as BondPeriodNumber,
+                                            interest_rate,
+                                            &period_desc,
+                                            package,
+                                            time_step,
+                                        )
                                     })
                                     .sum::<EverUSDBalance>()
                             })
@@ -1889,6 +4356,187 @@ pub mod pallet {
             processed
         }
     
+        /// <pre>
+        /// Returns effective interest rate of the period "index", calculated from the
+        /// impact report of the previous period. "rates" are interest rates of previous periods
+        /// </pre>
+        fn period_interest_rate(
+            bond: &BondStructOf<T>,
+            index: usize,
+            rates: &[BondInterest],
+            reports: &[BondImpactReportStruct],
+            holidays: &[BondPeriodNumber],
+        ) -> BondInterest {
+            if holidays.contains(&(index as BondPeriodNumber)) {
+                // coupon holiday
+                0
+            } else if bond.inner.is_stable() {
+                bond.inner.interest_rate_base_value
+            } else if index == 0 {
+                // There is no periods and data yet, set start period interest rate value
+                bond.inner.interest_rate_start_period_value.unwrap_or(0)
+            } else if reports[index - 1].signed {
+                // There is confirmed impact_data about this period
+                // Calculate interest rate, based on impact_data and baseline,min,max parameters of bond
+                bond.calc_impact_interest_rate(index - 1, &reports[index - 1])
+            } else {
+                // Report is missed, apply penalty for missed report(but not more than interest_rate_margin_cap)
+                // to the interest rate of the last period, which wasn't coupon holiday
+                let last_interest_rate = Self::last_interest_rate(bond, rates, holidays);
+                min(
+                    last_interest_rate
+                        + bond.inner.interest_rate_penalty_for_missed_report.unwrap_or(0),
+                    bond.inner.interest_rate_margin_cap.unwrap_or(0),
+                )
+            }
+        }
+
+        /// <pre>
+        /// Returns interest rate of the last period, which wasn't coupon holiday
+        /// </pre>
+        fn last_interest_rate(bond: &BondStructOf<T>, rates: &[BondInterest], holidays: &[BondPeriodNumber]) -> BondInterest {
+            rates
+                .iter()
+                .enumerate()
+                .rev()
+                .find(|(i, _)| !holidays.contains(&(*i as BondPeriodNumber)))
+                .map_or(bond.inner.interest_rate_start_period_value.unwrap_or(0), |(_, &rate)| rate)
+        }
+
+        /// <pre>
+        /// Returns coupon yield of "bond_units" for "days" of the period with given interest rate
+        /// </pre>
+        fn coupon_yield(
+            bond: &BondStructOf<T>,
+            period: BondPeriodNumber,
+            interest_rate: BondInterest,
+            bond_units: BondUnitAmount,
+            days: EverUSDBalance,
+        ) -> EverUSDBalance {
+            let package_yield = bond.unit_par_value(period) / 1000
+                * interest_rate as EverUSDBalance
+                / INTEREST_RATE_YEAR;
+            package_yield * bond_units as EverUSDBalance * days / 100
+        }
+
+        /// <pre>
+        /// Returns the payment for the BondUnitsPackage in the period: coupon yield,
+        /// accrued on outstanding par value for the time the package was held, and
+        /// principal, repaid at the end of the period according to "principal_schedule".
+        /// Packages bought after the end of the period don't get its principal
+        /// </pre>
+        fn package_period_payment(
+            bond: &BondStructOf<T>,
+            period: BondPeriodNumber,
+            interest_rate: BondInterest,
+            period_desc: &PeriodDescr,
+            package: &BondUnitPackage,
+            time_step: BondPeriod,
+        ) -> EverUSDBalance {
+            let (coupon_yield, principal) =
+                Self::package_period_coupon_and_principal(bond, period, interest_rate, period_desc, package, time_step);
+            coupon_yield + principal
+        }
+
+        /// <pre>
+        /// Returns coupon yield and principal parts of package_period_payment()
+        /// </pre>
+        fn package_period_coupon_and_principal(
+            bond: &BondStructOf<T>,
+            period: BondPeriodNumber,
+            interest_rate: BondInterest,
+            period_desc: &PeriodDescr,
+            package: &BondUnitPackage,
+            time_step: BondPeriod,
+        ) -> (EverUSDBalance, EverUSDBalance) {
+            let coupon_yield = Self::coupon_yield(
+                bond,
+                period,
+                interest_rate,
+                package.bond_units,
+                (period_desc.duration(package.acquisition) / time_step) as EverUSDBalance,
+            );
+            let principal = if package.acquisition < period_desc.payment_period {
+                bond.unit_principal(period) * package.bond_units as EverUSDBalance
+            } else {
+                0
+            };
+            (coupon_yield, principal)
+        }
+
+        /// <pre>
+        /// Charges fees of given kind on the amount to fee accounts of the bond.
+        /// Returns the total fee, which should be withheld from the amount
+        /// </pre>
+        fn charge_bond_fees(
+            bond: &BondId,
+            fees: &[BondFeeStructOf<T>],
+            kind: BondFeeKind,
+            amount: EverUSDBalance,
+        ) -> EverUSDBalance {
+            let mut total_fee: EverUSDBalance = 0;
+            for fee in fees {
+                let fee_amount = fee.fee(kind, amount);
+                if fee_amount == 0 {
+                    continue;
+                }
+                FeeEverUSD::<T>::mutate(&fee.account, |balance| *balance += fee_amount);
+                total_fee += fee_amount;
+                Self::deposit_event(Event::<T>::BondFeeCharged(*bond, fee.account.clone(), kind, fee_amount));
+            }
+            total_fee
+        }
+
+        /// <pre>
+        /// Transfers bond proceeds to the Issuer. Proceeds of the bond with declared
+        /// use of proceeds are reserved on the Issuer's account until allocated
+        /// </pre>
+        fn pay_proceeds(bond: &BondId, item: &BondStructOf<T>, amount: EverUSDBalance) -> DispatchResult {
+            Self::balance_add(&item.issuer, amount)?;
+            if !item.inner.use_of_proceeds.is_empty() {
+                Self::balance_reserve(&item.issuer, amount)?;
+                BondProceeds::<T>::mutate(bond, |raised| *raised += amount);
+            }
+            Ok(())
+        }
+
+        /// <pre>
+        /// Returns the amount of bond proceeds, allocated to the category
+        /// </pre>
+        fn proceeds_allocated(allocations: &[BondProceedsAllocationStructOf<T>], category: BondProceedsCategory) -> EverUSDBalance {
+            allocations
+                .iter()
+                .filter(|allocation| allocation.category == category)
+                .map(|allocation| allocation.amount)
+                .sum()
+        }
+
+        /// <pre>
+        /// Returns use of proceeds report of the bond: raised and allocated proceeds,
+        /// allocated amount and cap of every declared category, and allocation ledger
+        /// </pre>
+        pub fn get_proceeds_report(bond: &BondId) -> BondProceedsReport<T::Moment> {
+            let categories = match BondRegistry::<T>::get(bond) {
+                Some(item) => item.inner.use_of_proceeds,
+                None => return Default::default(),
+            };
+            let raised = BondProceeds::<T>::get(bond);
+            let allocations = BondProceedsAllocation::<T>::get(bond);
+            BondProceedsReport {
+                raised,
+                allocated: allocations.iter().map(|allocation| allocation.amount).sum(),
+                categories: categories
+                    .iter()
+                    .map(|item| BondProceedsCategoryReport {
+                        category: item.category,
+                        cap: item.cap_amount(raised),
+                        allocated: Self::proceeds_allocated(&allocations, item.category),
+                    })
+                    .collect(),
+                allocations,
+            }
+        }
+
         /// <pre>
         /// Redeem bond units, get principal value, and coupon yield in the balance
         /// Function summarizes data from all passed periods,
@@ -1903,44 +4551,98 @@ pub mod pallet {
             bondholder: &T::AccountId,
         ) -> EverUSDBalance {
             let packages = BondUnitPackageRegistry::<T>::take(id, &bondholder);
-            let time_step = T::TimeStep::get();
             let bond_yields = BondCouponYield::<T>::get(id);
             assert!(!bond_yields.is_empty());
-            // calc coupon yield
-            let mut payable: EverUSDBalance = bond_yields
+            let (payable, coupon_yield) = Self::packages_claim(bond, &packages, &bond_yields);
+            bond.coupon_yield += payable;
+            // coupon fees are withheld from unpaid coupon yield
+            let payable = payable - Self::charge_bond_fees(id, &bond.inner.fees, BondFeeKind::COUPON, coupon_yield);
+    
+            Self::balance_add(bondholder, payable).unwrap();
+            Self::update_account_bond_index(id, bondholder);
+    
+            payable
+        }
+
+        /// <pre>
+        /// Pays off bondholder's share of recovery fund of DEFAULT_SETTLED bond.
+        /// Bondholder's claim (outstanding par value and unpaid coupon yield of his
+        /// BondUnitsPackage-s) is reduced pro-rata, if recovery fund is less than total claim
+        /// </pre>
+        pub fn recover_bond_units(
+            id: &BondId,
+            bond: &mut BondStructOf<T>,
+            bondholder: &T::AccountId,
+        ) -> Result<EverUSDBalance, DispatchError> {
+            let settlement = match BondDefaultSettlement::<T>::get(id) {
+                Some(settlement) => settlement,
+                None => return Ok(0),
+            };
+            let fund = bond.bond_credit
+                .checked_sub(bond.coupon_yield)
+                .ok_or(Error::<T>::BalanceOverdraft)?;
+            let packages = BondUnitPackageRegistry::<T>::take(id, &bondholder);
+            let bond_yields = BondCouponYield::<T>::get(id);
+            let (claim, _) = Self::packages_claim(bond, &packages, &bond_yields);
+            // rounding leftovers stay in bond fund
+            let payable = min(settlement.recovery_amount(claim), fund);
+            bond.coupon_yield += payable;
+
+            Self::balance_add(bondholder, payable)?;
+            Self::update_account_bond_index(id, bondholder);
+
+            Ok(payable)
+        }
+
+        /// <pre>
+        /// Returns unpaid coupon yield of all passed periods and outstanding
+        /// principal value of bondholder's BondUnitsPackage-s, and unpaid coupon yield alone.
+        /// Paid amount is split between coupon yield and principal installments of
+        /// amortized bond in proportion to their accrued values
+        /// </pre>
+        fn packages_claim(
+            bond: &BondStructOf<T>,
+            packages: &[BondUnitPackage],
+            bond_yields: &[PeriodYield],
+        ) -> (EverUSDBalance, EverUSDBalance) {
+            let time_step = T::TimeStep::get();
+            // calc coupon yield and principal installments
+            let (coupon_yield, principal) = bond_yields
                 .iter()
                 .enumerate()
-                .map(|(i, bond_yield)| {
+                .flat_map(|(i, bond_yield)| {
                     let period_desc = bond.period_desc(i as BondPeriodNumber).unwrap();
-                    let package_yield = bond.inner.bond_units_base_price / 1000
-                        * bond_yield.interest_rate as EverUSDBalance
-                        / INTEREST_RATE_YEAR;
-                    packages
-                        .iter()
-                        .map(|package| {
-                            package_yield
-                                * package.bond_units as EverUSDBalance
-                                * (period_desc.duration(package.acquisition) / time_step)
-                                    as EverUSDBalance
-                                / 100
-                        })
-                        .sum::<EverUSDBalance>()
+                    packages.iter().map(move |package| {
+                        Self::package_period_coupon_and_principal(
+                            bond,
+                            i as BondPeriodNumber,
+                            bond_yield.interest_rate,
+                            &period_desc,
+                            package,
+                            time_step,
+                        )
+                    })
                 })
-                .sum::<EverUSDBalance>();
+                .fold((0, 0), |acc: (EverUSDBalance, EverUSDBalance), payment| {
+                    (acc.0 + payment.0, acc.1 + payment.1)
+                });
     
             let (bond_units, paid_yield): (BondUnitAmount, EverUSDBalance) =
                 packages.iter().fold((0, 0), |acc, package| {
                     (acc.0 + package.bond_units, acc.1 + package.coupon_yield)
                 });
             // substrate paid coupon
-            payable -= paid_yield;
-            // add principal value
-            payable += bond.par_value(bond_units);
-            bond.coupon_yield += payable;
-    
-            Self::balance_add(bondholder, payable).unwrap();
-    
-            payable
+            let payable = coupon_yield + principal - paid_yield;
+            let unpaid_coupon_yield = if principal == 0 {
+                payable
+            } else {
+                (payable as u128 * coupon_yield as u128 / (coupon_yield + principal) as u128) as EverUSDBalance
+            };
+            // add outstanding principal value
+            (
+                payable + bond.outstanding_par_value(bond_units, bond_yields.len() as BondPeriodNumber),
+                unpaid_coupon_yield,
+            )
         }
     
         /// <pre>
@@ -1986,6 +4688,8 @@ pub mod pallet {
     
             let time_step = T::TimeStep::get();
             let mut payable = 0;
+            // coupon yield part of payable, without principal installments
+            let mut paid_coupon_yield: EverUSDBalance = 0;
     
             let mut prev_total_yield = if last_bondholder_coupon_yield.period_num == 0 {
                 0
@@ -2037,32 +4741,39 @@ pub mod pallet {
     
                 debug_assert!(installment <= accrued_yield);
     
-                let package_yield = bond.inner.bond_units_base_price / 1000
-                    * bond_yield.interest_rate as EverUSDBalance
-                    / INTEREST_RATE_YEAR;
-    
                 let period_desc = bond.period_desc(i as BondPeriodNumber).unwrap();
     
                 BondUnitPackageRegistry::<T>::mutate(id, &bondholder, |packages| {
                     for package in packages.iter_mut() {
-                        let accrued = package_yield
-                            * package.bond_units as EverUSDBalance
-                            * (period_desc.duration(package.acquisition) / time_step) as EverUSDBalance
-                            / 100;
+                        let (coupon_yield, principal) = Self::package_period_coupon_and_principal(
+                            bond,
+                            i as BondPeriodNumber,
+                            bond_yield.interest_rate,
+                            &period_desc,
+                            package,
+                            time_step,
+                        );
+                        let accrued = coupon_yield + principal;
     
-                        let package_coupon_yield = if installment == accrued_yield {
-                            accrued
+                        let (package_coupon_yield, package_paid_coupon_yield) = if installment == accrued_yield {
+                            (accrued, coupon_yield)
                         } else {
-                            (installment as u128 * accrued as u128 / accrued_yield as u128) as u64
+                            (
+                                (installment as u128 * accrued as u128 / accrued_yield as u128) as u64,
+                                (installment as u128 * coupon_yield as u128 / accrued_yield as u128) as u64,
+                            )
                         };
     
                         payable += package_coupon_yield;
+                        paid_coupon_yield += package_paid_coupon_yield;
                         package.coupon_yield += package_coupon_yield;
                     }
                 });
             }
     
             bond.coupon_yield = bond.coupon_yield.saturating_add(payable);
+            // coupon fees are withheld from paid coupon yield
+            let payable = payable - Self::charge_bond_fees(id, &bond.inner.fees, BondFeeKind::COUPON, paid_coupon_yield);
     
             BondLastCouponYield::<T>::insert(id, &bondholder, last_bondholder_coupon_yield);
             Self::balance_add(bondholder, payable).unwrap();
@@ -2090,13 +4801,9 @@ pub mod pallet {
                 let mut missed_periods = 0;
                 let mut interest: bond::BondInterest = bond.inner.interest_rate_start_period_value.unwrap_or(0);
     
-                for (report, baseline) in reports[0..period]
-                    .iter()
-                    .zip(bond.inner.impact_data_baseline[0..period].iter())
-                    .rev()
-                {
+                for (index, report) in reports[0..period].iter().enumerate().rev() {
                     if report.signed {
-                        interest = bond.calc_effective_interest_rate(baseline.unwrap_or(0), report.impact_data);
+                        interest = bond.calc_impact_interest_rate(index, report);
                         break;
                     }
                     missed_periods += 1;
@@ -2171,14 +4878,18 @@ pub mod pallet {
         #[cfg(test)]
         pub fn evercity_balance() -> ledger::EvercityBalance {
             let account: EverUSDBalance = BalanceEverUSD::<T>::iter_values().sum();
+            let reserved: EverUSDBalance = ReservedEverUSD::<T>::iter_values().sum();
             let bond_fund: EverUSDBalance = BondRegistry::<T>::iter_values()
                 .map(|bond| bond.bond_debit - bond.coupon_yield)
                 .sum();
+            let fees: EverUSDBalance = FeeEverUSD::<T>::iter_values().sum();
     
             ledger::EvercityBalance {
                 supply: TotalSupplyEverUSD::<T>::get(),
                 account,
+                reserved,
                 bond_fund,
+                fees,
             }
         }
 
@@ -2262,8 +4973,31 @@ pub mod pallet {
                              coupon_yield: 0,
                         }
                     );
-                })
+                });
+                Self::update_account_bond_index(bond_id, &acc);
             }
         }
     }
+
+    impl<T: Config> BondTransferGuard<T::AccountId> for Pallet<T> {
+        /// Checks restrictions, set by the bond Issuer: investor should be eligible
+        /// and should not exceed maximum holding after acquisition
+        fn ensure_can_acquire(
+            bond: &BondId,
+            _from: Option<&T::AccountId>,
+            to: &T::AccountId,
+            bond_units: BondUnitAmount,
+        ) -> DispatchResult {
+            let restriction = BondTransferRestriction::<T>::get(bond);
+            ensure!(
+                restriction.is_eligible(BondWhitelist::<T>::get(bond, to).as_ref()),
+                Error::<T>::BondTransferNotPermitted
+            );
+            let holding = Self::bondholder_units(bond, to)
+                .checked_add(bond_units)
+                .ok_or(Error::<T>::BondHoldingLimitExceeded)?;
+            ensure!(restriction.is_holding_permitted(holding), Error::<T>::BondHoldingLimitExceeded);
+            Ok(())
+        }
+    }
 }
\ No newline at end of file
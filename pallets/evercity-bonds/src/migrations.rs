@@ -0,0 +1,320 @@
+//! Storage migrations of the pallet.
+//! Every migration step translates storage of the previous StorageVersion
+//! and is applied once in "on_runtime_upgrade" hook
+use crate::bond::{
+    BondImpactReportStruct, BondImpactType, BondInnerStruct, BondInterest, BondInterestRateCurve, BondPeriod,
+    BondPeriodNumber, BondState, BondStruct, BondUnitAmount, BondUnitSaleLotStruct, CarbonUnitsMetadata,
+    EverUSDBalance,
+};
+use crate::pallet::{
+    AccountBondIndex, AccountBondRoleIndex, BondImpactReport, BondRegistry, BondUnitPackageLot,
+    BondUnitPackageRegistry, BurnRequestEverUSD, Config, ConfirmedBurnEverUSD, ConfirmedMintEverUSD,
+    LastBondUnitSaleLotId, LastTokenBurnRequestId, LastTokenMintRequestId, MintRequestEverUSD, Pallet,
+    TotalSupplyEverUSD,
+};
+use crate::token::{TokenBurnRequestStruct, TokenMintRequestStruct};
+use frame_support::{
+    codec::Decode,
+    dispatch::Vec,
+    migration::storage_key_iter,
+    traits::{GetStorageVersion, PalletInfoAccess, StorageVersion},
+    weights::Weight,
+    Blake2_128Concat,
+};
+
+/// Current storage version of the pallet
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+/// Applies all migration steps, newer than the stored version of the pallet
+pub fn migrate<T: Config>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+    if Pallet::<T>::on_chain_storage_version() < 1 {
+        weight = weight.saturating_add(v1::migrate::<T>());
+    }
+    STORAGE_VERSION.put::<Pallet<T>>();
+    weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Version 1: bond terms are extended with call schedule, principal schedule, discount,
+/// additional KPIs, interest rate curve, bookbuilding, fees and use of proceeds,
+/// impact reports keep KPI values and the last rejection, sale lots have unique ids,
+/// bonds are indexed by accounts of their roles and by accounts of their holders,
+/// accounts can have several EverUSD mint and burn requests with unique ids,
+/// confirmed mints and burns of EverUSD are counted for reconciliation
+pub mod v1 {
+    use super::*;
+
+    #[derive(Decode)]
+    pub struct OldBondInnerStruct<Moment, Hash, AccountId> {
+        pub docs_pack_root_hash_main: Hash,
+        pub docs_pack_root_hash_legal: Hash,
+        pub docs_pack_root_hash_finance: Hash,
+        pub docs_pack_root_hash_tech: Hash,
+        pub impact_data_type: BondImpactType,
+        pub impact_data_baseline: Vec<Option<u64>>,
+        pub impact_data_max_deviation_cap: Option<u64>,
+        pub impact_data_max_deviation_floor: Option<u64>,
+        #[codec(compact)]
+        pub impact_data_send_period: BondPeriod,
+        pub interest_rate_penalty_for_missed_report: Option<BondInterest>,
+        #[codec(compact)]
+        pub interest_rate_base_value: BondInterest,
+        pub interest_rate_margin_cap: Option<BondInterest>,
+        pub interest_rate_margin_floor: Option<BondInterest>,
+        pub interest_rate_start_period_value: Option<BondInterest>,
+        pub interest_pay_period: Option<BondPeriod>,
+        pub start_period: Option<BondPeriod>,
+        pub payment_period: BondPeriod,
+        #[codec(compact)]
+        pub bond_duration: BondPeriodNumber,
+        #[codec(compact)]
+        pub bond_finishing_period: BondPeriod,
+        #[codec(compact)]
+        pub mincap_deadline: Moment,
+        #[codec(compact)]
+        pub bond_units_mincap_amount: BondUnitAmount,
+        #[codec(compact)]
+        pub bond_units_maxcap_amount: BondUnitAmount,
+        #[codec(compact)]
+        pub bond_units_base_price: EverUSDBalance,
+        pub carbon_metadata: Option<CarbonUnitsMetadata<AccountId>>,
+    }
+
+    #[derive(Decode)]
+    pub struct OldBondStruct<AccountId, Moment, Hash> {
+        pub inner: OldBondInnerStruct<Moment, Hash, AccountId>,
+        pub issuer: AccountId,
+        pub manager: Option<AccountId>,
+        pub auditor: Option<AccountId>,
+        pub impact_reporter: Option<AccountId>,
+        #[codec(compact)]
+        pub issued_amount: BondUnitAmount,
+        #[codec(compact)]
+        pub creation_date: Moment,
+        #[codec(compact)]
+        pub booking_start_date: Moment,
+        #[codec(compact)]
+        pub active_start_date: Moment,
+        pub state: BondState,
+        #[codec(compact)]
+        pub bond_debit: EverUSDBalance,
+        #[codec(compact)]
+        pub bond_credit: EverUSDBalance,
+        #[codec(compact)]
+        pub coupon_yield: EverUSDBalance,
+        #[codec(compact)]
+        pub nonce: u64,
+    }
+
+    #[derive(Decode)]
+    pub struct OldBondImpactReportStruct {
+        #[codec(compact)]
+        pub create_period: BondPeriod,
+        #[codec(compact)]
+        pub impact_data: u64,
+        pub signed: bool,
+    }
+
+    #[derive(Decode)]
+    pub struct OldBondUnitSaleLotStruct<AccountId, Moment> {
+        #[codec(compact)]
+        pub deadline: Moment,
+        pub new_bondholder: Option<AccountId>,
+        #[codec(compact)]
+        pub bond_units: BondUnitAmount,
+        #[codec(compact)]
+        pub amount: EverUSDBalance,
+    }
+
+    impl<AccountId, Moment, Hash> From<OldBondStruct<AccountId, Moment, Hash>> for BondStruct<AccountId, Moment, Hash> {
+        fn from(old: OldBondStruct<AccountId, Moment, Hash>) -> Self {
+            let inner = old.inner;
+            BondStruct {
+                inner: BondInnerStruct {
+                    docs_pack_root_hash_main: inner.docs_pack_root_hash_main,
+                    docs_pack_root_hash_legal: inner.docs_pack_root_hash_legal,
+                    docs_pack_root_hash_finance: inner.docs_pack_root_hash_finance,
+                    docs_pack_root_hash_tech: inner.docs_pack_root_hash_tech,
+                    impact_data_type: inner.impact_data_type,
+                    impact_data_baseline: inner.impact_data_baseline,
+                    impact_data_max_deviation_cap: inner.impact_data_max_deviation_cap,
+                    impact_data_max_deviation_floor: inner.impact_data_max_deviation_floor,
+                    impact_data_send_period: inner.impact_data_send_period,
+                    interest_rate_penalty_for_missed_report: inner.interest_rate_penalty_for_missed_report,
+                    interest_rate_base_value: inner.interest_rate_base_value,
+                    interest_rate_margin_cap: inner.interest_rate_margin_cap,
+                    interest_rate_margin_floor: inner.interest_rate_margin_floor,
+                    interest_rate_start_period_value: inner.interest_rate_start_period_value,
+                    interest_pay_period: inner.interest_pay_period,
+                    start_period: inner.start_period,
+                    payment_period: inner.payment_period,
+                    bond_duration: inner.bond_duration,
+                    bond_finishing_period: inner.bond_finishing_period,
+                    mincap_deadline: inner.mincap_deadline,
+                    bond_units_mincap_amount: inner.bond_units_mincap_amount,
+                    bond_units_maxcap_amount: inner.bond_units_maxcap_amount,
+                    bond_units_base_price: inner.bond_units_base_price,
+                    carbon_metadata: inner.carbon_metadata,
+                    call_schedule: None,
+                    principal_schedule: None,
+                    discount: None,
+                    impact_kpis: Vec::new(),
+                    interest_rate_curve: BondInterestRateCurve::LINEAR,
+                    bookbuilding: None,
+                    fees: Vec::new(),
+                    use_of_proceeds: Vec::new(),
+                },
+                issuer: old.issuer,
+                manager: old.manager,
+                auditor: old.auditor,
+                impact_reporter: old.impact_reporter,
+                issued_amount: old.issued_amount,
+                creation_date: old.creation_date,
+                booking_start_date: old.booking_start_date,
+                active_start_date: old.active_start_date,
+                state: old.state,
+                bond_debit: old.bond_debit,
+                bond_credit: old.bond_credit,
+                coupon_yield: old.coupon_yield,
+                nonce: old.nonce,
+            }
+        }
+    }
+
+    /// Mint or burn request of EverUSD, one per account
+    #[derive(Decode)]
+    pub struct OldTokenRequestStruct<Moment> {
+        #[codec(compact)]
+        pub amount: EverUSDBalance,
+        #[codec(compact)]
+        pub deadline: Moment,
+    }
+
+    impl From<OldBondImpactReportStruct> for BondImpactReportStruct {
+        fn from(old: OldBondImpactReportStruct) -> Self {
+            BondImpactReportStruct {
+                create_period: old.create_period,
+                impact_data: old.impact_data,
+                kpi_data: Vec::new(),
+                signed: old.signed,
+                rejection: None,
+            }
+        }
+    }
+
+    /// Translates bonds, impact reports and sale lots, assigning ids to the lots,
+    /// fills AccountBondRoleIndex and AccountBondIndex, moves EverUSD mint and burn requests
+    /// to the maps keyed by request id and seeds confirmed mint and burn counters
+    pub fn migrate<T: Config>() -> Weight {
+        let mut translated: u64 = 0;
+
+        BondRegistry::<T>::translate::<OldBondStruct<T::AccountId, T::Moment, T::Hash>, _>(|bond, old| {
+            let item: BondStruct<T::AccountId, T::Moment, T::Hash> = old.into();
+            for account in item.role_accounts() {
+                AccountBondRoleIndex::<T>::insert(account, bond, ());
+                translated += 1;
+            }
+            translated += 1;
+            Some(item)
+        });
+
+        BondImpactReport::<T>::translate_values::<Vec<OldBondImpactReportStruct>, _>(|old| {
+            translated += 1;
+            Some(old.into_iter().map(Into::into).collect())
+        });
+
+        let mut last_lot_id = LastBondUnitSaleLotId::<T>::get();
+        BondUnitPackageLot::<T>::translate_values::<Vec<OldBondUnitSaleLotStruct<T::AccountId, T::Moment>>, _>(
+            |old| {
+                translated += 1;
+                Some(
+                    old.into_iter()
+                        .map(|lot| {
+                            last_lot_id += 1;
+                            BondUnitSaleLotStruct {
+                                id: last_lot_id,
+                                deadline: lot.deadline,
+                                new_bondholder: lot.new_bondholder,
+                                bond_units: lot.bond_units,
+                                amount: lot.amount,
+                            }
+                        })
+                        .collect(),
+                )
+            },
+        );
+        LastBondUnitSaleLotId::<T>::put(last_lot_id);
+
+        // purchase bids and bookbuilding orders didn't exist before version 1,
+        // so bond units and sale lots are the only holdings to be indexed
+        for (bond, account, packages) in BondUnitPackageRegistry::<T>::iter() {
+            translated += 1;
+            if !packages.is_empty() {
+                AccountBondIndex::<T>::insert(&account, bond, ());
+            }
+        }
+        for (bond, account, lots) in BondUnitPackageLot::<T>::iter() {
+            translated += 1;
+            if !lots.is_empty() {
+                AccountBondIndex::<T>::insert(&account, bond, ());
+            }
+        }
+
+        // old requests are keyed by account only, so they can't be decoded as the entries
+        // of the new maps sharing their prefix. Drain them and insert back with new ids
+        let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+        let mint_requests: Vec<_> = storage_key_iter::<T::AccountId, OldTokenRequestStruct<T::Moment>, Blake2_128Concat>(
+            pallet,
+            b"MintRequestEverUSD",
+        )
+        .drain()
+        .collect();
+        let mut last_mint_id = LastTokenMintRequestId::<T>::get();
+        for (account, old) in mint_requests {
+            translated += 1;
+            if old.amount == 0 {
+                continue;
+            }
+            last_mint_id += 1;
+            MintRequestEverUSD::<T>::insert(&account, last_mint_id, TokenMintRequestStruct {
+                id: last_mint_id,
+                amount: old.amount,
+                confirmed: 0,
+                deadline: old.deadline,
+                payment_reference: Vec::new(),
+            });
+        }
+        LastTokenMintRequestId::<T>::put(last_mint_id);
+
+        let burn_requests: Vec<_> = storage_key_iter::<T::AccountId, OldTokenRequestStruct<T::Moment>, Blake2_128Concat>(
+            pallet,
+            b"BurnRequestEverUSD",
+        )
+        .drain()
+        .collect();
+        let mut last_burn_id = LastTokenBurnRequestId::<T>::get();
+        for (account, old) in burn_requests {
+            translated += 1;
+            if old.amount == 0 {
+                continue;
+            }
+            last_burn_id += 1;
+            BurnRequestEverUSD::<T>::insert(&account, last_burn_id, TokenBurnRequestStruct {
+                id: last_burn_id,
+                amount: old.amount,
+                confirmed: 0,
+                deadline: old.deadline,
+                payment_reference: Vec::new(),
+            });
+        }
+        LastTokenBurnRequestId::<T>::put(last_burn_id);
+
+        // the counters didn't exist before version 1, so the whole supply is treated
+        // as confirmed mints to keep the reconciliation consistent
+        ConfirmedMintEverUSD::<T>::put(TotalSupplyEverUSD::<T>::get());
+        ConfirmedBurnEverUSD::<T>::put(0);
+        translated += 3;
+
+        T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+    }
+}
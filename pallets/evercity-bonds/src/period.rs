@@ -1,4 +1,4 @@
-use crate::bond::{BondInterest, BondPeriod, BondPeriodNumber, BondStruct};
+use crate::bond::{BondId, BondInterest, BondPeriod, BondPeriodNumber, BondStruct};
 use crate::bond::EverUSDBalance;
 use frame_support::{
     codec::{Decode, Encode},
@@ -39,9 +39,11 @@ impl PeriodDescr {
 }
 
 /// Struct, storing per-period coupon_yield and effective interest_rate for given bond
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct PeriodYield {
-    /// bond cumulative accrued yield for this period
+    /// bond cumulative accrued yield for this period,
+    /// including principal, repaid according to "principal_schedule"
     #[codec(compact)]
     pub total_yield: EverUSDBalance,
     // obsolete
@@ -61,6 +63,42 @@ pub struct PeriodDataStruct {
     pub signed: bool,
 }
 
+/// Kind of the bond deadline
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, RuntimeDebug, TypeInfo)]
+#[allow(non_camel_case_types)]
+pub enum BondDeadlineKind {
+    /// Issuer or ImpactReporter sends impact data of the period
+    IMPACT_REPORT,
+    /// Auditor approves impact data of the period
+    IMPACT_REPORT_APPROVAL,
+    /// Issuer deposits coupon yield, accrued before the period
+    COUPON_PAYMENT,
+    /// Maturity date of the bond
+    MATURITY,
+    /// Issuer redeems the bond, otherwise it becomes bankrupt
+    FINISHING_PERIOD_END,
+}
+
+/// Upcoming deadline of the bond, used by BondApi::get_bond_calendar
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BondDeadline<Moment> {
+    pub bond: BondId,
+    pub kind: BondDeadlineKind,
+    /// Period number, the deadline belongs to
+    #[codec(compact)]
+    pub period: BondPeriodNumber,
+    /// Moment, when the action becomes available
+    #[codec(compact)]
+    pub opens: Moment,
+    /// Moment, when the action is due
+    #[codec(compact)]
+    pub deadline: Moment,
+}
+
+pub type BondDeadlineOf<T> = BondDeadline<<T as pallet_timestamp::Config>::Moment>;
+
 pub struct PeriodIterator<'a, AccountId, Moment, Hash> {
     bond: &'a BondStruct<AccountId, Moment, Hash>,
     index: BondPeriodNumber,
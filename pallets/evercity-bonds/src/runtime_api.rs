@@ -1,8 +1,54 @@
+use codec::Codec;
 use frame_support::dispatch::Vec;
+use crate::{
+    bond::{
+        BondImpactReportHistoryItem, BondInterest, BondPeriodNumber, BondPortfolioStruct, BondProceedsReport, BondStruct, BondUnitAmount,
+        BondUnitPackage, BondUnitPriceStruct, BondUnitPurchaseBidStruct, BondUnitSaleLotStruct, EverUSDBalance,
+    },
+    token::{EverUSDReconciliationStruct, ReserveAttestationStruct},
+    BondDeadline, BondId, PeriodDataStruct, PeriodYield,
+};
 
 sp_api::decl_runtime_apis! {
-    pub trait BondApi {
+    /// Version 2: trait is generic over AccountId, Moment and Hash,
+    /// bond, period and account queries are added
+    #[api_version(2)]
+    pub trait BondApi<AccountId, Moment, Hash> where
+        AccountId: Codec,
+        Moment: Codec,
+        Hash: Codec,
+    {
         /// delegate call to the pallet get_impact_reports()
-        fn get_impact_reports(bond: crate::BondId)->Vec<crate::PeriodDataStruct>;
+        fn get_impact_reports(bond: BondId)->Vec<PeriodDataStruct>;
+        /// returns bond structure from BondRegistry
+        fn get_bond(bond: BondId)->Option<BondStruct<AccountId, Moment, Hash>>;
+        /// delegate call to the pallet get_coupon_yields()
+        fn get_coupon_yields(bond: BondId)->Vec<PeriodYield>;
+        /// returns bond unit packages owned by the bondholder
+        fn get_bond_unit_packages(bond: BondId, bondholder: AccountId)->Vec<BondUnitPackage>;
+        /// returns sale lots of the bond grouped by the seller
+        fn get_bond_unit_lots(bond: BondId)->Vec<(AccountId, Vec<BondUnitSaleLotStruct<AccountId, Moment>>)>;
+        /// returns purchase bids of the bond grouped by the bidder
+        fn get_bond_unit_bids(bond: BondId)->Vec<(AccountId, Vec<BondUnitPurchaseBidStruct<Moment>>)>;
+        /// returns sent, approved and rejected impact reports of the bond period
+        fn get_impact_report_history(bond: BondId, period: BondPeriodNumber)->Vec<BondImpactReportHistoryItem<AccountId, Moment>>;
+        /// delegate call to the pallet get_proceeds_report()
+        fn get_proceeds_report(bond: BondId)->BondProceedsReport<Moment>;
+        /// returns upcoming deadlines of the bond
+        fn get_bond_calendar(bond: BondId)->Vec<BondDeadline<Moment>>;
+        /// returns upcoming deadlines of all bonds, the account participates in
+        fn get_account_calendar(account: AccountId)->Vec<BondDeadline<Moment>>;
+        /// delegate call to the pallet get_bond_unit_price()
+        fn get_bond_unit_price(bond: BondId, bond_units: BondUnitAmount)->Option<BondUnitPriceStruct>;
+        /// delegate call to the pallet get_yield_to_maturity()
+        fn get_yield_to_maturity(bond: BondId, bond_units: BondUnitAmount, price: EverUSDBalance)->Option<BondInterest>;
+        /// delegate call to the pallet get_implied_yield()
+        fn get_implied_yield(bond: BondId)->Option<BondInterest>;
+        /// delegate call to the pallet get_portfolio()
+        fn get_portfolio(account: AccountId)->BondPortfolioStruct<AccountId, Moment>;
+        /// delegate call to the pallet get_everusd_reconciliation()
+        fn get_everusd_reconciliation()->EverUSDReconciliationStruct;
+        /// delegate call to the pallet get_latest_reserve_attestation()
+        fn get_latest_reserve_attestation()->Option<ReserveAttestationStruct<AccountId, Moment>>;
     }
 }
@@ -2,15 +2,22 @@
 use frame_support::{
     assert_noop, assert_ok,
     Blake2_256, StorageHasher,
+    weights::Weight,
 };
 
 use crate::tests::mock::*;
+use crate::bond::{
+    BondCallSchedule, BondImpactKpi, BondImpactReportAction, BondImpactReportRejection, BondImpactType,
+    BondAllocationRule, BondFeeStruct, BondInterest, BondInterestRateCurve, BondInterestRatePoint, BondRestructuring,
+    BondProceedsCategory, BondProceedsCategoryStruct, BondTransferRestrictionStruct, BondUnitPriceStruct,
+};
 use crate::{
-    BondId, BondImpactReportStruct, BondPeriodNumber, BondState, BondStructOf,
+    BondDeadlineKind, BondId, BondImpactReportStruct, BondPeriodNumber, BondState, BondStructOf,
     BondUnitAmount, EverUSDBalance,
     DEFAULT_DAY_DURATION,
 };
 use super::helpers::*;
+use pallet_evercity_accounts::accounts::INVESTOR_ROLE_MASK;
 
 #[test]
 fn bond_transfer_units() {
@@ -387,48 +394,64 @@ fn bond_period_interest_rate() {
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 20000_u64,
+                kpi_data: Vec::new(),
                 signed: true,
+                rejection: None,
             },
             //missing report
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
             // worst result and maximal interest rate value
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 14000_u64,
+                kpi_data: Vec::new(),
                 signed: true,
+                rejection: None,
             },
             //missing report. it cannot make interest rate worse
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
             // very good result lead to mininal interest rate
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 100000_u64,
+                kpi_data: Vec::new(),
                 signed: true,
+                rejection: None,
             },
             //first missing report.
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
             //second missing report.
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
         ];
 
@@ -792,48 +815,64 @@ fn bond_stable_calc_bond_interest_rate() {
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
             //missing report
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
             // worst result and maximal interest rate value
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
             //missing report. it cannot make interest rate worse
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
             // very good result lead to mininal interest rate
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
             //first missing report.
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
             //second missing report.
             BondImpactReportStruct {
                 create_period: 0,
                 impact_data: 0,
+                kpi_data: Vec::new(),
                 signed: false,
+                rejection: None,
             },
         ];
 
@@ -884,6 +923,82 @@ fn bond_try_withdraw_before_deadline() {
     });
 }
 
+#[test]
+fn bond_auto_withdraw_after_deadline() {
+    use frame_support::traits::Hooks;
+    let mut bond = get_test_bond();
+    let bondid: BondId = "BOND".into();
+    const BOND_ARRANGER: u64 = 9;
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(add_token(INVESTOR1, 50_000_000_000_000_000));
+
+        bond.inner.mincap_deadline = 50000;
+        assert_ok!(Evercity::bond_add_new(
+            Origin::signed(ACCOUNT),
+            bondid,
+            bond.inner
+        ));
+        assert_ok!(Evercity::bond_release(Origin::signed(BOND_ARRANGER), bondid, 0));
+        assert_ok!(Evercity::bond_unit_package_buy(
+            Origin::signed(INVESTOR1),
+            bondid,
+            1,
+            100
+        ));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), 50_000_000_000_000_000 - 100 * 4_000_000_000_000);
+
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(49000);
+        Evercity::on_idle(1, Weight::MAX);
+        assert_eq!(Evercity::get_bond(&bondid).state, BondState::BOOKING);
+
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(51000);
+        // not enough weight to process the bond
+        assert_eq!(Evercity::on_idle(2, 0), 0);
+        assert_eq!(Evercity::get_bond(&bondid).state, BondState::BOOKING);
+
+        assert!(Evercity::on_idle(3, Weight::MAX) > 0);
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.state, BondState::PREPARE);
+        assert_eq!(chain_bond_item.bond_credit, 0);
+        assert_eq!(chain_bond_item.issued_amount, 0);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), 50_000_000_000_000_000);
+        assert!(Evercity::bond_unit_registry(&bondid, &INVESTOR1).is_empty());
+    });
+}
+
+#[test]
+fn bond_auto_bankrupt_after_finishing_period() {
+    use frame_support::traits::Hooks;
+    const ACCOUNT: u64 = 3;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        let inner = &chain_bond_item.inner;
+        // maturity + finishing period, seconds
+        let finish = inner.start_period.unwrap_or(0)
+            + inner.bond_duration * inner.payment_period
+            + inner.bond_finishing_period;
+        let finish_moment = chain_bond_item.active_start_date + finish as u64 * 1000;
+
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(finish_moment - 1000);
+        Evercity::on_idle(1, Weight::MAX);
+        assert_eq!(Evercity::get_bond(&bondid).state, BondState::ACTIVE);
+
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(finish_moment);
+        Evercity::on_idle(2, Weight::MAX);
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.state, BondState::BANKRUPT);
+        assert!(chain_bond_item.bond_credit > chain_bond_item.bond_debit);
+        assert_eq!(Evercity::get_coupon_yields(&bondid).len(), chain_bond_item.get_periods() as usize);
+    });
+}
+
 #[test]
 fn bond_try_withdraw_by_investor() {
     let mut bond = get_test_bond();
@@ -2746,6 +2861,110 @@ fn bond_impact_report_try_approve_unauthorized() {
     });
 }
 
+#[test]
+fn bond_impact_report_reject_and_resubmit() {
+    const ACCOUNT1: u64 = 3;
+    const AUDITOR: u64 = 5;
+    let bondid: BondId = "BOND1".into();
+    let reason = b"meter readings don't match".to_vec();
+    let evidence = Some([1_u8; 16]);
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        let bond = get_test_bond().inner;
+        bond_activate(bondid, ACCOUNT1, bond.clone());
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        // first period
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(
+            chain_bond_item.active_start_date + 1000_u64 * (bond.start_period.unwrap_or(0) - 1) as u64,
+        );
+
+        assert_ok!(Evercity::bond_impact_report_send(
+            Origin::signed(ACCOUNT1),
+            bondid,
+            0,
+            1000
+        ));
+
+        for acc in iter_accounts().filter(|acc| *acc != AUDITOR) {
+            assert_noop!(
+                Evercity::bond_impact_report_reject(Origin::signed(acc), bondid, 0, 1000, Vec::new(), reason.clone(), None),
+                RuntimeError::AccountNotAuthorized
+            );
+        }
+        assert_noop!(
+            Evercity::bond_impact_report_reject(Origin::signed(AUDITOR), bondid, 0, 1000, Vec::new(), Vec::new(), None),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_noop!(
+            Evercity::bond_impact_report_reject(Origin::signed(AUDITOR), bondid, 0, 1001, Vec::new(), reason.clone(), None),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_noop!(
+            Evercity::bond_impact_report_reject(Origin::signed(AUDITOR), bondid, 0, 1000, vec![1000], reason.clone(), None),
+            RuntimeError::BondParamIncorrect
+        );
+
+        assert_ok!(Evercity::bond_impact_report_reject(
+            Origin::signed(AUDITOR),
+            bondid,
+            0,
+            1000,
+            Vec::new(),
+            reason.clone(),
+            evidence
+        ));
+        let report = &Evercity::impact_reports(&bondid)[0];
+        assert_eq!(report.create_period, 0);
+        assert!(!report.signed);
+        let rejection = BondImpactReportRejection {
+            impact_data: 1000,
+            reason: reason.clone(),
+            evidence,
+        };
+        assert_eq!(report.rejection, Some(rejection.clone()));
+
+        // rejected report cannot be approved
+        assert_noop!(
+            Evercity::bond_impact_report_approve(Origin::signed(AUDITOR), bondid, 0, 1000),
+            RuntimeError::BondParamIncorrect
+        );
+
+        // resubmit
+        assert_ok!(Evercity::bond_impact_report_send(
+            Origin::signed(ACCOUNT1),
+            bondid,
+            0,
+            1200
+        ));
+        assert_ok!(Evercity::bond_impact_report_approve(
+            Origin::signed(AUDITOR),
+            bondid,
+            0,
+            1200
+        ));
+        let report = &Evercity::impact_reports(&bondid)[0];
+        assert!(report.signed);
+        assert_eq!(report.impact_data, 1200);
+
+        let history: Vec<_> = Evercity::impact_report_history(&bondid, 0)
+            .into_iter()
+            .map(|item| (item.account, item.impact_data, item.action))
+            .collect();
+        assert_eq!(
+            history,
+            vec![
+                (ACCOUNT1, 1000, BondImpactReportAction::SENT),
+                (AUDITOR, 1000, BondImpactReportAction::REJECTED(rejection)),
+                (ACCOUNT1, 1200, BondImpactReportAction::SENT),
+                (AUDITOR, 1200, BondImpactReportAction::APPROVED),
+            ]
+        );
+        assert!(Evercity::impact_report_history(&bondid, 1).is_empty());
+    });
+}
+
 #[test]
 fn bond_impact_report_try_approve_unattended() {
     const ACCOUNT1: u64 = 3;
@@ -3075,6 +3294,7 @@ fn bond_lot_bit_n_buy() {
         assert!(Evercity::bond_check_invariant(&bondid));
 
         let lot = BondUnitSaleLotStruct {
+            id: 0,
             deadline: 100000,
             new_bondholder: Default::default(),
             bond_units: 600,
@@ -3084,13 +3304,18 @@ fn bond_lot_bit_n_buy() {
         assert_ok!(Evercity::bond_unit_lot_bid(
             Origin::signed(INVESTOR1),
             bondid,
-            lot.clone()
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
         ));
         assert_ok!(Evercity::bond_unit_lot_settle(
             Origin::signed(INVESTOR2),
             bondid,
             INVESTOR1,
-            lot
+            Evercity::last_bond_unit_lot_id(),
+            lot.bond_units,
+            lot.amount
         ));
         assert!(Evercity::bond_check_invariant(&bondid));
         assert!(Evercity::evercity_balance().is_ok());
@@ -3141,6 +3366,7 @@ fn bond_lot_paid_coupon() {
         assert_eq!(period, 1);
 
         let lot = BondUnitSaleLotStruct {
+            id: 0,
             deadline: moment + 1,
             new_bondholder: Default::default(),
             bond_units: 400,
@@ -3160,13 +3386,18 @@ fn bond_lot_paid_coupon() {
         assert_ok!(Evercity::bond_unit_lot_bid(
             Origin::signed(INVESTOR1),
             bondid,
-            lot.clone()
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
         ));
         assert_ok!(Evercity::bond_unit_lot_settle(
             Origin::signed(INVESTOR2),
             bondid,
             INVESTOR1,
-            lot
+            Evercity::last_bond_unit_lot_id(),
+            lot.bond_units,
+            lot.amount
         ));
         assert!(Evercity::evercity_balance().is_ok());
 
@@ -3206,6 +3437,7 @@ fn bond_lot_try_buy_foreign() {
         bond_activate(bondid, ACCOUNT, bond);
 
         let lot = BondUnitSaleLotStruct {
+            id: 0,
             deadline: 100000,
             new_bondholder: Some(7),
             bond_units: 600,
@@ -3215,15 +3447,58 @@ fn bond_lot_try_buy_foreign() {
         assert_ok!(Evercity::bond_unit_lot_bid(
             Origin::signed(INVESTOR1),
             bondid,
-            lot.clone()
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
         ));
         assert_noop!(
-            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR2), bondid, INVESTOR1, lot),
+            Evercity::bond_unit_lot_settle(
+                Origin::signed(INVESTOR2),
+                bondid,
+                INVESTOR1,
+                Evercity::last_bond_unit_lot_id(),
+                lot.bond_units,
+                lot.amount
+            ),
             RuntimeError::LotNotFound
         );
     });
 }
 
+#[test]
+fn bond_lot_try_buy_own() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            None,
+            600,
+            600 * 3_000_000_000_000,
+            100000
+        ));
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(
+                Origin::signed(INVESTOR1),
+                bondid,
+                INVESTOR1,
+                Evercity::last_bond_unit_lot_id(),
+                600,
+                600 * 3_000_000_000_000
+            ),
+            RuntimeError::LotParamIncorrect
+        );
+    });
+}
+
 #[test]
 fn bond_lot_try_create_expired() {
     const ACCOUNT: u64 = 3;
@@ -3236,6 +3511,7 @@ fn bond_lot_try_create_expired() {
         bond_activate(bondid, ACCOUNT, bond);
 
         let lot = BondUnitSaleLotStruct {
+            id: 0,
             deadline: 100000,
             new_bondholder: Default::default(),
             bond_units: 600,
@@ -3244,7 +3520,14 @@ fn bond_lot_try_create_expired() {
         // move forward
         <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(1000000 + 1);
         assert_noop!(
-            Evercity::bond_unit_lot_bid(Origin::signed(INVESTOR1), bondid, lot),
+            Evercity::bond_unit_lot_bid(
+                Origin::signed(INVESTOR1),
+                bondid,
+                lot.new_bondholder,
+                lot.bond_units,
+                lot.amount,
+                lot.deadline
+            ),
             RuntimeError::LotParamIncorrect
         );
     });
@@ -3263,6 +3546,7 @@ fn bond_lot_try_buy_expired() {
         bond_activate(bondid, ACCOUNT, bond);
 
         let lot = BondUnitSaleLotStruct {
+            id: 0,
             deadline: 100000,
             new_bondholder: Default::default(),
             bond_units: 600,
@@ -3272,14 +3556,24 @@ fn bond_lot_try_buy_expired() {
         assert_ok!(Evercity::bond_unit_lot_bid(
             Origin::signed(INVESTOR1),
             bondid,
-            lot.clone()
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
         ));
 
         // move forward
         <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(1000000 + 1);
 
         assert_noop!(
-            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR2), bondid, INVESTOR1, lot),
+            Evercity::bond_unit_lot_settle(
+                Origin::signed(INVESTOR2),
+                bondid,
+                INVESTOR1,
+                Evercity::last_bond_unit_lot_id(),
+                lot.bond_units,
+                lot.amount
+            ),
             RuntimeError::LotObsolete
         );
     });
@@ -3297,6 +3591,7 @@ fn bond_lot_try_exceed_portfolio() {
         bond_activate(bondid, ACCOUNT, bond);
 
         let lot = BondUnitSaleLotStruct {
+            id: 0,
             deadline: 100000,
             new_bondholder: Default::default(),
             bond_units: 500,
@@ -3306,10 +3601,20 @@ fn bond_lot_try_exceed_portfolio() {
         assert_ok!(Evercity::bond_unit_lot_bid(
             Origin::signed(INVESTOR1),
             bondid,
-            lot.clone()
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
         ));
         assert_noop!(
-            Evercity::bond_unit_lot_bid(Origin::signed(INVESTOR1), bondid, lot.clone()),
+            Evercity::bond_unit_lot_bid(
+                Origin::signed(INVESTOR1),
+                bondid,
+                lot.new_bondholder,
+                lot.bond_units,
+                lot.amount,
+                lot.deadline
+            ),
             RuntimeError::BalanceOverdraft
         );
         // make amend. make prior lots expired
@@ -3319,7 +3624,2222 @@ fn bond_lot_try_exceed_portfolio() {
         assert_ok!(Evercity::bond_unit_lot_bid(
             Origin::signed(INVESTOR1),
             bondid,
-            lot
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
+        ));
+    });
+}
+#[test]
+fn bond_lot_identical_lots_get_unique_ids() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: 100000,
+            new_bondholder: Default::default(),
+            bond_units: 300,
+            amount: 300 * 3_000_000_000_000,
+        };
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
+        ));
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
+        ));
+
+        let ids: Vec<_> = Evercity::bond_unit_lots(&bondid, &INVESTOR1).iter().map(|l| l.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR2),
+            bondid,
+            INVESTOR1,
+            2,
+            lot.bond_units,
+            lot.amount
+        ));
+        let ids: Vec<_> = Evercity::bond_unit_lots(&bondid, &INVESTOR1).iter().map(|l| l.id).collect();
+        assert_eq!(ids, vec![1]);
+        assert!(Evercity::bond_check_invariant(&bondid));
+    });
+}
+
+#[test]
+fn bond_lot_partial_settle() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: 100000,
+            new_bondholder: Default::default(),
+            bond_units: 600,
+            amount: 600 * 3_000_000_000_000,
+        };
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
+        ));
+        let lot_id = Evercity::last_bond_unit_lot_id();
+
+        // price doesn't match the share of the lot
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(
+                Origin::signed(INVESTOR2),
+                bondid,
+                INVESTOR1,
+                lot_id,
+                200,
+                300 * 3_000_000_000_000
+            ),
+            RuntimeError::LotParamIncorrect
+        );
+        // more than the lot contains
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(
+                Origin::signed(INVESTOR2),
+                bondid,
+                INVESTOR1,
+                lot_id,
+                601,
+                601 * 3_000_000_000_000
+            ),
+            RuntimeError::LotParamIncorrect
+        );
+
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR2),
+            bondid,
+            INVESTOR1,
+            lot_id,
+            200,
+            200 * 3_000_000_000_000
+        ));
+        assert!(Evercity::bond_check_invariant(&bondid));
+        assert!(Evercity::evercity_balance().is_ok());
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1) - balance1, 200 * 3_000_000_000_000);
+        assert_eq!(balance2 - Evercity::balance_everusd(&INVESTOR2), 200 * 3_000_000_000_000);
+
+        let lots = Evercity::bond_unit_lots(&bondid, &INVESTOR1);
+        assert_eq!(lots.len(), 1);
+        assert_eq!(lots[0].id, lot_id);
+        assert_eq!(lots[0].bond_units, 400);
+        assert_eq!(lots[0].amount, 400 * 3_000_000_000_000);
+
+        // buy the rest of the lot
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR2),
+            bondid,
+            INVESTOR1,
+            lot_id,
+            400,
+            400 * 3_000_000_000_000
         ));
+        assert!(Evercity::bond_unit_lots(&bondid, &INVESTOR1).is_empty());
+
+        let bond_units1: BondUnitAmount = Evercity::bond_holder_packages(&bondid, &INVESTOR1).iter().map(|p| p.bond_units).sum();
+        let bond_units2: BondUnitAmount = Evercity::bond_holder_packages(&bondid, &INVESTOR2).iter().map(|p| p.bond_units).sum();
+        assert_eq!(bond_units1, 0);
+        assert_eq!(bond_units2, 1200);
     });
-}
\ No newline at end of file
+}
+
+#[test]
+fn bond_lot_cancel() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: 100000,
+            new_bondholder: Default::default(),
+            bond_units: 600,
+            amount: 600 * 3_000_000_000_000,
+        };
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
+        ));
+        let lot_id = Evercity::last_bond_unit_lot_id();
+
+        // only the seller can cancel the lot
+        assert_noop!(
+            Evercity::bond_unit_lot_cancel(Origin::signed(INVESTOR2), bondid, lot_id),
+            RuntimeError::LotNotFound
+        );
+        // all bond units are already in the lot
+        assert_noop!(
+            Evercity::bond_unit_lot_bid(
+                Origin::signed(INVESTOR1),
+                bondid,
+                lot.new_bondholder,
+                lot.bond_units,
+                lot.amount,
+                lot.deadline
+            ),
+            RuntimeError::BalanceOverdraft
+        );
+
+        assert_ok!(Evercity::bond_unit_lot_cancel(Origin::signed(INVESTOR1), bondid, lot_id));
+        assert!(Evercity::bond_unit_lots(&bondid, &INVESTOR1).is_empty());
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(
+                Origin::signed(INVESTOR2),
+                bondid,
+                INVESTOR1,
+                lot_id,
+                lot.bond_units,
+                lot.amount
+            ),
+            RuntimeError::LotNotFound
+        );
+        assert_noop!(
+            Evercity::bond_unit_lot_cancel(Origin::signed(INVESTOR1), bondid, lot_id),
+            RuntimeError::LotNotFound
+        );
+        // bond units are released for the new lot
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
+        ));
+    });
+}
+
+#[test]
+fn bond_lot_update_price() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: 100000,
+            new_bondholder: Default::default(),
+            bond_units: 600,
+            amount: 600 * 3_000_000_000_000,
+        };
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
+        ));
+        let lot_id = Evercity::last_bond_unit_lot_id();
+
+        assert_noop!(
+            Evercity::bond_unit_lot_update_price(Origin::signed(INVESTOR2), bondid, lot_id, 600 * 2_000_000_000_000),
+            RuntimeError::LotNotFound
+        );
+        assert_ok!(Evercity::bond_unit_lot_update_price(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot_id,
+            600 * 2_000_000_000_000
+        ));
+        assert_eq!(Evercity::bond_unit_lots(&bondid, &INVESTOR1)[0].amount, 600 * 2_000_000_000_000);
+
+        // buyer expects the old price
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(
+                Origin::signed(INVESTOR2),
+                bondid,
+                INVESTOR1,
+                lot_id,
+                lot.bond_units,
+                lot.amount
+            ),
+            RuntimeError::LotParamIncorrect
+        );
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR2),
+            bondid,
+            INVESTOR1,
+            lot_id,
+            lot.bond_units,
+            600 * 2_000_000_000_000
+        ));
+
+        // expired lot cannot be amended
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR2),
+            bondid,
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
+        ));
+        let lot_id = Evercity::last_bond_unit_lot_id();
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(100000 + 1);
+        assert_noop!(
+            Evercity::bond_unit_lot_update_price(Origin::signed(INVESTOR2), bondid, lot_id, 1),
+            RuntimeError::LotObsolete
+        );
+    });
+}
+
+#[test]
+fn bond_purchase_bid_partial_settle() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        let bid = BondUnitPurchaseBidStruct {
+            id: 0,
+            deadline: 100000,
+            bond_units: 300,
+            amount: 300 * 3_000_000_000_000,
+        };
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_unit_purchase_bid(Origin::signed(INVESTOR2), bondid, bid));
+        let bid_id = Evercity::last_bond_unit_bid_id();
+        assert_eq!(bid_id, 1);
+        // tokens are reserved
+        assert_eq!(Evercity::reserved_everusd(&INVESTOR2), 300 * 3_000_000_000_000);
+        assert_eq!(balance2 - Evercity::balance_everusd(&INVESTOR2), 300 * 3_000_000_000_000);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        // bidder cannot fill own bid
+        assert_noop!(
+            Evercity::bond_unit_purchase_bid_settle(
+                Origin::signed(INVESTOR2),
+                bondid,
+                INVESTOR2,
+                bid_id,
+                100,
+                100 * 3_000_000_000_000
+            ),
+            RuntimeError::BidParamIncorrect
+        );
+        // price doesn't match the share of the bid
+        assert_noop!(
+            Evercity::bond_unit_purchase_bid_settle(
+                Origin::signed(INVESTOR1),
+                bondid,
+                INVESTOR2,
+                bid_id,
+                100,
+                200 * 3_000_000_000_000
+            ),
+            RuntimeError::BidParamIncorrect
+        );
+
+        assert_ok!(Evercity::bond_unit_purchase_bid_settle(
+            Origin::signed(INVESTOR1),
+            bondid,
+            INVESTOR2,
+            bid_id,
+            100,
+            100 * 3_000_000_000_000
+        ));
+        assert!(Evercity::bond_check_invariant(&bondid));
+        assert!(Evercity::evercity_balance().is_ok());
+        assert_eq!(Evercity::reserved_everusd(&INVESTOR2), 200 * 3_000_000_000_000);
+        let bids = Evercity::bond_unit_bids(&bondid, &INVESTOR2);
+        assert_eq!(bids.len(), 1);
+        assert_eq!(bids[0].bond_units, 200);
+        assert_eq!(bids[0].amount, 200 * 3_000_000_000_000);
+
+        assert_ok!(Evercity::bond_unit_purchase_bid_settle(
+            Origin::signed(INVESTOR1),
+            bondid,
+            INVESTOR2,
+            bid_id,
+            200,
+            200 * 3_000_000_000_000
+        ));
+        assert!(Evercity::bond_check_invariant(&bondid));
+        assert!(Evercity::evercity_balance().is_ok());
+        assert!(Evercity::bond_unit_bids(&bondid, &INVESTOR2).is_empty());
+        assert_eq!(Evercity::reserved_everusd(&INVESTOR2), 0);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1) - balance1, 300 * 3_000_000_000_000);
+        assert_eq!(balance2 - Evercity::balance_everusd(&INVESTOR2), 300 * 3_000_000_000_000);
+
+        let bond_units1: BondUnitAmount = Evercity::bond_holder_packages(&bondid, &INVESTOR1).iter().map(|p| p.bond_units).sum();
+        let bond_units2: BondUnitAmount = Evercity::bond_holder_packages(&bondid, &INVESTOR2).iter().map(|p| p.bond_units).sum();
+        assert_eq!(bond_units1, 300);
+        assert_eq!(bond_units2, 900);
+    });
+}
+
+#[test]
+fn bond_purchase_bid_cancel() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        let bid = BondUnitPurchaseBidStruct {
+            id: 0,
+            deadline: 100000,
+            bond_units: 300,
+            amount: 300 * 3_000_000_000_000,
+        };
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_unit_purchase_bid(Origin::signed(INVESTOR2), bondid, bid));
+        let bid_id = Evercity::last_bond_unit_bid_id();
+
+        assert_noop!(
+            Evercity::bond_unit_purchase_bid_cancel(Origin::signed(INVESTOR1), bondid, bid_id),
+            RuntimeError::BidNotFound
+        );
+        assert_ok!(Evercity::bond_unit_purchase_bid_cancel(Origin::signed(INVESTOR2), bondid, bid_id));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), balance2);
+        assert_eq!(Evercity::reserved_everusd(&INVESTOR2), 0);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        assert_noop!(
+            Evercity::bond_unit_purchase_bid_settle(
+                Origin::signed(INVESTOR1),
+                bondid,
+                INVESTOR2,
+                bid_id,
+                300,
+                300 * 3_000_000_000_000
+            ),
+            RuntimeError::BidNotFound
+        );
+    });
+}
+
+#[test]
+fn bond_purchase_bid_try_invalid() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+
+        let bid = BondUnitPurchaseBidStruct {
+            id: 0,
+            deadline: 100000,
+            bond_units: 300,
+            amount: 300 * 3_000_000_000_000,
+        };
+        // bond is not active yet
+        let bondid2: BondId = "BOND2".into();
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid2, bond.clone()));
+        assert_noop!(
+            Evercity::bond_unit_purchase_bid(Origin::signed(INVESTOR2), bondid2, bid.clone()),
+            RuntimeError::BondStateNotPermitAction
+        );
+        bond_activate(bondid, ACCOUNT, bond);
+
+        // issuer is not an investor
+        assert_noop!(
+            Evercity::bond_unit_purchase_bid(Origin::signed(ACCOUNT), bondid, bid.clone()),
+            RuntimeError::AccountNotAuthorized
+        );
+        // bidder doesn't have enough tokens
+        let mut expensive = bid.clone();
+        expensive.amount = Evercity::balance_everusd(&INVESTOR2) + 1;
+        assert_noop!(
+            Evercity::bond_unit_purchase_bid(Origin::signed(INVESTOR2), bondid, expensive),
+            RuntimeError::BalanceOverdraft
+        );
+
+        assert_ok!(Evercity::bond_unit_purchase_bid(Origin::signed(INVESTOR2), bondid, bid));
+        let bid_id = Evercity::last_bond_unit_bid_id();
+
+        // bond units of the seller are offered in the sale lot
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: 100000,
+            new_bondholder: Default::default(),
+            bond_units: 400,
+            amount: 400 * 3_000_000_000_000,
+        };
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
+        ));
+        assert_noop!(
+            Evercity::bond_unit_purchase_bid_settle(
+                Origin::signed(INVESTOR1),
+                bondid,
+                INVESTOR2,
+                bid_id,
+                300,
+                300 * 3_000_000_000_000
+            ),
+            RuntimeError::BalanceOverdraft
+        );
+        assert_ok!(Evercity::bond_unit_purchase_bid_settle(
+            Origin::signed(INVESTOR1),
+            bondid,
+            INVESTOR2,
+            bid_id,
+            200,
+            200 * 3_000_000_000_000
+        ));
+
+        // move forward
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(100000 + 1);
+        assert_noop!(
+            Evercity::bond_unit_purchase_bid_settle(
+                Origin::signed(INVESTOR1),
+                bondid,
+                INVESTOR2,
+                bid_id,
+                100,
+                100 * 3_000_000_000_000
+            ),
+            RuntimeError::BidObsolete
+        );
+    });
+}
+
+#[test]
+fn bond_purchase_bid_expired_released_on_initialize() {
+    use frame_support::traits::Hooks;
+    const ACCOUNT: u64 = 3;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        let bid = BondUnitPurchaseBidStruct {
+            id: 0,
+            deadline: 100000,
+            bond_units: 300,
+            amount: 300 * 3_000_000_000_000,
+        };
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_unit_purchase_bid(Origin::signed(INVESTOR2), bondid, bid));
+
+        // bid is alive
+        Evercity::on_initialize(1);
+        assert_eq!(Evercity::bond_unit_bids(&bondid, &INVESTOR2).len(), 1);
+
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(100000 + 1);
+        Evercity::on_initialize(2);
+        assert!(Evercity::bond_unit_bids(&bondid, &INVESTOR2).is_empty());
+        assert_eq!(Evercity::reserved_everusd(&INVESTOR2), 0);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), balance2);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_call_pays_par_and_premium() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let mut bond = get_test_bond().inner;
+        bond.call_schedule = Some(BondCallSchedule {
+            earliest_call_period: 3,
+            call_premium: 20_000, // 2%
+        });
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+        assert_ok!(add_token(ACCOUNT, 1_000_000_000_000_000));
+
+        // the third period hasn't come yet
+        let start = Evercity::get_bond(&bondid).active_start_date;
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(120 + 30 + 1));
+        assert_noop!(
+            Evercity::bond_call(Origin::signed(ACCOUNT), bondid, 3),
+            RuntimeError::BondOutOfOrder
+        );
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(120 + 30 * 3 + 1));
+        assert_noop!(
+            Evercity::bond_call(Origin::signed(INVESTOR1), bondid, 3),
+            RuntimeError::BondAccessDenied
+        );
+
+        // bidder's reserve is returned when the bond is called
+        let bid = BondUnitPurchaseBidStruct {
+            id: 0,
+            deadline: start + days2timestamp(365),
+            bond_units: 100,
+            amount: 100 * 4_000_000_000_000,
+        };
+        assert_ok!(Evercity::bond_unit_purchase_bid(Origin::signed(INVESTOR2), bondid, bid));
+
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+        // two bondholders and one bidder
+        assert_noop!(
+            Evercity::bond_call(Origin::signed(ACCOUNT), bondid, 2),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_ok!(Evercity::bond_call(Origin::signed(ACCOUNT), bondid, 3));
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.state, BondState::FINISHED);
+        assert_eq!(chain_bond_item.bond_debit, chain_bond_item.bond_credit);
+        assert!(Evercity::bond_holder_packages(&bondid, &INVESTOR1).is_empty());
+        assert!(Evercity::bond_holder_packages(&bondid, &INVESTOR2).is_empty());
+        assert!(Evercity::bond_unit_bids(&bondid, &INVESTOR2).is_empty());
+        assert_eq!(Evercity::reserved_everusd(&INVESTOR2), 0);
+
+        // par value + 2% premium + coupon yield of three periods
+        let par_and_premium = 600 * (4_000_000_000_000 + 80_000_000_000);
+        assert!(Evercity::balance_everusd(&INVESTOR1) - balance1 > par_and_premium);
+        assert!(Evercity::balance_everusd(&INVESTOR2) - balance2 > par_and_premium + 100 * 4_000_000_000_000);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        assert_noop!(
+            Evercity::bond_call(Origin::signed(ACCOUNT), bondid, 3),
+            RuntimeError::BondStateNotPermitAction
+        );
+    });
+}
+
+#[test]
+fn bond_call_try_invalid() {
+    const ACCOUNT: u64 = 3;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let mut bond = get_test_bond().inner;
+        bond.call_schedule = Some(BondCallSchedule {
+            earliest_call_period: 0,
+            call_premium: 20_000,
+        });
+        assert_noop!(
+            Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, bond.clone()),
+            RuntimeError::BondParamIncorrect
+        );
+        bond.call_schedule = Some(BondCallSchedule {
+            earliest_call_period: 1,
+            call_premium: 1_000_001,
+        });
+        assert_noop!(
+            Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, bond),
+            RuntimeError::BondParamIncorrect
+        );
+
+        // not callable bond
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let start = Evercity::get_bond(&bondid).active_start_date;
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(120 + 30 * 3 + 1));
+        assert_noop!(
+            Evercity::bond_call(Origin::signed(ACCOUNT), bondid, 3),
+            RuntimeError::BondIsNotConfigured
+        );
+    });
+}
+
+#[test]
+fn bond_amortizing_principal_repaid_with_coupon() {
+    const ACCOUNT1: u64 = 3;
+    const ACCOUNT2: u64 = 7;
+    const INVESTOR1: u64 = 4;
+    const BOND_ARRANGER: u64 = 9;
+    // 5% of base price is repaid every period
+    const UNIT_PRINCIPAL: EverUSDBalance = 200_000_000_000;
+    let bondid1: BondId = "BOND1".into();
+    let bondid2: BondId = "BOND2".into();
+
+    new_test_ext().execute_with(|| {
+        let mut bond = get_test_bond().inner;
+        bond.principal_schedule = Some(vec![50_000; 12]);
+        bond_grand_everusd();
+        bond_activate(bondid1, ACCOUNT1, get_test_bond().inner);
+        bond_activate(bondid2, ACCOUNT2, bond);
+
+        let mut chain_bond_item1 = Evercity::get_bond(&bondid1);
+        let mut chain_bond_item2 = Evercity::get_bond(&bondid2);
+        assert_eq!(chain_bond_item2.unit_par_value(1), 4_000_000_000_000);
+        assert_eq!(chain_bond_item2.unit_par_value(2), 4_000_000_000_000 - UNIT_PRINCIPAL);
+        assert_eq!(chain_bond_item2.unit_par_value(13), 4_000_000_000_000 - 12 * UNIT_PRINCIPAL);
+
+        // the second period has started, interest pay period is over
+        let now = chain_bond_item2.active_start_date + days2timestamp(120 + 30 + 8);
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(now);
+
+        Evercity::calc_and_store_bond_coupon_yield(&bondid1, &mut chain_bond_item1, now);
+        Evercity::calc_and_store_bond_coupon_yield(&bondid2, &mut chain_bond_item2, now);
+        // principal of the first period is accrued together with coupon yield
+        assert_eq!(
+            chain_bond_item2.bond_credit - chain_bond_item1.bond_credit,
+            1200 * UNIT_PRINCIPAL
+        );
+
+        // issuer pays off coupon yield only
+        assert_ok!(add_token(ACCOUNT2, chain_bond_item1.bond_credit));
+        assert_ok!(Evercity::bond_deposit_everusd(
+            Origin::signed(ACCOUNT2),
+            bondid2,
+            chain_bond_item1.bond_credit
+        ));
+        let chain_bond_item2 = Evercity::get_bond(&bondid2);
+        assert_eq!(chain_bond_item2.get_debt(), 1200 * UNIT_PRINCIPAL);
+
+        // missed principal payment is a default
+        assert_ok!(Evercity::bond_declare_bankrupt(Origin::signed(BOND_ARRANGER), bondid2));
+        assert_eq!(Evercity::get_bond(&bondid2).state, BondState::BANKRUPT);
+
+        // amortized bond units are sold by outstanding par value
+        let balance = Evercity::balance_everusd(&INVESTOR1);
+        assert_ok!(Evercity::bond_unit_package_buy(
+            Origin::signed(INVESTOR1),
+            bondid2,
+            Evercity::get_bond(&bondid2).nonce,
+            10
+        ));
+        assert_eq!(
+            balance - Evercity::balance_everusd(&INVESTOR1),
+            10 * (4_000_000_000_000 - UNIT_PRINCIPAL)
+        );
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_amortizing_try_invalid_schedule() {
+    const ACCOUNT: u64 = 3;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let mut bond = get_test_bond().inner;
+        // schedule must cover every period of the bond
+        bond.principal_schedule = Some(vec![50_000; 11]);
+        assert_noop!(
+            Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, bond.clone()),
+            RuntimeError::BondParamIncorrect
+        );
+        // cannot repay more than the principal
+        bond.principal_schedule = Some(vec![100_000; 12]);
+        assert_noop!(
+            Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, bond.clone()),
+            RuntimeError::BondParamIncorrect
+        );
+        bond.principal_schedule = Some(vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1_000_000]);
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, bond));
+    });
+}
+
+fn get_zero_coupon_test_bond() -> BondInnerStruct {
+    let mut bond = get_test_bond().inner;
+    bond.discount = Some(100_000); // 10%
+    bond.interest_rate_base_value = 0;
+    bond.impact_data_send_period = 0;
+    bond.impact_data_baseline = Vec::new();
+    bond.impact_data_max_deviation_cap = None;
+    bond.impact_data_max_deviation_floor = None;
+    bond.interest_rate_penalty_for_missed_report = None;
+    bond.interest_rate_margin_cap = None;
+    bond.interest_rate_margin_floor = None;
+    bond.interest_rate_start_period_value = None;
+    bond.interest_pay_period = None;
+    bond
+}
+
+#[test]
+fn bond_zero_coupon_validation() {
+    let bond = get_zero_coupon_test_bond();
+    assert!(bond.is_zero_coupon());
+    assert!(bond.is_valid(DEFAULT_DAY_DURATION));
+
+    let mut invalid = bond.clone();
+    invalid.discount = Some(0);
+    assert!(!invalid.is_valid(DEFAULT_DAY_DURATION));
+    invalid.discount = Some(1_000_000);
+    assert!(!invalid.is_valid(DEFAULT_DAY_DURATION));
+
+    // zero-coupon bond doesn't pay coupon yield
+    let mut invalid = bond.clone();
+    invalid.interest_rate_base_value = 2000;
+    assert!(!invalid.is_valid(DEFAULT_DAY_DURATION));
+
+    let mut invalid = bond.clone();
+    invalid.interest_pay_period = Some(7 * DEFAULT_DAY_DURATION);
+    assert!(!invalid.is_valid(DEFAULT_DAY_DURATION));
+
+    let mut invalid = bond.clone();
+    invalid.impact_data_send_period = 10 * DEFAULT_DAY_DURATION;
+    assert!(!invalid.is_valid(DEFAULT_DAY_DURATION));
+
+    let mut invalid = bond;
+    invalid.principal_schedule = Some(vec![50_000; 12]);
+    assert!(!invalid.is_valid(DEFAULT_DAY_DURATION));
+}
+
+#[test]
+fn bond_zero_coupon_sold_at_discount_redeemed_at_par() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const AUDITOR: u64 = 5;
+    const BOND_ARRANGER: u64 = 9;
+    const ISSUE_PRICE: EverUSDBalance = 3_600_000_000_000;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        let initial_balance1 = Evercity::balance_everusd(&INVESTOR1);
+        let initial_balance2 = Evercity::balance_everusd(&INVESTOR2);
+
+        let mut bond = get_zero_coupon_test_bond();
+        bond.mincap_deadline = 50000;
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, bond));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(10_000);
+        assert_ok!(Evercity::bond_release(Origin::signed(BOND_ARRANGER), bondid, 0));
+        assert_ok!(Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, 1, 600));
+        assert_ok!(Evercity::bond_unit_package_buy(Origin::signed(INVESTOR2), bondid, 1, 600));
+        assert!(Evercity::bond_check_invariant(&bondid));
+
+        // bond units are sold below par value
+        assert_eq!(initial_balance1 - Evercity::balance_everusd(&INVESTOR1), 600 * ISSUE_PRICE);
+        assert_eq!(Evercity::get_bond(&bondid).bond_credit, 1200 * ISSUE_PRICE);
+
+        assert_ok!(Evercity::bond_set_auditor(Origin::signed(BOND_ARRANGER), bondid, AUDITOR));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(30000);
+        assert_ok!(Evercity::bond_activate(
+            Origin::signed(BOND_ARRANGER),
+            bondid,
+            Evercity::get_bond(&bondid).nonce
+        ));
+        assert_eq!(Evercity::balance_everusd(&ACCOUNT), 1200 * ISSUE_PRICE);
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        // 10% discount for 480 days
+        assert_eq!(Evercity::get_implied_yield(&bondid), Some(8449));
+
+        // in the middle of bond lifetime the unit is sold by accreted value
+        let start = chain_bond_item.active_start_date;
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(240));
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        assert_ok!(Evercity::bond_unit_package_buy(
+            Origin::signed(INVESTOR1),
+            bondid,
+            chain_bond_item.nonce,
+            10
+        ));
+        assert_eq!(balance1 - Evercity::balance_everusd(&INVESTOR1), 10 * 3_800_000_000_000);
+
+        // there is no coupon yield
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        assert_ok!(Evercity::bond_withdraw_everusd(Origin::signed(INVESTOR1), bondid));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), balance1);
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.state, BondState::ACTIVE);
+        assert_eq!(chain_bond_item.bond_credit, 0);
+
+        // full par value is paid at maturity date
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(120 + 12 * 30 + 1));
+        assert_ok!(add_token(ACCOUNT, 1_000_000_000_000_000));
+        assert_ok!(Evercity::bond_redeem(Origin::signed(ACCOUNT), bondid));
+        assert_eq!(Evercity::get_bond(&bondid).bond_debit, 1210 * 4_000_000_000_000);
+
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_withdraw_everusd(Origin::signed(INVESTOR1), bondid));
+        assert_ok!(Evercity::bond_withdraw_everusd(Origin::signed(INVESTOR2), bondid));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1) - balance1, 610 * 4_000_000_000_000);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2) - balance2, 600 * 4_000_000_000_000);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+fn get_kpi_test_bond() -> BondStructOf<TestRuntime> {
+    let mut bond = get_test_bond();
+    let kpi = BondImpactKpi {
+        impact_data_type: BondImpactType::CO2_EMISSIONS_REDUCTION,
+        weight: 250_000,
+        impact_data_baseline: vec![Some(100_u64); 12],
+        impact_data_max_deviation_cap: 200,
+        impact_data_max_deviation_floor: 50,
+    };
+    bond.inner.impact_kpis = vec![
+        kpi.clone(),
+        BondImpactKpi {
+            impact_data_type: BondImpactType::WATER_SAVED,
+            ..kpi
+        },
+    ];
+    bond
+}
+
+#[test]
+fn bond_kpi_weighted_interest_rate() {
+    let bond = get_kpi_test_bond();
+    assert!(bond.inner.is_valid(DEFAULT_DAY_DURATION));
+
+    let report = |impact_data: u64, kpi_data: Vec<u64>| BondImpactReportStruct {
+        create_period: 1,
+        impact_data,
+        kpi_data,
+        signed: true,
+        rejection: None,
+    };
+    // all KPIs are on baseline
+    assert_eq!(bond.calc_impact_interest_rate(0, &report(20000, vec![100, 100])), 2000);
+    // 50% * 2.0% + 25% * 1.0% + 25% * 4.0%
+    assert_eq!(bond.calc_impact_interest_rate(0, &report(20000, vec![200, 50])), 2250);
+    // 50% * 1.0% + 25% * 1.0% + 25% * 1.0%
+    assert_eq!(bond.calc_impact_interest_rate(0, &report(30000, vec![300, 250])), 1000);
+
+    // the first KPI must have a weight
+    let mut invalid = bond.clone();
+    invalid.inner.impact_kpis[0].weight = 750_000;
+    assert!(!invalid.inner.is_valid(DEFAULT_DAY_DURATION));
+    // baseline for each period
+    let mut invalid = bond.clone();
+    invalid.inner.impact_kpis[1].impact_data_baseline.pop();
+    assert!(!invalid.inner.is_valid(DEFAULT_DAY_DURATION));
+    // baseline out of cap-floor bounds
+    let mut invalid = bond.clone();
+    invalid.inner.impact_kpis[1].impact_data_baseline[0] = Some(300);
+    assert!(!invalid.inner.is_valid(DEFAULT_DAY_DURATION));
+    let mut invalid = bond;
+    invalid.inner.impact_kpis[1].weight = 0;
+    assert!(!invalid.inner.is_valid(DEFAULT_DAY_DURATION));
+}
+
+#[test]
+fn bond_impact_kpi_report_send_approve() {
+    const ACCOUNT1: u64 = 3;
+    const AUDITOR: u64 = 5;
+    let bondid: BondId = "BOND1".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        let bond = get_kpi_test_bond().inner;
+        bond_activate(bondid, ACCOUNT1, bond.clone());
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        // first period
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(
+            chain_bond_item.active_start_date + 1000_u64 * (bond.start_period.unwrap_or(0) - 1) as u64,
+        );
+
+        // report must contain values of all KPIs
+        assert_noop!(
+            Evercity::bond_impact_report_send(Origin::signed(ACCOUNT1), bondid, 0, 20000),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_noop!(
+            Evercity::bond_impact_kpi_report_send(Origin::signed(ACCOUNT1), bondid, 0, 20000, vec![200]),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_ok!(Evercity::bond_impact_kpi_report_send(
+            Origin::signed(ACCOUNT1),
+            bondid,
+            0,
+            20000,
+            vec![200, 50]
+        ));
+
+        // auditor confirms values of all KPIs
+        assert_noop!(
+            Evercity::bond_impact_report_approve(Origin::signed(AUDITOR), bondid, 0, 20000),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_noop!(
+            Evercity::bond_impact_kpi_report_approve(Origin::signed(AUDITOR), bondid, 0, 20000, vec![200, 51]),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_ok!(Evercity::bond_impact_kpi_report_approve(
+            Origin::signed(AUDITOR),
+            bondid,
+            0,
+            20000,
+            vec![200, 50]
+        ));
+        let report = &Evercity::impact_reports(&bondid)[0];
+        assert!(report.signed);
+        assert_eq!(report.kpi_data, vec![200, 50]);
+
+        let history: Vec<_> = Evercity::impact_report_history(&bondid, 0)
+            .into_iter()
+            .map(|item| (item.account, item.kpi_data, item.action))
+            .collect();
+        assert_eq!(
+            history,
+            vec![
+                (ACCOUNT1, vec![200, 50], BondImpactReportAction::SENT),
+                (AUDITOR, vec![200, 50], BondImpactReportAction::APPROVED),
+            ]
+        );
+
+        // the second period
+        let now = chain_bond_item.active_start_date + days2timestamp(120 + 30 + 1);
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(now);
+        let mut chain_bond_item = Evercity::get_bond(&bondid);
+        Evercity::calc_and_store_bond_coupon_yield(&bondid, &mut chain_bond_item, now);
+        assert_eq!(Evercity::get_coupon_yields(&bondid)[1].interest_rate, 2250);
+    });
+}
+
+fn rate_points(points: &[(u64, BondInterest)]) -> Vec<BondInterestRatePoint> {
+    points
+        .iter()
+        .map(|&(impact_data, interest_rate)| BondInterestRatePoint { impact_data, interest_rate })
+        .collect()
+}
+
+#[test]
+fn bond_step_interest_rate_curve() {
+    let mut bond = get_test_bond();
+    // +0.25% if the target is missed, -0.1% if it is exceeded
+    bond.inner.interest_rate_curve = BondInterestRateCurve::STEP(rate_points(&[
+        (15000, 2250),
+        (20000, 2000),
+        (25000, 1900),
+    ]));
+    assert!(bond.inner.is_valid(DEFAULT_DAY_DURATION));
+
+    // below the first threshold
+    assert_eq!(bond.calc_effective_interest_rate(20000, 14999), 4000);
+    assert_eq!(bond.calc_effective_interest_rate(20000, 15000), 2250);
+    assert_eq!(bond.calc_effective_interest_rate(20000, 19999), 2250);
+    assert_eq!(bond.calc_effective_interest_rate(20000, 20000), 2000);
+    assert_eq!(bond.calc_effective_interest_rate(20000, 24000), 2000);
+    assert_eq!(bond.calc_effective_interest_rate(20000, 100000), 1900);
+
+    // thresholds must be sorted
+    let mut invalid = bond.clone();
+    invalid.inner.interest_rate_curve = BondInterestRateCurve::STEP(rate_points(&[(20000, 2000), (15000, 2250)]));
+    assert!(!invalid.inner.is_valid(DEFAULT_DAY_DURATION));
+    // interest rate must be in floor-cap bounds
+    invalid.inner.interest_rate_curve = BondInterestRateCurve::STEP(rate_points(&[(15000, 4001)]));
+    assert!(!invalid.inner.is_valid(DEFAULT_DAY_DURATION));
+    invalid.inner.interest_rate_curve = BondInterestRateCurve::STEP(Vec::new());
+    assert!(!invalid.inner.is_valid(DEFAULT_DAY_DURATION));
+    // stable bond has fixed interest rate
+    let mut invalid = get_test_bond_stable();
+    invalid.inner.interest_rate_curve = bond.inner.interest_rate_curve.clone();
+    assert!(!invalid.inner.is_valid(DEFAULT_DAY_DURATION));
+
+    // interest rate of the curve cannot be restructured
+    assert!(!bond.restructure(&BondRestructuring::RATE_CHANGE(2500), 1));
+    assert_eq!(bond.inner.interest_rate_base_value, 2000);
+}
+
+#[test]
+fn bond_piecewise_linear_interest_rate_curve() {
+    let mut bond = get_test_bond();
+    bond.inner.interest_rate_curve = BondInterestRateCurve::PIECEWISE_LINEAR(rate_points(&[
+        (10000, 3000),
+        (20000, 2000),
+        (30000, 1500),
+    ]));
+    assert!(bond.inner.is_valid(DEFAULT_DAY_DURATION));
+
+    // out of the table
+    assert_eq!(bond.calc_effective_interest_rate(20000, 0), 3000);
+    assert_eq!(bond.calc_effective_interest_rate(20000, 40000), 1500);
+    // table points
+    assert_eq!(bond.calc_effective_interest_rate(20000, 10000), 3000);
+    assert_eq!(bond.calc_effective_interest_rate(20000, 20000), 2000);
+    assert_eq!(bond.calc_effective_interest_rate(20000, 30000), 1500);
+    // interpolation
+    assert_eq!(bond.calc_effective_interest_rate(20000, 15000), 2500);
+    assert_eq!(bond.calc_effective_interest_rate(20000, 26000), 1700);
+
+    // at least two points
+    let mut invalid = bond.clone();
+    invalid.inner.interest_rate_curve = BondInterestRateCurve::PIECEWISE_LINEAR(rate_points(&[(20000, 2000)]));
+    assert!(!invalid.inner.is_valid(DEFAULT_DAY_DURATION));
+    // impact_data values must be unique
+    invalid.inner.interest_rate_curve =
+        BondInterestRateCurve::PIECEWISE_LINEAR(rate_points(&[(20000, 2000), (20000, 1500)]));
+    assert!(!invalid.inner.is_valid(DEFAULT_DAY_DURATION));
+    invalid.inner.interest_rate_curve =
+        BondInterestRateCurve::PIECEWISE_LINEAR(rate_points(&[(10000, 999), (20000, 1500)]));
+    assert!(!invalid.inner.is_valid(DEFAULT_DAY_DURATION));
+}
+
+#[test]
+fn bond_restructuring_coupon_holiday_approved() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let start = Evercity::get_bond(&bondid).active_start_date;
+        let nonce = Evercity::get_bond(&bondid).nonce;
+
+        // coupon holiday for the first and the second payment periods
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(120 + 1));
+        assert_ok!(Evercity::bond_restructuring_propose(
+            Origin::signed(ACCOUNT),
+            bondid,
+            BondRestructuring::COUPON_HOLIDAY(2),
+            start + days2timestamp(130)
+        ));
+        assert_ok!(Evercity::bond_restructuring_vote(Origin::signed(INVESTOR1), bondid, true));
+        // half of issued bond units isn't a majority
+        assert_noop!(
+            Evercity::bond_restructuring_finalize(Origin::signed(INVESTOR1), bondid, 2),
+            RuntimeError::BondOutOfOrder
+        );
+        assert_ok!(Evercity::bond_restructuring_vote(Origin::signed(INVESTOR2), bondid, true));
+        assert_eq!(Evercity::bond_restructuring_vote_count(&bondid), 2);
+        // the number of votes is greater than the witness
+        assert_noop!(
+            Evercity::bond_restructuring_finalize(Origin::signed(INVESTOR1), bondid, 1),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_ok!(Evercity::bond_restructuring_finalize(Origin::signed(INVESTOR1), bondid, 2));
+
+        assert!(Evercity::bond_restructuring_proposal(&bondid).is_none());
+        assert!(Evercity::bond_restructuring_votes(&bondid, &INVESTOR1).is_none());
+        assert_eq!(Evercity::bond_restructuring_vote_count(&bondid), 0);
+        assert_eq!(Evercity::bond_coupon_holidays(&bondid), vec![1, 2]);
+        assert_eq!(Evercity::get_bond(&bondid).nonce, nonce + 1);
+
+        let now = start + days2timestamp(120 + 30 * 3 + 1);
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(now);
+        let mut chain_bond_item = Evercity::get_bond(&bondid);
+        Evercity::calc_and_store_bond_coupon_yield(&bondid, &mut chain_bond_item, now);
+        let yields = Evercity::get_coupon_yields(&bondid);
+        assert_eq!(yields.len(), 4);
+        assert_eq!(yields[0].interest_rate, 1900);
+        assert_eq!(yields[1].interest_rate, 0);
+        assert_eq!(yields[2].interest_rate, 0);
+        assert_eq!(yields[1].total_yield, yields[0].total_yield);
+        assert_eq!(yields[2].total_yield, yields[0].total_yield);
+        // missed report penalty is applied to the rate before the holiday
+        assert_eq!(yields[3].interest_rate, 1900 + 400);
+    });
+}
+
+#[test]
+fn bond_restructuring_rate_change_quorum() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let start = Evercity::get_bond(&bondid).active_start_date;
+        let deadline = start + days2timestamp(130);
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(120 + 1));
+
+        // nobody voted
+        assert_ok!(Evercity::bond_restructuring_propose(
+            Origin::signed(ACCOUNT),
+            bondid,
+            BondRestructuring::RATE_CHANGE(2500),
+            deadline
+        ));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(deadline + 1);
+        assert_ok!(Evercity::bond_restructuring_finalize(Origin::signed(INVESTOR1), bondid, 2));
+        assert_eq!(Evercity::get_bond(&bondid).inner.interest_rate_base_value, 2000);
+
+        // quorum is reached, but votes are equal
+        let deadline = start + days2timestamp(140);
+        assert_ok!(Evercity::bond_restructuring_propose(
+            Origin::signed(ACCOUNT),
+            bondid,
+            BondRestructuring::RATE_CHANGE(2500),
+            deadline
+        ));
+        assert_ok!(Evercity::bond_restructuring_vote(Origin::signed(INVESTOR1), bondid, true));
+        assert_ok!(Evercity::bond_restructuring_vote(Origin::signed(INVESTOR2), bondid, false));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(deadline + 1);
+        assert_ok!(Evercity::bond_restructuring_finalize(Origin::signed(INVESTOR1), bondid, 2));
+        assert_eq!(Evercity::get_bond(&bondid).inner.interest_rate_base_value, 2000);
+
+        // quorum is reached by the only voter
+        let deadline = start + days2timestamp(150);
+        assert_ok!(Evercity::bond_restructuring_propose(
+            Origin::signed(ACCOUNT),
+            bondid,
+            BondRestructuring::RATE_CHANGE(2500),
+            deadline
+        ));
+        assert_ok!(Evercity::bond_restructuring_vote(Origin::signed(INVESTOR2), bondid, false));
+        // bondholder can change his vote
+        assert_ok!(Evercity::bond_restructuring_vote(Origin::signed(INVESTOR2), bondid, true));
+        assert_eq!(Evercity::bond_restructuring_vote_count(&bondid), 1);
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(deadline + 1);
+        assert_ok!(Evercity::bond_restructuring_finalize(Origin::signed(INVESTOR1), bondid, 2));
+        assert_eq!(Evercity::get_bond(&bondid).inner.interest_rate_base_value, 2500);
+        assert!(Evercity::bond_restructuring_proposal(&bondid).is_none());
+    });
+}
+
+#[test]
+fn bond_restructuring_maturity_extension() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const BOND_ARRANGER: u64 = 9;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let start = Evercity::get_bond(&bondid).active_start_date;
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(120 + 1));
+
+        assert_ok!(Evercity::bond_restructuring_propose(
+            Origin::signed(BOND_ARRANGER),
+            bondid,
+            BondRestructuring::MATURITY_EXTENSION(2),
+            start + days2timestamp(130)
+        ));
+        assert_ok!(Evercity::bond_restructuring_vote(Origin::signed(INVESTOR1), bondid, true));
+        assert_ok!(Evercity::bond_restructuring_vote(Origin::signed(INVESTOR2), bondid, true));
+        assert_ok!(Evercity::bond_restructuring_finalize(Origin::signed(ACCOUNT), bondid, 2));
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.inner.bond_duration, 14);
+        assert_eq!(chain_bond_item.inner.impact_data_baseline.len(), 14);
+        assert_eq!(Evercity::impact_reports(&bondid).len(), 14);
+        assert!(chain_bond_item.inner.is_valid(DEFAULT_DAY_DURATION));
+    });
+}
+
+#[test]
+fn bond_restructuring_try_invalid() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, get_test_bond().inner));
+        assert_noop!(
+            Evercity::bond_restructuring_propose(
+                Origin::signed(ACCOUNT),
+                bondid,
+                BondRestructuring::RATE_CHANGE(2500),
+                days2timestamp(10)
+            ),
+            RuntimeError::BondStateNotPermitAction
+        );
+    });
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let start = Evercity::get_bond(&bondid).active_start_date;
+        let deadline = start + days2timestamp(130);
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(120 + 1));
+
+        assert_noop!(
+            Evercity::bond_restructuring_vote(Origin::signed(INVESTOR1), bondid, true),
+            RuntimeError::ProposalNotFound
+        );
+        assert_noop!(
+            Evercity::bond_restructuring_finalize(Origin::signed(INVESTOR1), bondid, 2),
+            RuntimeError::ProposalNotFound
+        );
+        assert_noop!(
+            Evercity::bond_restructuring_propose(
+                Origin::signed(INVESTOR1),
+                bondid,
+                BondRestructuring::RATE_CHANGE(2500),
+                deadline
+            ),
+            RuntimeError::BondAccessDenied
+        );
+        // deadline has passed
+        assert_noop!(
+            Evercity::bond_restructuring_propose(
+                Origin::signed(ACCOUNT),
+                bondid,
+                BondRestructuring::RATE_CHANGE(2500),
+                start
+            ),
+            RuntimeError::BondParamIncorrect
+        );
+        // interest rate is above the cap
+        assert_noop!(
+            Evercity::bond_restructuring_propose(
+                Origin::signed(ACCOUNT),
+                bondid,
+                BondRestructuring::RATE_CHANGE(4001),
+                deadline
+            ),
+            RuntimeError::BondParamIncorrect
+        );
+        // holiday is longer than the rest of the bond
+        assert_noop!(
+            Evercity::bond_restructuring_propose(
+                Origin::signed(ACCOUNT),
+                bondid,
+                BondRestructuring::COUPON_HOLIDAY(13),
+                deadline
+            ),
+            RuntimeError::BondParamIncorrect
+        );
+
+        assert_ok!(Evercity::bond_restructuring_propose(
+            Origin::signed(ACCOUNT),
+            bondid,
+            BondRestructuring::RATE_CHANGE(2500),
+            deadline
+        ));
+        assert_noop!(
+            Evercity::bond_restructuring_propose(
+                Origin::signed(ACCOUNT),
+                bondid,
+                BondRestructuring::COUPON_HOLIDAY(1),
+                deadline
+            ),
+            RuntimeError::BondOutOfOrder
+        );
+        // not a bondholder
+        assert_noop!(
+            Evercity::bond_restructuring_vote(Origin::signed(ACCOUNT), bondid, true),
+            RuntimeError::BondAccessDenied
+        );
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(deadline + 1);
+        assert_noop!(
+            Evercity::bond_restructuring_vote(Origin::signed(INVESTOR1), bondid, true),
+            RuntimeError::ProposalObsolete
+        );
+    });
+}
+
+#[test]
+fn bond_default_settled_recovery_distributed_pro_rata() {
+    const MASTER: u64 = 1;
+    const CUSTODIAN: u64 = 2;
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const BOND_ARRANGER: u64 = 9;
+    const RECOVERED: EverUSDBalance = 600 * 4_000_000_000_000;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        // recovered EverUSD are transferred to custodian
+        assert_ok!(EvercityAccounts::account_set_with_role_and_data(
+            Origin::signed(MASTER),
+            CUSTODIAN,
+            INVESTOR_ROLE_MASK
+        ));
+        assert_ok!(add_token(CUSTODIAN, RECOVERED));
+
+        let start = Evercity::get_bond(&bondid).active_start_date;
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(120 + 30 + 8));
+        assert_noop!(
+            Evercity::bond_recovery_deposit(Origin::signed(CUSTODIAN), bondid, RECOVERED),
+            RuntimeError::BondStateNotPermitAction
+        );
+        assert_ok!(Evercity::bond_declare_bankrupt(Origin::signed(BOND_ARRANGER), bondid));
+        let accrued = Evercity::get_bond(&bondid).bond_credit;
+        assert!(accrued > 0);
+
+        assert_noop!(
+            Evercity::bond_recovery_deposit(Origin::signed(INVESTOR1), bondid, RECOVERED),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_ok!(Evercity::bond_recovery_deposit(Origin::signed(CUSTODIAN), bondid, RECOVERED));
+        // recovery deposit doesn't make bond ACTIVE
+        assert_eq!(Evercity::get_bond(&bondid).state, BondState::BANKRUPT);
+
+        assert_noop!(
+            Evercity::bond_default_settle(Origin::signed(CUSTODIAN), bondid, 2),
+            RuntimeError::AccountNotAuthorized
+        );
+        // the number of bondholders is greater than the witness
+        assert_noop!(
+            Evercity::bond_default_settle(Origin::signed(BOND_ARRANGER), bondid, 1),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_ok!(Evercity::bond_default_settle(Origin::signed(BOND_ARRANGER), bondid, 2));
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.state, BondState::DEFAULT_SETTLED);
+        let settlement = Evercity::bond_default_settlement(&bondid).unwrap();
+        assert_eq!(settlement.period, 2);
+        assert_eq!(settlement.bond_credit, accrued);
+        assert_eq!(settlement.recovery_fund, RECOVERED);
+        assert_eq!(settlement.total_claim, 1200 * 4_000_000_000_000 + accrued);
+        assert_eq!(chain_bond_item.bond_credit, RECOVERED);
+
+        // bondholders have equal claims
+        let recovery = settlement.recovery_amount(settlement.total_claim / 2);
+        assert!(recovery <= RECOVERED / 2);
+        for investor in [INVESTOR1, INVESTOR2] {
+            let balance = Evercity::balance_everusd(&investor);
+            assert_ok!(Evercity::bond_withdraw_everusd(Origin::signed(investor), bondid));
+            assert_eq!(Evercity::balance_everusd(&investor) - balance, recovery);
+            assert!(Evercity::bond_holder_packages(&bondid, &investor).is_empty());
+            // nothing more to claim
+            assert_ok!(Evercity::bond_withdraw_everusd(Origin::signed(investor), bondid));
+            assert_eq!(Evercity::balance_everusd(&investor) - balance, recovery);
+        }
+        // no surplus for the issuer
+        let balance = Evercity::balance_everusd(&ACCOUNT);
+        assert_ok!(Evercity::bond_withdraw_everusd(Origin::signed(ACCOUNT), bondid));
+        assert_eq!(Evercity::balance_everusd(&ACCOUNT), balance);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        // terminal state
+        assert_noop!(
+            Evercity::bond_default_settle(Origin::signed(BOND_ARRANGER), bondid, 2),
+            RuntimeError::BondStateNotPermitAction
+        );
+        assert_noop!(
+            Evercity::bond_deposit_everusd(Origin::signed(ACCOUNT), bondid, 1),
+            RuntimeError::BondStateNotPermitAction
+        );
+    });
+}
+
+#[test]
+fn bond_allocation_rule_allocate() {
+    // not oversubscribed
+    assert_eq!(BondAllocationRule::PRO_RATA.allocate(10, &[1, 2, 3]), vec![1, 2, 3]);
+    assert_eq!(BondAllocationRule::TIME_PRIORITY.allocate(10, &[1, 2, 3]), vec![1, 2, 3]);
+
+    assert_eq!(BondAllocationRule::PRO_RATA.allocate(1000, &[600, 600, 300]), vec![400, 400, 200]);
+    // rounding leftovers are given to earlier orders
+    assert_eq!(BondAllocationRule::PRO_RATA.allocate(1000, &[700, 700, 700]), vec![334, 333, 333]);
+    assert_eq!(BondAllocationRule::PRO_RATA.allocate(2, &[1, 1, 1]), vec![1, 1, 0]);
+
+    assert_eq!(BondAllocationRule::TIME_PRIORITY.allocate(1000, &[600, 600, 300]), vec![600, 400, 0]);
+    assert_eq!(BondAllocationRule::TIME_PRIORITY.allocate(0, &[600]), vec![0]);
+}
+
+#[test]
+fn bond_bookbuilding_pro_rata_allocation() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const INVESTOR3: u64 = 7;
+    const AUDITOR: u64 = 5;
+    const BOND_ARRANGER: u64 = 9;
+    const PRICE: EverUSDBalance = 4_000_000_000_000;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let mut bond = get_test_bond().inner;
+        bond.bookbuilding = Some(BondAllocationRule::PRO_RATA);
+        bond_grand_everusd();
+        assert_ok!(add_token(INVESTOR3, 50_000_000_000_000_000));
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, bond));
+        assert_noop!(
+            Evercity::bond_unit_order_place(Origin::signed(INVESTOR1), bondid, 1200),
+            RuntimeError::BondStateNotPermitAction
+        );
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(10_000);
+        assert_ok!(Evercity::bond_release(Origin::signed(BOND_ARRANGER), bondid, 0));
+
+        // direct sale is closed in bookbuilding mode
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, Evercity::get_bond(&bondid).nonce, 600),
+            RuntimeError::BondStateNotPermitAction
+        );
+        assert_noop!(
+            Evercity::bond_unit_order_place(Origin::signed(INVESTOR1), bondid, 1801),
+            RuntimeError::BondParamIncorrect
+        );
+
+        // bond is oversubscribed: 3000 of 1800 bond units are ordered
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        assert_ok!(Evercity::bond_unit_order_place(Origin::signed(INVESTOR1), bondid, 1200));
+        assert_ok!(Evercity::bond_unit_order_place(Origin::signed(INVESTOR2), bondid, 100));
+        assert_ok!(Evercity::bond_unit_order_place(Origin::signed(INVESTOR3), bondid, 600));
+        // the order is replaced
+        assert_ok!(Evercity::bond_unit_order_place(Origin::signed(INVESTOR2), bondid, 1200));
+        assert_eq!(Evercity::reserved_everusd(&INVESTOR1), 1200 * PRICE);
+        assert_eq!(Evercity::reserved_everusd(&INVESTOR2), 1200 * PRICE);
+        assert_eq!(Evercity::bond_unit_orders(&bondid, &INVESTOR2).unwrap().bond_units, 1200);
+        assert_eq!(Evercity::get_bond(&bondid).issued_amount, 0);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        assert_ok!(Evercity::bond_set_auditor(Origin::signed(BOND_ARRANGER), bondid, AUDITOR));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(30_000);
+        assert_ok!(Evercity::bond_activate(
+            Origin::signed(BOND_ARRANGER),
+            bondid,
+            Evercity::get_bond(&bondid).nonce
+        ));
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.state, BondState::ACTIVE);
+        assert_eq!(chain_bond_item.issued_amount, 1800);
+        assert_eq!(Evercity::balance_everusd(&ACCOUNT), 1800 * PRICE);
+        for (investor, bond_units) in [(INVESTOR1, 720), (INVESTOR2, 720), (INVESTOR3, 360)] {
+            let packages = Evercity::bond_holder_packages(&bondid, &investor);
+            assert_eq!(packages.len(), 1);
+            assert_eq!(packages[0].bond_units, bond_units);
+            assert_eq!(Evercity::reserved_everusd(&investor), 0);
+            assert!(Evercity::bond_unit_orders(&bondid, &investor).is_none());
+        }
+        // unfilled amount is refunded
+        assert_eq!(balance1 - Evercity::balance_everusd(&INVESTOR1), 720 * PRICE);
+        assert!(Evercity::bond_check_invariant(&bondid));
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_bookbuilding_orders_refunded_on_withdraw() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const AUDITOR: u64 = 5;
+    const BOND_ARRANGER: u64 = 9;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let mut bond = get_test_bond().inner;
+        bond.bookbuilding = Some(BondAllocationRule::TIME_PRIORITY);
+        bond_grand_everusd();
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, bond));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(10_000);
+        assert_ok!(Evercity::bond_release(Origin::signed(BOND_ARRANGER), bondid, 0));
+        assert_ok!(Evercity::bond_set_auditor(Origin::signed(BOND_ARRANGER), bondid, AUDITOR));
+
+        assert_ok!(Evercity::bond_unit_order_place(Origin::signed(INVESTOR1), bondid, 600));
+        assert_ok!(Evercity::bond_unit_order_place(Origin::signed(INVESTOR2), bondid, 300));
+        assert_ok!(Evercity::bond_unit_order_cancel(Origin::signed(INVESTOR2), bondid));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), balance2);
+        assert_noop!(
+            Evercity::bond_unit_order_cancel(Origin::signed(INVESTOR2), bondid),
+            RuntimeError::BondParamIncorrect
+        );
+
+        // mincap isn't reached
+        assert_noop!(
+            Evercity::bond_activate(Origin::signed(BOND_ARRANGER), bondid, Evercity::get_bond(&bondid).nonce),
+            RuntimeError::BondParamIncorrect
+        );
+        assert!(Evercity::bond_unit_orders(&bondid, &INVESTOR1).is_some());
+
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(days2timestamp(21));
+        assert_ok!(Evercity::bond_withdraw(Origin::signed(ACCOUNT), bondid));
+        assert_eq!(Evercity::get_bond(&bondid).state, BondState::PREPARE);
+        assert!(Evercity::bond_unit_orders(&bondid, &INVESTOR1).is_none());
+        assert_eq!(Evercity::reserved_everusd(&INVESTOR1), 0);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), balance1);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_bookbuilding_auto_withdraw_after_deadline() {
+    use frame_support::traits::Hooks;
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const BOND_ARRANGER: u64 = 9;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let mut bond = get_test_bond().inner;
+        bond.bookbuilding = Some(BondAllocationRule::PRO_RATA);
+        bond_grand_everusd();
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, bond));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(10_000);
+        assert_ok!(Evercity::bond_release(Origin::signed(BOND_ARRANGER), bondid, 0));
+
+        assert_ok!(Evercity::bond_unit_order_place(Origin::signed(INVESTOR1), bondid, 600));
+        assert_ok!(Evercity::bond_unit_order_place(Origin::signed(INVESTOR2), bondid, 600));
+        assert_eq!(Evercity::bond_unit_order_count(&bondid), 2);
+
+        // ordered bond units reach mincap, the bond waits for activation
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(days2timestamp(21));
+        Evercity::on_idle(1, Weight::MAX);
+        assert_eq!(Evercity::get_bond(&bondid).state, BondState::BOOKING);
+        assert_noop!(
+            Evercity::bond_withdraw(Origin::signed(ACCOUNT), bondid),
+            RuntimeError::BondParamIncorrect
+        );
+
+        assert_ok!(Evercity::bond_unit_order_cancel(Origin::signed(INVESTOR2), bondid));
+        assert_eq!(Evercity::bond_unit_order_count(&bondid), 1);
+        Evercity::on_idle(2, Weight::MAX);
+        assert_eq!(Evercity::get_bond(&bondid).state, BondState::PREPARE);
+        assert!(Evercity::bond_unit_orders(&bondid, &INVESTOR1).is_none());
+        assert_eq!(Evercity::bond_unit_order_count(&bondid), 0);
+        assert_eq!(Evercity::reserved_everusd(&INVESTOR1), 0);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), balance1);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), balance2);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_fees_charged_on_issuance_coupon_and_trade() {
+    const EVERCITY: u64 = 1;
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const AUDITOR: u64 = 5;
+    const BOND_ARRANGER: u64 = 9;
+    const PRICE: EverUSDBalance = 4_000_000_000_000;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let mut bond = get_test_bond().inner;
+        bond.fees = vec![
+            BondFeeStruct { account: EVERCITY, issuance_fee: 10_000, coupon_fee: 50_000, trade_fee: 5_000 },
+            BondFeeStruct { account: BOND_ARRANGER, issuance_fee: 5_000, coupon_fee: 0, trade_fee: 5_000 },
+        ];
+        bond_grand_everusd();
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, bond));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(10_000);
+        assert_ok!(Evercity::bond_release(Origin::signed(BOND_ARRANGER), bondid, 0));
+        assert_ok!(Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, 1, 600));
+        assert_ok!(Evercity::bond_unit_package_buy(Origin::signed(INVESTOR2), bondid, 1, 600));
+        assert_ok!(Evercity::bond_set_auditor(Origin::signed(BOND_ARRANGER), bondid, AUDITOR));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(30_000);
+        assert_ok!(Evercity::bond_activate(
+            Origin::signed(BOND_ARRANGER),
+            bondid,
+            Evercity::get_bond(&bondid).nonce
+        ));
+
+        // 1.5% issuance fee
+        assert_eq!(Evercity::balance_everusd(&ACCOUNT), 1200 * PRICE - 18 * PRICE);
+        assert_eq!(Evercity::fee_everusd(&EVERCITY), 12 * PRICE);
+        assert_eq!(Evercity::fee_everusd(&BOND_ARRANGER), 6 * PRICE);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        // 1% trade fee is withheld from the seller
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: 100000,
+            new_bondholder: Default::default(),
+            bond_units: 100,
+            amount: 100 * PRICE,
+        };
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
+        ));
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR2),
+            bondid,
+            INVESTOR1,
+            Evercity::last_bond_unit_lot_id(),
+            lot.bond_units,
+            lot.amount
+        ));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1) - balance1, 99 * PRICE);
+        assert_eq!(balance2 - Evercity::balance_everusd(&INVESTOR2), 100 * PRICE);
+        assert_eq!(Evercity::fee_everusd(&EVERCITY), 12 * PRICE + PRICE / 2);
+        assert_eq!(Evercity::fee_everusd(&BOND_ARRANGER), 6 * PRICE + PRICE / 2);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        // 5% coupon fee is withheld from coupon yield
+        let start = Evercity::get_bond(&bondid).active_start_date;
+        let now = start + days2timestamp(120 + 1);
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(now);
+        let mut chain_bond_item = Evercity::get_bond(&bondid);
+        Evercity::calc_and_store_bond_coupon_yield(&bondid, &mut chain_bond_item, now);
+        assert_ok!(Evercity::bond_deposit_everusd(Origin::signed(ACCOUNT), bondid, chain_bond_item.bond_credit));
+
+        for investor in [INVESTOR1, INVESTOR2] {
+            let balance = Evercity::balance_everusd(&investor);
+            let fee = Evercity::fee_everusd(&EVERCITY);
+            assert_ok!(Evercity::bond_withdraw_everusd(Origin::signed(investor), bondid));
+            let received = Evercity::balance_everusd(&investor) - balance;
+            let fee = Evercity::fee_everusd(&EVERCITY) - fee;
+            assert!(received > 0);
+            assert_eq!(fee, (received + fee) / 20);
+        }
+        assert_eq!(Evercity::fee_everusd(&BOND_ARRANGER), 6 * PRICE + PRICE / 2);
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.coupon_yield, chain_bond_item.bond_credit);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        // fee accounts withdraw the fees
+        let fee = Evercity::fee_everusd(&BOND_ARRANGER);
+        let balance = Evercity::balance_everusd(&BOND_ARRANGER);
+        assert_ok!(Evercity::fee_withdraw_everusd(Origin::signed(BOND_ARRANGER)));
+        assert_eq!(Evercity::balance_everusd(&BOND_ARRANGER) - balance, fee);
+        assert_eq!(Evercity::fee_everusd(&BOND_ARRANGER), 0);
+        assert_noop!(
+            Evercity::fee_withdraw_everusd(Origin::signed(BOND_ARRANGER)),
+            RuntimeError::BalanceOverdraft
+        );
+        assert_ok!(Evercity::fee_withdraw_everusd(Origin::signed(EVERCITY)));
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_fees_try_invalid() {
+    let fee = |issuance_fee, coupon_fee, trade_fee| BondFeeStruct {
+        account: 1,
+        issuance_fee,
+        coupon_fee,
+        trade_fee,
+    };
+    let mut bond = get_test_bond();
+    bond.inner.fees = vec![fee(50_000, 50_000, 50_000), fee(50_000, 50_000, 50_000)];
+    assert!(bond.inner.is_valid(DEFAULT_DAY_DURATION));
+    // total fee of every kind is limited
+    bond.inner.fees = vec![fee(50_000, 0, 0), fee(50_001, 0, 0)];
+    assert!(!bond.inner.is_valid(DEFAULT_DAY_DURATION));
+    bond.inner.fees = vec![fee(0, 100_001, 0)];
+    assert!(!bond.inner.is_valid(DEFAULT_DAY_DURATION));
+    bond.inner.fees = vec![fee(0, 0, 100_001)];
+    assert!(!bond.inner.is_valid(DEFAULT_DAY_DURATION));
+    // the number of fee accounts is limited
+    bond.inner.fees = vec![fee(0, 0, 0); 5];
+    assert!(!bond.inner.is_valid(DEFAULT_DAY_DURATION));
+}
+
+#[test]
+fn bond_transfer_restricted_by_whitelist_and_holding_limit() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const INVESTOR3: u64 = 7;
+    const AUDITOR: u64 = 5;
+    const BOND_ARRANGER: u64 = 9;
+    const PRICE: EverUSDBalance = 4_000_000_000_000;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        assert_ok!(add_token(INVESTOR3, 50_000_000_000_000_000));
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, get_test_bond().inner));
+        let mut restriction = BondTransferRestrictionStruct {
+            whitelist_only: true,
+            jurisdictions: vec![*b"DE"],
+            max_holding: 700,
+        };
+        assert_noop!(
+            Evercity::bond_transfer_restriction_set(Origin::signed(BOND_ARRANGER), bondid, restriction.clone()),
+            RuntimeError::BondAccessDenied
+        );
+        assert_ok!(Evercity::bond_transfer_restriction_set(Origin::signed(ACCOUNT), bondid, restriction.clone()));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(10_000);
+        assert_ok!(Evercity::bond_release(Origin::signed(BOND_ARRANGER), bondid, 0));
+
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, 1, 600),
+            RuntimeError::BondTransferNotPermitted
+        );
+        assert_ok!(Evercity::bond_whitelist_add(Origin::signed(ACCOUNT), bondid, INVESTOR1, *b"DE"));
+        assert_ok!(Evercity::bond_whitelist_add(Origin::signed(ACCOUNT), bondid, INVESTOR2, *b"US"));
+        assert_ok!(Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, 1, 600));
+        // jurisdiction isn't permitted
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR2), bondid, 1, 600),
+            RuntimeError::BondTransferNotPermitted
+        );
+        restriction.jurisdictions.push(*b"US");
+        assert_ok!(Evercity::bond_transfer_restriction_set(Origin::signed(ACCOUNT), bondid, restriction.clone()));
+        assert_ok!(Evercity::bond_unit_package_buy(Origin::signed(INVESTOR2), bondid, 1, 600));
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, 1, 101),
+            RuntimeError::BondHoldingLimitExceeded
+        );
+        assert_ok!(Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, 1, 100));
+        assert_eq!(Evercity::bondholder_units(&bondid, &INVESTOR1), 700);
+
+        assert_ok!(Evercity::bond_set_auditor(Origin::signed(BOND_ARRANGER), bondid, AUDITOR));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(30_000);
+        assert_ok!(Evercity::bond_activate(
+            Origin::signed(BOND_ARRANGER),
+            bondid,
+            Evercity::get_bond(&bondid).nonce
+        ));
+
+        // secondary market is restricted the same way
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: 100000,
+            new_bondholder: Default::default(),
+            bond_units: 100,
+            amount: 100 * PRICE,
+        };
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR2),
+            bondid,
+            lot.new_bondholder,
+            lot.bond_units,
+            lot.amount,
+            lot.deadline
+        ));
+        let lot_id = Evercity::last_bond_unit_lot_id();
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR1), bondid, INVESTOR2, lot_id, lot.bond_units, lot.amount),
+            RuntimeError::BondHoldingLimitExceeded
+        );
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR3), bondid, INVESTOR2, lot_id, lot.bond_units, lot.amount),
+            RuntimeError::BondTransferNotPermitted
+        );
+        assert_ok!(Evercity::bond_unit_purchase_bid(
+            Origin::signed(INVESTOR3),
+            bondid,
+            BondUnitPurchaseBidStruct { id: 0, deadline: 100000, bond_units: 100, amount: 100 * PRICE }
+        ));
+        assert_noop!(
+            Evercity::bond_unit_purchase_bid_settle(
+                Origin::signed(INVESTOR1),
+                bondid,
+                INVESTOR3,
+                Evercity::last_bond_unit_bid_id(),
+                100,
+                100 * PRICE
+            ),
+            RuntimeError::BondTransferNotPermitted
+        );
+
+        // restrictions can be relaxed
+        restriction.max_holding = 0;
+        assert_ok!(Evercity::bond_transfer_restriction_set(Origin::signed(ACCOUNT), bondid, restriction));
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR1),
+            bondid,
+            INVESTOR2,
+            lot_id,
+            lot.bond_units,
+            lot.amount
+        ));
+        assert_eq!(Evercity::bondholder_units(&bondid, &INVESTOR1), 800);
+
+        // holdings of removed investor remain
+        assert_ok!(Evercity::bond_whitelist_remove(Origin::signed(ACCOUNT), bondid, INVESTOR1));
+        assert_noop!(
+            Evercity::bond_whitelist_remove(Origin::signed(ACCOUNT), bondid, INVESTOR1),
+            RuntimeError::AccountNotExist
+        );
+        assert_eq!(Evercity::bondholder_units(&bondid, &INVESTOR1), 800);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_transfer_restricted_bookbuilding_orders() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const INVESTOR3: u64 = 7;
+    const AUDITOR: u64 = 5;
+    const BOND_ARRANGER: u64 = 9;
+    const PRICE: EverUSDBalance = 4_000_000_000_000;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let mut bond = get_test_bond().inner;
+        bond.bookbuilding = Some(BondAllocationRule::PRO_RATA);
+        bond_grand_everusd();
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, bond));
+        let restriction = BondTransferRestrictionStruct {
+            whitelist_only: true,
+            jurisdictions: Vec::new(),
+            max_holding: 1200,
+        };
+        assert_ok!(Evercity::bond_transfer_restriction_set(Origin::signed(ACCOUNT), bondid, restriction));
+        assert_ok!(Evercity::bond_whitelist_add(Origin::signed(ACCOUNT), bondid, INVESTOR1, *b"DE"));
+        assert_ok!(Evercity::bond_whitelist_add(Origin::signed(ACCOUNT), bondid, INVESTOR2, *b"FR"));
+        // only investors can be whitelisted
+        assert_noop!(
+            Evercity::bond_whitelist_add(Origin::signed(ACCOUNT), bondid, BOND_ARRANGER, *b"FR"),
+            RuntimeError::AccountNotAuthorized
+        );
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(10_000);
+        assert_ok!(Evercity::bond_release(Origin::signed(BOND_ARRANGER), bondid, 0));
+
+        assert_noop!(
+            Evercity::bond_unit_order_place(Origin::signed(INVESTOR3), bondid, 600),
+            RuntimeError::BondTransferNotPermitted
+        );
+        assert_noop!(
+            Evercity::bond_unit_order_place(Origin::signed(INVESTOR1), bondid, 1201),
+            RuntimeError::BondHoldingLimitExceeded
+        );
+        assert_ok!(Evercity::bond_unit_order_place(Origin::signed(INVESTOR1), bondid, 1200));
+        assert_ok!(Evercity::bond_unit_order_place(Origin::signed(INVESTOR2), bondid, 1200));
+        // investor, removed from whitelist, receives nothing at allocation
+        assert_ok!(Evercity::bond_whitelist_remove(Origin::signed(ACCOUNT), bondid, INVESTOR2));
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+
+        assert_ok!(Evercity::bond_set_auditor(Origin::signed(BOND_ARRANGER), bondid, AUDITOR));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(30_000);
+        assert_ok!(Evercity::bond_activate(
+            Origin::signed(BOND_ARRANGER),
+            bondid,
+            Evercity::get_bond(&bondid).nonce
+        ));
+
+        assert_eq!(Evercity::get_bond(&bondid).issued_amount, 1200);
+        assert_eq!(Evercity::bondholder_units(&bondid, &INVESTOR1), 1200);
+        assert_eq!(Evercity::bondholder_units(&bondid, &INVESTOR2), 0);
+        assert_eq!(Evercity::reserved_everusd(&INVESTOR2), 0);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), balance2 + 1200 * PRICE);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_transfer_restriction_try_invalid() {
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let restriction = BondTransferRestrictionStruct {
+            whitelist_only: false,
+            jurisdictions: vec![*b"DE"; 33],
+            max_holding: 0,
+        };
+        assert_noop!(
+            Evercity::bond_transfer_restriction_set(Origin::signed(3), bondid, restriction.clone()),
+            RuntimeError::BondNotFound
+        );
+        assert_ok!(Evercity::bond_add_new(Origin::signed(3), bondid, get_test_bond().inner));
+        assert_noop!(
+            Evercity::bond_transfer_restriction_set(Origin::signed(3), bondid, restriction),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_noop!(
+            Evercity::bond_whitelist_add(Origin::signed(7), bondid, 4, *b"DE"),
+            RuntimeError::BondAccessDenied
+        );
+    });
+}
+
+#[test]
+fn bond_proceeds_allocated_by_category() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const AUDITOR: u64 = 5;
+    const BOND_ARRANGER: u64 = 9;
+    const PRICE: EverUSDBalance = 4_000_000_000_000;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let mut bond = get_test_bond().inner;
+        bond.use_of_proceeds = vec![
+            BondProceedsCategoryStruct { category: BondProceedsCategory::RENEWABLE_ENERGY, cap: 600_000 },
+            BondProceedsCategoryStruct { category: BondProceedsCategory::GREEN_BUILDINGS, cap: 0 },
+        ];
+        bond_grand_everusd();
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, bond));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(10_000);
+        assert_ok!(Evercity::bond_release(Origin::signed(BOND_ARRANGER), bondid, 0));
+        assert_ok!(Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, 1, 600));
+        assert_ok!(Evercity::bond_unit_package_buy(Origin::signed(INVESTOR2), bondid, 1, 600));
+        assert_ok!(Evercity::bond_set_auditor(Origin::signed(BOND_ARRANGER), bondid, AUDITOR));
+        // bond isn't active yet
+        assert_noop!(
+            Evercity::bond_proceeds_withdraw(Origin::signed(ACCOUNT), bondid, BondProceedsCategory::RENEWABLE_ENERGY, PRICE, None),
+            RuntimeError::BondStateNotPermitAction
+        );
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(30_000);
+        assert_ok!(Evercity::bond_activate(
+            Origin::signed(BOND_ARRANGER),
+            bondid,
+            Evercity::get_bond(&bondid).nonce
+        ));
+
+        // proceeds are reserved on the issuer account
+        assert_eq!(Evercity::balance_everusd(&ACCOUNT), 0);
+        assert_eq!(Evercity::reserved_everusd(&ACCOUNT), 1200 * PRICE);
+        assert_eq!(Evercity::bond_proceeds(&bondid), 1200 * PRICE);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        assert_noop!(
+            Evercity::bond_proceeds_withdraw(Origin::signed(BOND_ARRANGER), bondid, BondProceedsCategory::RENEWABLE_ENERGY, PRICE, None),
+            RuntimeError::BondAccessDenied
+        );
+        assert_noop!(
+            Evercity::bond_proceeds_withdraw(Origin::signed(ACCOUNT), bondid, BondProceedsCategory::WATER_MANAGEMENT, PRICE, None),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_ok!(Evercity::bond_proceeds_withdraw(
+            Origin::signed(ACCOUNT),
+            bondid,
+            BondProceedsCategory::RENEWABLE_ENERGY,
+            500 * PRICE,
+            None
+        ));
+        // 60% of proceeds can be allocated to renewable energy
+        assert_noop!(
+            Evercity::bond_proceeds_withdraw(Origin::signed(ACCOUNT), bondid, BondProceedsCategory::RENEWABLE_ENERGY, 221 * PRICE, None),
+            RuntimeError::ProceedsCapExceeded
+        );
+        assert_ok!(Evercity::bond_proceeds_withdraw(
+            Origin::signed(ACCOUNT),
+            bondid,
+            BondProceedsCategory::RENEWABLE_ENERGY,
+            220 * PRICE,
+            None
+        ));
+        assert_noop!(
+            Evercity::bond_proceeds_withdraw(Origin::signed(ACCOUNT), bondid, BondProceedsCategory::GREEN_BUILDINGS, 481 * PRICE, None),
+            RuntimeError::BalanceOverdraft
+        );
+        assert_ok!(Evercity::bond_proceeds_withdraw(
+            Origin::signed(ACCOUNT),
+            bondid,
+            BondProceedsCategory::GREEN_BUILDINGS,
+            480 * PRICE,
+            Some([1; 16])
+        ));
+
+        assert_eq!(Evercity::balance_everusd(&ACCOUNT), 1200 * PRICE);
+        assert_eq!(Evercity::reserved_everusd(&ACCOUNT), 0);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        let report = Evercity::get_proceeds_report(&bondid);
+        assert_eq!(report.raised, 1200 * PRICE);
+        assert_eq!(report.allocated, 1200 * PRICE);
+        assert_eq!(report.categories.len(), 2);
+        assert_eq!(report.categories[0].cap, 720 * PRICE);
+        assert_eq!(report.categories[0].allocated, 720 * PRICE);
+        assert_eq!(report.categories[1].cap, 0);
+        assert_eq!(report.categories[1].allocated, 480 * PRICE);
+        assert_eq!(report.allocations.len(), 3);
+        assert_eq!(report.allocations[2].evidence, Some([1; 16]));
+        assert_eq!(report.allocations[2].allocation_date, 30_000);
+    });
+}
+
+#[test]
+fn bond_proceeds_try_invalid_categories() {
+    let category = |category, cap| BondProceedsCategoryStruct { category, cap };
+    let mut bond = get_test_bond();
+    bond.inner.use_of_proceeds = vec![
+        category(BondProceedsCategory::RENEWABLE_ENERGY, 1_000_000),
+        category(BondProceedsCategory::OTHER, 0),
+    ];
+    assert!(bond.inner.is_valid(DEFAULT_DAY_DURATION));
+    bond.inner.use_of_proceeds = vec![category(BondProceedsCategory::RENEWABLE_ENERGY, 1_000_001)];
+    assert!(!bond.inner.is_valid(DEFAULT_DAY_DURATION));
+    // categories are unique
+    bond.inner.use_of_proceeds = vec![
+        category(BondProceedsCategory::OTHER, 100_000),
+        category(BondProceedsCategory::OTHER, 200_000),
+    ];
+    assert!(!bond.inner.is_valid(DEFAULT_DAY_DURATION));
+}
+
+#[test]
+fn bond_calendar_lists_upcoming_deadlines() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const AUDITOR: u64 = 5;
+    const MANAGER: u64 = 8;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), "BOND2".into(), get_test_bond().inner));
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let start = Evercity::get_bond(&bondid).active_start_date;
+
+        // inactive bond has no deadlines
+        assert!(Evercity::get_bond_calendar(&"BOND2".into()).is_empty());
+
+        // 12 impact reports with approvals, 12 coupon payments, maturity and finishing period end
+        let calendar = Evercity::get_bond_calendar(&bondid);
+        assert_eq!(calendar.len(), 38);
+        assert!(calendar.windows(2).all(|pair| pair[0].deadline <= pair[1].deadline));
+        assert_eq!(calendar[0].kind, BondDeadlineKind::IMPACT_REPORT);
+        assert_eq!(calendar[1].kind, BondDeadlineKind::IMPACT_REPORT_APPROVAL);
+        for deadline in &calendar[0..2] {
+            assert_eq!(deadline.bond, bondid);
+            assert_eq!(deadline.period, 0);
+            assert_eq!(deadline.opens, start + days2timestamp(110));
+            assert_eq!(deadline.deadline, start + days2timestamp(120));
+        }
+        assert_eq!(calendar[2].kind, BondDeadlineKind::COUPON_PAYMENT);
+        assert_eq!(calendar[2].period, 1);
+        assert_eq!(calendar[2].opens, start + days2timestamp(120));
+        assert_eq!(calendar[2].deadline, start + days2timestamp(127));
+        assert_eq!(calendar[36].kind, BondDeadlineKind::MATURITY);
+        assert_eq!(calendar[36].deadline, start + days2timestamp(120 + 12 * 30));
+        assert_eq!(calendar[37].kind, BondDeadlineKind::FINISHING_PERIOD_END);
+        assert_eq!(calendar[37].deadline, start + days2timestamp(120 + 12 * 30 + 14));
+
+        // passed deadlines and approved reports are omitted
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(125));
+        assert_ok!(Evercity::set_impact_data(&bondid, 1, 20000));
+        let calendar = Evercity::get_bond_calendar(&bondid);
+        assert_eq!(calendar.len(), 34);
+        assert_eq!(calendar[0].kind, BondDeadlineKind::COUPON_PAYMENT);
+        assert!(calendar.iter().all(|deadline| deadline.kind == BondDeadlineKind::COUPON_PAYMENT || deadline.period != 1));
+
+        for account in [ACCOUNT, INVESTOR1, AUDITOR] {
+            assert_eq!(Evercity::get_account_calendar(&account), calendar);
+        }
+        assert!(Evercity::get_account_calendar(&MANAGER).is_empty());
+    });
+}
+
+#[test]
+fn bond_unit_price_and_yield_to_maturity() {
+    const ACCOUNT: u64 = 3;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), "BOND2".into(), get_test_bond().inner));
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let start = Evercity::get_bond(&bondid).active_start_date;
+
+        // inactive bond has no price
+        assert_eq!(Evercity::get_bond_unit_price(&"BOND2".into(), 100), None);
+        assert_eq!(Evercity::get_yield_to_maturity(&"BOND2".into(), 100, 400_000_000_000_000), None);
+
+        // 5 days of the first period passed, impact data equals baseline
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(125));
+        assert_ok!(Evercity::set_impact_data(&bondid, 0, 20000));
+        let accrued_interest = 4_000_000_000_000 / 1000 * 2000 / 365 * 100 * 5 / 100;
+        assert_eq!(
+            Evercity::get_bond_unit_price(&bondid, 100),
+            Some(BondUnitPriceStruct {
+                interest_rate: 2000,
+                accrued_interest,
+                clean_price: 400_000_000_000_000,
+                dirty_price: 400_000_000_000_000 + accrued_interest,
+            })
+        );
+
+        // bond bought at the dirty price yields the coupon rate
+        let ytm = Evercity::get_yield_to_maturity(&bondid, 100, 400_000_000_000_000 + accrued_interest).unwrap();
+        assert!((1995..=2005).contains(&ytm));
+        // the lower price, the higher yield
+        let discount_ytm = Evercity::get_yield_to_maturity(&bondid, 100, 390_000_000_000_000).unwrap();
+        assert!(discount_ytm > ytm);
+        // negative yield and zero price are not supported
+        assert_eq!(Evercity::get_yield_to_maturity(&bondid, 100, 500_000_000_000_000), None);
+        assert_eq!(Evercity::get_yield_to_maturity(&bondid, 100, 0), None);
+    });
+}
+
+#[test]
+fn bond_portfolio_lists_account_positions() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const BIDDER: u64 = 7;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        assert_ok!(add_token(BIDDER, 50_000_000_000_000_000));
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let start = Evercity::get_bond(&bondid).active_start_date;
+
+        let portfolio = Evercity::get_portfolio(&INVESTOR1);
+        assert_eq!(portfolio.everusd_balance, Evercity::balance_everusd(&INVESTOR1));
+        assert_eq!(portfolio.positions.len(), 1);
+        assert_eq!(portfolio.positions[0].bond, bondid);
+        assert_eq!(portfolio.positions[0].state, BondState::ACTIVE);
+        assert_eq!(portfolio.positions[0].bond_units, 600);
+        assert_eq!(portfolio.positions[0].par_value, 600 * 4_000_000_000_000);
+        assert_eq!(portfolio.positions[0].unrealised_coupon, 0);
+        assert!(Evercity::get_portfolio(&BIDDER).positions.is_empty());
+
+        // account with purchase bid only
+        let bid = BondUnitPurchaseBidStruct {
+            id: 0,
+            deadline: 100000,
+            bond_units: 300,
+            amount: 300 * 3_000_000_000_000,
+        };
+        assert_ok!(Evercity::bond_unit_purchase_bid(Origin::signed(BIDDER), bondid, bid));
+        let portfolio = Evercity::get_portfolio(&BIDDER);
+        assert_eq!(portfolio.everusd_reserved, 300 * 3_000_000_000_000);
+        assert_eq!(portfolio.positions.len(), 1);
+        assert_eq!(portfolio.positions[0].bond_units, 0);
+        let bid_id = portfolio.positions[0].bids[0].id;
+        assert_ok!(Evercity::bond_unit_purchase_bid_cancel(Origin::signed(BIDDER), bondid, bid_id));
+        assert!(Evercity::get_portfolio(&BIDDER).positions.is_empty());
+
+        // coupon yield of the passed period, which hasn't been calculated yet
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(start + days2timestamp(125));
+        let portfolio = Evercity::get_portfolio(&INVESTOR1);
+        assert_eq!(
+            portfolio.positions[0].unrealised_coupon,
+            4_000_000_000_000 / 1000 * 1900 / 365 * 600 * 120 / 100
+        );
+    });
+}
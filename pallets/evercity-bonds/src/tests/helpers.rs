@@ -6,7 +6,7 @@ use frame_support::{
 use crate::tests::mock::*;
 use crate::{
     BondInnerStructOf, BondStructOf,
-    BondUnitAmount, BondUnitPackage, BondUnitSaleLotStructOf, Error, EverUSDBalance, DEFAULT_DAY_DURATION,
+    BondUnitAmount, BondUnitPackage, BondUnitPurchaseBidStructOf, BondUnitSaleLotStructOf, Error, EverUSDBalance, DEFAULT_DAY_DURATION,
 };
 
 //////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -28,6 +28,7 @@ pub type BondStruct = BondStructOf<TestRuntime>;
 pub type RuntimeError = Error<TestRuntime>;
 pub type AccountId = <TestRuntime as frame_system::Config>::AccountId;
 pub type BondUnitSaleLotStruct = BondUnitSaleLotStructOf<TestRuntime>;
+pub type BondUnitPurchaseBidStruct = BondUnitPurchaseBidStructOf<TestRuntime>;
 
 
 pub fn bond_current_period(bond: &BondStruct, now: Moment) -> u32 {
@@ -36,8 +37,9 @@ pub fn bond_current_period(bond: &BondStruct, now: Moment) -> u32 {
 
 /// Auxiliary function that replenish account balance
 pub fn add_token(id: AccountId, amount: EverUSDBalance) -> DispatchResult {
-    Evercity::token_mint_request_create_everusd(Origin::signed(id), amount)?;
-    Evercity::token_mint_request_confirm_everusd(Origin::signed(CUSTODIAN_ID), id, amount)
+    Evercity::token_mint_request_create_everusd(Origin::signed(id), amount, Vec::new())?;
+    let request_id = Evercity::last_mint_request_id();
+    Evercity::token_mint_request_confirm_everusd(Origin::signed(CUSTODIAN_ID), id, request_id, amount)
 }
 
 /// Converts days into milliseconds
@@ -73,6 +73,8 @@ parameter_types! {
     pub const MintRequestTtl: u32 = DEFAULT_DAY_DURATION as u32 * 7 * 1000;
     pub const MaxMintAmount: EverUSDBalance = EVERUSD_MAX_MINT_AMOUNT;
     pub const TimeStep: BondPeriod = DEFAULT_DAY_DURATION;
+    pub const MaxLifecycleOpsPerBlock: u32 = 50;
+    pub const ReserveAttestationTtl: u32 = DEFAULT_DAY_DURATION as u32 * 45 * 1000;
 }
 
 impl Config for TestRuntime {
@@ -81,8 +83,11 @@ impl Config for TestRuntime {
     type MintRequestTtl = MintRequestTtl;
     type MaxMintAmount = MaxMintAmount;
     type TimeStep = TimeStep;
+    type ReserveAttestationTtl = ReserveAttestationTtl;
+    type MaxLifecycleOpsPerBlock = MaxLifecycleOpsPerBlock;
     type WeightInfo = ();
     type OnAddBond = ();
+    type BondTransferGuard = Evercity;
 }
 
 parameter_types! {
@@ -186,6 +191,14 @@ pub fn get_test_bond() -> BondStruct {
             bond_units_maxcap_amount: 1800,
             bond_units_base_price: 4_000_000_000_000,
             carbon_metadata: Default::default(),
+            call_schedule: None,
+            principal_schedule: None,
+            discount: None,
+            impact_kpis: Vec::new(),
+            interest_rate_curve: Default::default(),
+            bookbuilding: None,
+            fees: Vec::new(),
+            use_of_proceeds: Vec::new(),
         },
 
         issuer: 0,
@@ -237,6 +250,14 @@ pub fn get_test_bond_stable() -> BondStruct {
             bond_units_maxcap_amount: 1800,
             bond_units_base_price: 4_000_000_000_000,
             carbon_metadata: Default::default(),
+            call_schedule: None,
+            principal_schedule: None,
+            discount: None,
+            impact_kpis: Vec::new(),
+            interest_rate_curve: Default::default(),
+            bookbuilding: None,
+            fees: Vec::new(),
+            use_of_proceeds: Vec::new(),
         },
 
         issuer: 0,
@@ -296,7 +317,15 @@ pub fn get_test_bond_stable_carbon() -> BondStruct {
                     project_developer: None,
                 },
                 account_investments: Default::default(),
-            })
+            }),
+            call_schedule: None,
+            principal_schedule: None,
+            discount: None,
+            impact_kpis: Vec::new(),
+            interest_rate_curve: Default::default(),
+            bookbuilding: None,
+            fees: Vec::new(),
+            use_of_proceeds: Vec::new(),
         },
 
         issuer: 0,
@@ -14,16 +14,22 @@ fn it_token_mint_create_with_confirm() {
     new_test_ext().execute_with(|| {
         assert_ok!(Evercity::token_mint_request_create_everusd(
             Origin::signed(ACCOUNT),
-            100000
+            100000,
+            b"PAYMENT-0001".to_vec()
         ));
         assert_eq!(Evercity::total_supply(), 0);
+        let request = Evercity::mint_request_everusd(ACCOUNT, 1).unwrap();
+        assert_eq!(request.id, 1);
+        assert_eq!(request.payment_reference, b"PAYMENT-0001".to_vec());
 
         assert_ok!(Evercity::token_mint_request_confirm_everusd(
             Origin::signed(CUSTODIAN_ID),
             ACCOUNT,
+            1,
             100000
         ));
         assert_eq!(Evercity::total_supply(), 100000);
+        assert!(Evercity::mint_request_everusd(ACCOUNT, 1).is_none());
     });
 }
 
@@ -33,17 +39,19 @@ fn it_token_mint_create_with_revoke() {
     new_test_ext().execute_with(|| {
         assert_ok!(Evercity::token_mint_request_create_everusd(
             Origin::signed(ACCOUNT), // INVESTOR
-            100000
+            100000,
+            Vec::new()
         ));
 
         assert_ok!(Evercity::token_mint_request_revoke_everusd(Origin::signed(
             ACCOUNT
-        ),));
+        ), 1));
 
         assert_noop!(
             Evercity::token_mint_request_confirm_everusd(
                 Origin::signed(CUSTODIAN_ID),
                 ACCOUNT,
+                1,
                 100000
             ),
             RuntimeError::MintRequestDoesntExist
@@ -57,16 +65,18 @@ fn it_token_mint_create_with_decline() {
     new_test_ext().execute_with(|| {
         assert_ok!(Evercity::token_mint_request_create_everusd(
             Origin::signed(ACCOUNT),
-            100000
+            100000,
+            Vec::new()
         ));
 
         assert_ok!(Evercity::token_mint_request_decline_everusd(
             Origin::signed(CUSTODIAN_ID),
-            ACCOUNT
+            ACCOUNT,
+            1
         ));
 
         assert_noop!(
-            Evercity::token_mint_request_revoke_everusd(Origin::signed(ACCOUNT)),
+            Evercity::token_mint_request_revoke_everusd(Origin::signed(ACCOUNT), 1),
             RuntimeError::MintRequestDoesntExist
         );
     });
@@ -77,7 +87,7 @@ fn it_token_mint_create_denied() {
     const ACCOUNT: u64 = 5; // AUDITOR
     new_test_ext().execute_with(|| {
         assert_noop!(
-            Evercity::token_mint_request_create_everusd(Origin::signed(ACCOUNT), 100000),
+            Evercity::token_mint_request_create_everusd(Origin::signed(ACCOUNT), 100000, Vec::new()),
             RuntimeError::AccountNotAuthorized
         );
     });
@@ -87,13 +97,16 @@ fn it_token_mint_create_denied() {
 fn it_token_mint_create_hasty() {
     const ACCOUNT: u64 = 4; // INVESTOR
     new_test_ext().execute_with(|| {
-        assert_ok!(Evercity::token_mint_request_create_everusd(
-            Origin::signed(ACCOUNT),
-            100000
-        ));
+        for _ in 0..crate::token::MAX_TOKEN_REQUESTS_PER_ACCOUNT {
+            assert_ok!(Evercity::token_mint_request_create_everusd(
+                Origin::signed(ACCOUNT),
+                100000,
+                Vec::new()
+            ));
+        }
 
         assert_noop!(
-            Evercity::token_mint_request_create_everusd(Origin::signed(ACCOUNT), 10),
+            Evercity::token_mint_request_create_everusd(Origin::signed(ACCOUNT), 10, Vec::new()),
             RuntimeError::MintRequestAlreadyExist
         );
 
@@ -103,7 +116,8 @@ fn it_token_mint_create_hasty() {
 
         assert_ok!(Evercity::token_mint_request_create_everusd(
             Origin::signed(ACCOUNT),
-            10
+            10,
+            Vec::new()
         ));
     });
 }
@@ -115,31 +129,90 @@ fn it_token_mint_create_toolarge() {
         assert_noop!(
             Evercity::token_mint_request_create_everusd(
                 Origin::signed(ACCOUNT), // INVESTOR
-                EVERUSD_MAX_MINT_AMOUNT + 1
+                EVERUSD_MAX_MINT_AMOUNT + 1,
+                Vec::new()
+            ),
+            RuntimeError::MintRequestParamIncorrect
+        );
+        assert_noop!(
+            Evercity::token_mint_request_create_everusd(
+                Origin::signed(ACCOUNT),
+                1000,
+                vec![0; crate::token::MAX_PAYMENT_REFERENCE_LEN + 1]
             ),
             RuntimeError::MintRequestParamIncorrect
         );
     });
 }
 
+#[test]
+fn it_token_mint_multiple_requests_partial_confirm() {
+    const ACCOUNT: u64 = 4; // INVESTOR
+    new_test_ext().execute_with(|| {
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(ACCOUNT),
+            1000,
+            b"PAYMENT-0001".to_vec()
+        ));
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(ACCOUNT),
+            500,
+            b"PAYMENT-0002".to_vec()
+        ));
+
+        // first payment arrives in two transfers
+        assert_ok!(Evercity::token_mint_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            ACCOUNT,
+            1,
+            600
+        ));
+        assert_eq!(Evercity::mint_request_everusd(ACCOUNT, 1).unwrap().confirmed, 600);
+        assert_noop!(
+            Evercity::token_mint_request_confirm_everusd(
+                Origin::signed(CUSTODIAN_ID),
+                ACCOUNT,
+                1,
+                401
+            ),
+            RuntimeError::MintRequestParamIncorrect
+        );
+        assert_ok!(Evercity::token_mint_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            ACCOUNT,
+            1,
+            400
+        ));
+        assert!(Evercity::mint_request_everusd(ACCOUNT, 1).is_none());
+
+        // second request is still outstanding
+        assert_eq!(Evercity::mint_request_everusd(ACCOUNT, 2).unwrap().remaining(), 500);
+        assert_eq!(Evercity::balance_everusd(&ACCOUNT), 1000);
+        assert_eq!(Evercity::total_supply(), 1000);
+    });
+}
+
 #[test]
 fn it_token_burn_mint_overflow() {
     const ACCOUNT: u64 = 4;
     new_test_ext().execute_with(|| {
         assert_ok!(Evercity::token_mint_request_create_everusd(
             Origin::signed(ACCOUNT),
-            1000
+            1000,
+            Vec::new()
         ));
 
         assert_ok!(Evercity::token_mint_request_confirm_everusd(
             Origin::signed(CUSTODIAN_ID),
             ACCOUNT,
+            1,
             1000
         ));
         assert_noop!(
             Evercity::token_burn_request_create_everusd(
                 Origin::signed(ACCOUNT),
-                EverUSDBalance::MAX - 1000
+                EverUSDBalance::MAX - 1000,
+                Vec::new()
             ),
             RuntimeError::BalanceOverdraft
         );
@@ -160,13 +233,15 @@ fn it_token_mint_try_confirm_expired() {
     new_test_ext().execute_with(|| {
         assert_ok!(Evercity::token_mint_request_create_everusd(
             Origin::signed(ACCOUNT), // INVESTOR
-            1000
+            1000,
+            Vec::new()
         ));
         <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(days2timestamp(10));
         assert_noop!(
             Evercity::token_mint_request_confirm_everusd(
                 Origin::signed(CUSTODIAN_ID),
                 ACCOUNT,
+                1,
                 1000
             ),
             RuntimeError::MintRequestObsolete
@@ -185,7 +260,8 @@ fn it_token_burn_create_with_confirm() {
 
         assert_ok!(Evercity::token_burn_request_create_everusd(
             Origin::signed(ACCOUNT),
-            10000
+            10000,
+            Vec::new()
         ));
 
         assert_eq!(Evercity::total_supply(), 10000);
@@ -193,6 +269,7 @@ fn it_token_burn_create_with_confirm() {
         assert_ok!(Evercity::token_burn_request_confirm_everusd(
             Origin::signed(CUSTODIAN_ID),
             ACCOUNT,
+            1,
             10000
         ));
 
@@ -202,6 +279,7 @@ fn it_token_burn_create_with_confirm() {
             Evercity::token_burn_request_confirm_everusd(
                 Origin::signed(CUSTODIAN_ID),
                 ACCOUNT,
+                1,
                 10000
             ),
             RuntimeError::BurnRequestDoesntExist
@@ -218,9 +296,25 @@ fn it_token_burn_create_overrun() {
         assert_ok!(add_token(ACCOUNT, BALANCE));
 
         assert_noop!(
-            Evercity::token_burn_request_create_everusd(Origin::signed(ACCOUNT), BALANCE + 1),
+            Evercity::token_burn_request_create_everusd(Origin::signed(ACCOUNT), BALANCE + 1, Vec::new()),
+            RuntimeError::BalanceOverdraft
+        );
+
+        // outstanding burn requests can't exceed the balance in total
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(ACCOUNT),
+            6000,
+            Vec::new()
+        ));
+        assert_noop!(
+            Evercity::token_burn_request_create_everusd(Origin::signed(ACCOUNT), 4001, Vec::new()),
             RuntimeError::BalanceOverdraft
         );
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(ACCOUNT),
+            4000,
+            Vec::new()
+        ));
     });
 }
 
@@ -233,17 +327,19 @@ fn it_token_burn_create_with_revoke() {
 
         assert_ok!(Evercity::token_burn_request_create_everusd(
             Origin::signed(ACCOUNT),
-            10000
+            10000,
+            Vec::new()
         ));
 
         assert_ok!(Evercity::token_burn_request_revoke_everusd(Origin::signed(
             ACCOUNT
-        ),));
+        ), 1));
 
         assert_noop!(
             Evercity::token_burn_request_confirm_everusd(
                 Origin::signed(CUSTODIAN_ID),
                 ACCOUNT,
+                1,
                 10000
             ),
             RuntimeError::BurnRequestDoesntExist
@@ -258,13 +354,15 @@ fn it_token_burn_try_confirm_expired() {
         assert_ok!(add_token(ACCOUNT, 10000));
         assert_ok!(Evercity::token_burn_request_create_everusd(
             Origin::signed(ACCOUNT), // INVESTOR
-            1000
+            1000,
+            Vec::new()
         ));
         <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(days2timestamp(10));
         assert_noop!(
             Evercity::token_burn_request_confirm_everusd(
                 Origin::signed(CUSTODIAN_ID),
                 ACCOUNT,
+                1,
                 1000
             ),
             RuntimeError::BurnRequestObsolete
@@ -277,14 +375,17 @@ fn it_token_burn_hasty() {
     const ACCOUNT: u64 = 4; // INVESTOR
 
     new_test_ext().execute_with(|| {
-        assert_ok!(add_token(ACCOUNT, 10000));
+        assert_ok!(add_token(ACCOUNT, 100000));
 
-        assert_ok!(Evercity::token_burn_request_create_everusd(
-            Origin::signed(ACCOUNT),
-            5000
-        ));
+        for _ in 0..crate::token::MAX_TOKEN_REQUESTS_PER_ACCOUNT {
+            assert_ok!(Evercity::token_burn_request_create_everusd(
+                Origin::signed(ACCOUNT),
+                5000,
+                Vec::new()
+            ));
+        }
         assert_noop!(
-            Evercity::token_burn_request_create_everusd(Origin::signed(ACCOUNT), 10000),
+            Evercity::token_burn_request_create_everusd(Origin::signed(ACCOUNT), 10000, Vec::new()),
             RuntimeError::BurnRequestAlreadyExist
         );
 
@@ -294,7 +395,368 @@ fn it_token_burn_hasty() {
 
         assert_ok!(Evercity::token_burn_request_create_everusd(
             Origin::signed(ACCOUNT),
-            10000
+            10000,
+            Vec::new()
         ));
     })
-}
\ No newline at end of file
+}
+
+#[test]
+fn it_token_everusd_reconciliation() {
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(add_token(INVESTOR1, 10000));
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(INVESTOR2),
+            3000,
+            b"PAYMENT-0002".to_vec()
+        ));
+        assert_ok!(Evercity::token_mint_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            INVESTOR2,
+            2,
+            1000
+        ));
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(INVESTOR1),
+            4000,
+            Vec::new()
+        ));
+        assert_ok!(Evercity::token_burn_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            INVESTOR1,
+            1,
+            2500
+        ));
+
+        let reconciliation = Evercity::get_everusd_reconciliation();
+        assert_eq!(reconciliation.total_supply, 8500);
+        assert_eq!(reconciliation.confirmed_mint, 11000);
+        assert_eq!(reconciliation.confirmed_burn, 2500);
+        assert_eq!(reconciliation.pending_mint, 2000);
+        assert_eq!(reconciliation.pending_burn, 1500);
+        assert!(reconciliation.reconciled);
+    });
+}
+
+#[test]
+fn it_token_expired_requests_purged_on_initialize() {
+    use frame_support::traits::Hooks;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(add_token(INVESTOR2, 10000));
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(INVESTOR1),
+            1000,
+            Vec::new()
+        ));
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(INVESTOR2),
+            5000,
+            Vec::new()
+        ));
+
+        // requests are alive
+        Evercity::on_initialize(1);
+        assert_eq!(Evercity::mint_request_everusd(INVESTOR1, 2).unwrap().amount, 1000);
+        assert_eq!(Evercity::burn_request_everusd(INVESTOR2, 1).unwrap().amount, 5000);
+
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(days2timestamp(10));
+        Evercity::on_initialize(2);
+        assert_noop!(
+            Evercity::token_mint_request_revoke_everusd(Origin::signed(INVESTOR1), 2),
+            RuntimeError::MintRequestDoesntExist
+        );
+        assert_noop!(
+            Evercity::token_burn_request_revoke_everusd(Origin::signed(INVESTOR2), 1),
+            RuntimeError::BurnRequestDoesntExist
+        );
+        // burn request doesn't touch the balance
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), 10000);
+    });
+}
+
+#[test]
+fn it_token_transfer_everusd() {
+    const ISSUER: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(add_token(INVESTOR1, 10000));
+        assert_ok!(Evercity::everusd_transfer(
+            Origin::signed(INVESTOR1),
+            INVESTOR2,
+            3000,
+            Some(b"invoice #42".to_vec())
+        ));
+        assert_ok!(Evercity::everusd_transfer(
+            Origin::signed(INVESTOR2),
+            ISSUER,
+            1000,
+            None
+        ));
+
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), 7000);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), 2000);
+        assert_eq!(Evercity::balance_everusd(&ISSUER), 1000);
+        assert_eq!(Evercity::total_supply(), 10000);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn it_token_transfer_everusd_denied() {
+    const MASTER: u64 = 1;
+    const INVESTOR1: u64 = 4;
+    const AUDITOR: u64 = 5;
+    const INVESTOR2: u64 = 6;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(add_token(INVESTOR1, 10000));
+        // recipient cannot hold EverUSD
+        assert_noop!(
+            Evercity::everusd_transfer(Origin::signed(INVESTOR1), AUDITOR, 1000, None),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_noop!(
+            Evercity::everusd_transfer(Origin::signed(MASTER), INVESTOR1, 1000, None),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_noop!(
+            Evercity::everusd_transfer(Origin::signed(INVESTOR1), INVESTOR2, 10001, None),
+            RuntimeError::BalanceOverdraft
+        );
+        assert_noop!(
+            Evercity::everusd_transfer(Origin::signed(INVESTOR1), INVESTOR1, 1000, None),
+            RuntimeError::InvalidAction
+        );
+        assert_noop!(
+            Evercity::everusd_transfer(Origin::signed(INVESTOR1), INVESTOR2, 0, None),
+            RuntimeError::InvalidAction
+        );
+        assert_noop!(
+            Evercity::everusd_transfer(
+                Origin::signed(INVESTOR1),
+                INVESTOR2,
+                1000,
+                Some(vec![0; crate::token::MAX_TRANSFER_MEMO_LEN + 1])
+            ),
+            RuntimeError::InvalidAction
+        );
+    });
+}
+
+#[test]
+fn it_token_fungible_traits() {
+    use frame_support::traits::tokens::fungible::{Inspect, InspectHold, Mutate, MutateHold, Transfer};
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(<Evercity as Mutate<_>>::mint_into(&INVESTOR1, 10000));
+        assert_eq!(<Evercity as Inspect<_>>::total_issuance(), 10000);
+        assert_eq!(<Evercity as Inspect<_>>::balance(&INVESTOR1), 10000);
+
+        assert_ok!(<Evercity as Transfer<_>>::transfer(&INVESTOR1, &INVESTOR2, 4000, false));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), 6000);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), 4000);
+        assert!(<Evercity as Transfer<_>>::transfer(&INVESTOR1, &INVESTOR2, 6001, false).is_err());
+
+        assert_ok!(<Evercity as MutateHold<_>>::hold(&INVESTOR2, 3000));
+        assert_eq!(<Evercity as InspectHold<_>>::balance_on_hold(&INVESTOR2), 3000);
+        assert_eq!(<Evercity as Inspect<_>>::balance(&INVESTOR2), 1000);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        assert_ok!(<Evercity as MutateHold<_>>::transfer_held(&INVESTOR2, &INVESTOR1, 1000, false, false));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), 7000);
+        assert!(<Evercity as MutateHold<_>>::release(&INVESTOR2, 2001, false).is_err());
+        assert_eq!(<Evercity as MutateHold<_>>::release(&INVESTOR2, 5000, true), Ok(2000));
+        assert_eq!(<Evercity as InspectHold<_>>::balance_on_hold(&INVESTOR2), 0);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), 3000);
+
+        assert_eq!(<Evercity as Mutate<_>>::burn_from(&INVESTOR1, 7000), Ok(7000));
+        assert_eq!(<Evercity as Inspect<_>>::total_issuance(), 3000);
+        assert!(Evercity::evercity_balance().is_ok());
+        assert!(Evercity::get_everusd_reconciliation().reconciled);
+    });
+}
+
+#[test]
+fn it_token_reserve_attestation_limits_mint() {
+    const MASTER: u64 = 1;
+    const INVESTOR1: u64 = 4;
+    const AUDITOR: u64 = 5;
+    const STATEMENT: [u8; 16] = [1; 16];
+
+    new_test_ext().execute_with(|| {
+        // minting isn't restricted before the first signed attestation
+        assert_ok!(add_token(INVESTOR1, 10000));
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(days2timestamp(31));
+
+        assert_noop!(
+            Evercity::reserve_attestation_publish(
+                Origin::signed(MASTER), 15000, 0, days2timestamp(30), AUDITOR, STATEMENT
+            ),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_noop!(
+            Evercity::reserve_attestation_publish(
+                Origin::signed(CUSTODIAN_ID), 15000, 0, days2timestamp(30), INVESTOR1, STATEMENT
+            ),
+            RuntimeError::ReserveAttestationParamIncorrect
+        );
+        assert_noop!(
+            Evercity::reserve_attestation_publish(
+                Origin::signed(CUSTODIAN_ID), 15000, 0, days2timestamp(32), AUDITOR, STATEMENT
+            ),
+            RuntimeError::ReserveAttestationParamIncorrect
+        );
+        assert_ok!(Evercity::reserve_attestation_publish(
+            Origin::signed(CUSTODIAN_ID), 15000, 0, days2timestamp(30), AUDITOR, STATEMENT
+        ));
+        assert_eq!(Evercity::reserve_attestation(1).unwrap().supply, 10000);
+        assert!(Evercity::get_latest_reserve_attestation().is_none());
+
+        assert_noop!(
+            Evercity::reserve_attestation_sign(Origin::signed(INVESTOR1), 1),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_ok!(Evercity::reserve_attestation_sign(Origin::signed(AUDITOR), 1));
+        assert_noop!(
+            Evercity::reserve_attestation_sign(Origin::signed(AUDITOR), 1),
+            RuntimeError::ReserveAttestationAlreadySigned
+        );
+        assert_eq!(Evercity::get_latest_reserve_attestation().unwrap().id, 1);
+
+        // reserve covers 5000 more EverUSD
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(INVESTOR1),
+            6000,
+            Vec::new()
+        ));
+        assert_noop!(
+            Evercity::token_mint_request_confirm_everusd(Origin::signed(CUSTODIAN_ID), INVESTOR1, 2, 6000),
+            RuntimeError::ReserveInsufficient
+        );
+        assert_ok!(Evercity::token_mint_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            INVESTOR1,
+            2,
+            5000
+        ));
+
+        // attestation becomes stale
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(days2timestamp(76));
+        assert_ok!(Evercity::reserve_attestation_publish(
+            Origin::signed(CUSTODIAN_ID), 20000, days2timestamp(30), days2timestamp(75), AUDITOR, STATEMENT
+        ));
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(INVESTOR1),
+            1000,
+            Vec::new()
+        ));
+        assert_noop!(
+            Evercity::token_mint_request_confirm_everusd(Origin::signed(CUSTODIAN_ID), INVESTOR1, 3, 1000),
+            RuntimeError::ReserveAttestationStale
+        );
+
+        assert_ok!(Evercity::reserve_attestation_sign(Origin::signed(AUDITOR), 2));
+        assert_ok!(Evercity::token_mint_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            INVESTOR1,
+            3,
+            1000
+        ));
+        assert_eq!(Evercity::total_supply(), 16000);
+    });
+}
+
+#[test]
+fn it_token_rolling_window_limits() {
+    use crate::token::EverUSDLimitStruct;
+    use pallet_evercity_accounts::accounts::{AUDITOR_ROLE_MASK, INVESTOR_ROLE_MASK};
+    const MASTER: u64 = 1;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+
+    new_test_ext().execute_with(|| {
+        let global = EverUSDLimitStruct { window: 1, mint: 15000, burn: 0 };
+        assert_noop!(
+            Evercity::everusd_limit_set_global(Origin::signed(CUSTODIAN_ID), global.clone()),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_noop!(
+            Evercity::everusd_limit_set_global(Origin::signed(MASTER), EverUSDLimitStruct { window: 0, ..global.clone() }),
+            RuntimeError::EverUSDLimitParamIncorrect
+        );
+        assert_ok!(Evercity::everusd_limit_set_global(Origin::signed(MASTER), global));
+
+        // global limit is shared by all accounts
+        assert_ok!(add_token(INVESTOR1, 10000));
+        assert_ok!(Evercity::token_mint_request_create_everusd(Origin::signed(INVESTOR2), 6000, Vec::new()));
+        assert_noop!(
+            Evercity::token_mint_request_confirm_everusd(Origin::signed(CUSTODIAN_ID), INVESTOR2, 2, 6000),
+            RuntimeError::MintLimitExceeded
+        );
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(days2timestamp(1));
+        assert_ok!(Evercity::token_mint_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            INVESTOR2,
+            2,
+            6000
+        ));
+
+        // quota of each Investor account
+        let quota = EverUSDLimitStruct { window: 3, mint: 8000, burn: 3000 };
+        assert_noop!(
+            Evercity::everusd_limit_set_role(Origin::signed(MASTER), AUDITOR_ROLE_MASK, Some(quota.clone())),
+            RuntimeError::EverUSDLimitParamIncorrect
+        );
+        assert_ok!(Evercity::everusd_limit_set_role(Origin::signed(MASTER), INVESTOR_ROLE_MASK, Some(quota)));
+        assert_ok!(Evercity::token_mint_request_create_everusd(Origin::signed(INVESTOR2), 3000, Vec::new()));
+        assert_noop!(
+            Evercity::token_mint_request_confirm_everusd(Origin::signed(CUSTODIAN_ID), INVESTOR2, 3, 3000),
+            RuntimeError::MintLimitExceeded
+        );
+
+        // account limit replaces the quota
+        assert_ok!(Evercity::everusd_limit_set_account(
+            Origin::signed(MASTER),
+            INVESTOR2,
+            Some(EverUSDLimitStruct { window: 3, mint: 20000, burn: 0 })
+        ));
+        assert_ok!(Evercity::token_mint_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            INVESTOR2,
+            3,
+            3000
+        ));
+
+        assert_ok!(Evercity::token_burn_request_create_everusd(Origin::signed(INVESTOR1), 4000, Vec::new()));
+        assert_ok!(Evercity::token_burn_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            INVESTOR1,
+            1,
+            3000
+        ));
+        assert_noop!(
+            Evercity::token_burn_request_confirm_everusd(Origin::signed(CUSTODIAN_ID), INVESTOR1, 1, 1000),
+            RuntimeError::BurnLimitExceeded
+        );
+
+        // burned amount leaves the window
+        <pallet_timestamp::Pallet<TestRuntime>>::set_timestamp(days2timestamp(4));
+        assert_ok!(Evercity::token_burn_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            INVESTOR1,
+            1,
+            1000
+        ));
+        assert_eq!(Evercity::total_supply(), 15000);
+    });
+}
@@ -1,24 +1,54 @@
 use crate::bond::{EverUSDBalance, Expired};
 use frame_support::{
     codec::{Decode, Encode},
+    dispatch::Vec,
     sp_runtime::{
         RuntimeDebug,
     },
 };
+use pallet_evercity_filesign::file::FileId;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use scale_info::TypeInfo;
 
+/// Max length of the payment reference, attached to EverUSD transfer
+pub const MAX_TRANSFER_MEMO_LEN: usize = 64;
+
+/// Max length of the bank payment reference (or hash of the bank statement),
+/// attached to EverUSD mint or burn request
+pub const MAX_PAYMENT_REFERENCE_LEN: usize = 64;
+
+/// Max number of outstanding mint (and, separately, burn) requests of one account
+pub const MAX_TOKEN_REQUESTS_PER_ACCOUNT: usize = 16;
+
+pub type TokenMintRequestId = u64;
+pub type TokenBurnRequestId = u64;
+pub type ReserveAttestationId = u64;
+
 /// Structure, created by Issuer or Investor to receive EverUSD on her balance
 /// by paying USD to Custodian. Then Custodian confirms request, adding corresponding
 /// amount to mint request creator's balance
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug, TypeInfo)]
 pub struct TokenMintRequestStruct<Moment> {
+    #[codec(compact)]
+    pub id: TokenMintRequestId,
     #[codec(compact)]
     pub amount: EverUSDBalance,
+    /// amount, already confirmed by Custodian
+    #[codec(compact)]
+    pub confirmed: EverUSDBalance,
     #[codec(compact)]
     pub deadline: Moment,
+    /// opaque bank payment reference or hash of the bank statement
+    pub payment_reference: Vec<u8>,
+}
+
+impl<Moment> TokenMintRequestStruct<Moment> {
+    /// Amount, that is not confirmed yet
+    pub fn remaining(&self) -> EverUSDBalance {
+        self.amount.saturating_sub(self.confirmed)
+    }
 }
 
 impl<Moment: core::cmp::PartialOrd> Expired<Moment> for TokenMintRequestStruct<Moment> {
@@ -35,10 +65,24 @@ pub type TokenMintRequestStructOf<T> =
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug, TypeInfo)]
 pub struct TokenBurnRequestStruct<Moment> {
+    #[codec(compact)]
+    pub id: TokenBurnRequestId,
     #[codec(compact)]
     pub amount: EverUSDBalance,
+    /// amount, already confirmed by Custodian
+    #[codec(compact)]
+    pub confirmed: EverUSDBalance,
     #[codec(compact)]
     pub deadline: Moment,
+    /// opaque bank payment reference or hash of the bank statement
+    pub payment_reference: Vec<u8>,
+}
+
+impl<Moment> TokenBurnRequestStruct<Moment> {
+    /// Amount, that is not confirmed yet
+    pub fn remaining(&self) -> EverUSDBalance {
+        self.amount.saturating_sub(self.confirmed)
+    }
 }
 
 impl<Moment: core::cmp::PartialOrd> Expired<Moment> for TokenBurnRequestStruct<Moment> {
@@ -48,4 +92,124 @@ impl<Moment: core::cmp::PartialOrd> Expired<Moment> for TokenBurnRequestStruct<M
 }
 
 pub type TokenBurnRequestStructOf<T> =
-    TokenBurnRequestStruct<<T as pallet_timestamp::Config>::Moment>;
\ No newline at end of file
+    TokenBurnRequestStruct<<T as pallet_timestamp::Config>::Moment>;
+/// Reconciliation of the EverUSD total supply against the mint and burn
+/// requests, confirmed by Custodian. EverUSD, minted or burned bypassing
+/// the requests, breaks the reconciliation
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct EverUSDReconciliationStruct {
+    /// total supply of EverUSD
+    #[codec(compact)]
+    pub total_supply: EverUSDBalance,
+    /// total amount of confirmed mint requests
+    #[codec(compact)]
+    pub confirmed_mint: EverUSDBalance,
+    /// total amount of confirmed burn requests
+    #[codec(compact)]
+    pub confirmed_burn: EverUSDBalance,
+    /// amount of outstanding mint requests, not confirmed yet
+    #[codec(compact)]
+    pub pending_mint: EverUSDBalance,
+    /// amount of outstanding burn requests, not confirmed yet
+    #[codec(compact)]
+    pub pending_burn: EverUSDBalance,
+    /// total supply is equal to confirmed mint minus confirmed burn
+    pub reconciled: bool,
+}
+
+/// Custodian's attestation of the USD reserve, backing EverUSD at the end of the
+/// attestation period. Attestation becomes effective after the Auditor, named by
+/// Custodian, signs it
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ReserveAttestationStruct<AccountId, Moment> {
+    #[codec(compact)]
+    pub id: ReserveAttestationId,
+    pub custodian: AccountId,
+    pub auditor: AccountId,
+    /// USD reserve, held by Custodian, in EverUSD units
+    #[codec(compact)]
+    pub reserve: EverUSDBalance,
+    /// total supply of EverUSD at the moment of publication
+    #[codec(compact)]
+    pub supply: EverUSDBalance,
+    #[codec(compact)]
+    pub period_start: Moment,
+    #[codec(compact)]
+    pub period_end: Moment,
+    /// file (stored in filesign) with the bank statement
+    pub statement: FileId,
+    #[codec(compact)]
+    pub created_at: Moment,
+    /// attestation is co-signed by the Auditor
+    pub signed: bool,
+}
+
+pub type ReserveAttestationStructOf<T> =
+    ReserveAttestationStruct<<T as frame_system::Config>::AccountId, <T as pallet_timestamp::Config>::Moment>;
+
+/// Max length of the rolling window of EverUSD limits, in time steps (days)
+pub const MAX_EVERUSD_LIMIT_WINDOW: u32 = 366;
+
+/// Limit of EverUSD, minted and burned by confirmed requests in the rolling
+/// window of "window" time steps (days). Zero amount means no limit
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct EverUSDLimitStruct {
+    #[codec(compact)]
+    pub window: u32,
+    #[codec(compact)]
+    pub mint: EverUSDBalance,
+    #[codec(compact)]
+    pub burn: EverUSDBalance,
+}
+
+impl EverUSDLimitStruct {
+    pub fn is_valid(&self) -> bool {
+        self.window > 0 && self.window <= MAX_EVERUSD_LIMIT_WINDOW
+    }
+
+    /// Checks that "amount" can be minted (or burned), when "flows" are
+    /// already recorded before the time step "step"
+    pub fn allows(&self, flows: &[EverUSDFlowStruct], step: u64, mint: bool, amount: EverUSDBalance) -> bool {
+        let cap = if mint { self.mint } else { self.burn };
+        if cap == 0 {
+            return true;
+        }
+        let used: EverUSDBalance = flows
+            .iter()
+            .filter(|flow| flow.step + self.window as u64 > step)
+            .map(|flow| if mint { flow.minted } else { flow.burned })
+            .sum();
+        used.saturating_add(amount) <= cap
+    }
+}
+
+/// EverUSD, minted and burned by confirmed requests during one time step
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct EverUSDFlowStruct {
+    #[codec(compact)]
+    pub step: u64,
+    #[codec(compact)]
+    pub minted: EverUSDBalance,
+    #[codec(compact)]
+    pub burned: EverUSDBalance,
+}
+
+/// Records EverUSD, minted or burned at the time step "step", and drops
+/// the steps, which are out of the longest window
+pub fn record_everusd_flow(flows: &mut Vec<EverUSDFlowStruct>, step: u64, mint: bool, amount: EverUSDBalance) {
+    flows.retain(|flow| flow.step + MAX_EVERUSD_LIMIT_WINDOW as u64 > step);
+    if flows.last().map_or(true, |flow| flow.step != step) {
+        flows.push(EverUSDFlowStruct { step, ..Default::default() });
+    }
+    if let Some(flow) = flows.last_mut() {
+        if mint {
+            flow.minted = flow.minted.saturating_add(amount);
+        } else {
+            flow.burned = flow.burned.saturating_add(amount);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! RPC interface for the Evercity carbon credits pallet.
+//! Exposes `CarbonCreditsApi` runtime calls under the `carbonCredits_*` namespace,
+//! including paged listings of projects, lots and batch assets.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use pallet_evercity_bonds::EverUSDBalance;
+use pallet_evercity_carbon_credits::{
+    burn_certificate::CarbonCreditsBurnCertificate,
+    carbon_credits_passport::CarbonCreditsPassport,
+    cc_package_lot::CarbonCreditsPackageLot,
+    external_carbon_units::{BatchAsset, BatchAssetId},
+    portfolio::AccountPortfolio,
+    project::{ProjectId, ProjectStruct},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_evercity_carbon_credits::runtime_api::CarbonCreditsApi as CarbonCreditsRuntimeApi;
+
+type LotsPage<AccountId, Moment, AssetId, Balance> =
+    Vec<(AccountId, AssetId, Vec<CarbonCreditsPackageLot<AccountId, Moment, Balance, EverUSDBalance>>)>;
+
+#[rpc(client, server)]
+pub trait CarbonCreditsApi<BlockHash, AccountId, Moment, AssetId, Balance>
+where
+    AccountId: PartialEq + Clone,
+    Balance: Clone,
+{
+    /// Returns project by id
+    #[method(name = "carbonCredits_getProject")]
+    fn get_project(
+        &self,
+        id: ProjectId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<ProjectStruct<AccountId, Moment, Balance>>>;
+
+    /// Returns up to `limit` projects starting from project id `start`
+    #[method(name = "carbonCredits_getProjects")]
+    fn get_projects(
+        &self,
+        start: ProjectId,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(ProjectId, ProjectStruct<AccountId, Moment, Balance>)>>;
+
+    /// Returns carbon credits passport of the asset
+    #[method(name = "carbonCredits_getPassport")]
+    fn get_passport(
+        &self,
+        asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<CarbonCreditsPassport<AssetId>>>;
+
+    /// Returns burn certificates of the account
+    #[method(name = "carbonCredits_getBurnCertificates")]
+    fn get_burn_certificates(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<CarbonCreditsBurnCertificate<AssetId, Balance>>>;
+
+    /// Returns a page of carbon credits sale lots grouped by seller and asset
+    #[method(name = "carbonCredits_getLots")]
+    fn get_carbon_credit_lots(
+        &self,
+        offset: u32,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<LotsPage<AccountId, Moment, AssetId, Balance>>;
+
+    /// Returns a page of external batch assets
+    #[method(name = "carbonCredits_getBatchAssets")]
+    fn get_batch_assets(
+        &self,
+        offset: u32,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(BatchAssetId, BatchAsset<AccountId>)>>;
+
+    /// Returns statement of the account: EverUSD, bond positions, carbon credits, lots and burn certificates
+    #[method(name = "carbonCredits_getAccountPortfolio")]
+    fn get_account_portfolio(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<AccountPortfolio<AccountId, Moment, AssetId, Balance>>;
+}
+
+/// Implements the CarbonCreditsApi RPC trait for interacting with the carbon credits pallet.
+pub struct CarbonCredits<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> CarbonCredits<C, B> {
+    /// Creates a new instance of the CarbonCredits RPC handler.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+    /// The call to runtime failed.
+    RuntimeError,
+}
+
+impl From<Error> for i32 {
+    fn from(e: Error) -> i32 {
+        match e {
+            Error::RuntimeError => 1,
+        }
+    }
+}
+
+fn runtime_error(message: &'static str, e: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+    CallError::Custom(ErrorObject::owned(
+        Error::RuntimeError.into(),
+        message,
+        Some(format!("{:?}", e)),
+    ))
+    .into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Moment, AssetId, Balance>
+    CarbonCreditsApiServer<<Block as BlockT>::Hash, AccountId, Moment, AssetId, Balance>
+    for CarbonCredits<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: CarbonCreditsRuntimeApi<Block, AccountId, Moment, AssetId, Balance>,
+    AccountId: Codec + PartialEq + Clone + Send + Sync + 'static,
+    Moment: Codec + Send + Sync + 'static,
+    AssetId: Codec + Send + Sync + 'static,
+    Balance: Codec + Clone + Send + Sync + 'static,
+{
+    fn get_project(
+        &self,
+        id: ProjectId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<ProjectStruct<AccountId, Moment, Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_project(&at, id)
+            .map_err(|e| runtime_error("Unable to query project.", e))
+    }
+
+    fn get_projects(
+        &self,
+        start: ProjectId,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(ProjectId, ProjectStruct<AccountId, Moment, Balance>)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_projects(&at, start, limit)
+            .map_err(|e| runtime_error("Unable to query projects.", e))
+    }
+
+    fn get_passport(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<CarbonCreditsPassport<AssetId>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_passport(&at, asset_id)
+            .map_err(|e| runtime_error("Unable to query carbon credits passport.", e))
+    }
+
+    fn get_burn_certificates(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<CarbonCreditsBurnCertificate<AssetId, Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_burn_certificates(&at, account)
+            .map_err(|e| runtime_error("Unable to query burn certificates.", e))
+    }
+
+    fn get_carbon_credit_lots(
+        &self,
+        offset: u32,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<LotsPage<AccountId, Moment, AssetId, Balance>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_carbon_credit_lots(&at, offset, limit)
+            .map_err(|e| runtime_error("Unable to query carbon credits lots.", e))
+    }
+
+    fn get_batch_assets(
+        &self,
+        offset: u32,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(BatchAssetId, BatchAsset<AccountId>)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_batch_assets(&at, offset, limit)
+            .map_err(|e| runtime_error("Unable to query batch assets.", e))
+    }
+
+    fn get_account_portfolio(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<AccountPortfolio<AccountId, Moment, AssetId, Balance>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_account_portfolio(&at, account)
+            .map_err(|e| runtime_error("Unable to query account portfolio.", e))
+    }
+}
@@ -6,6 +6,8 @@ use frame_support::{
 use crate::required_signers::RequiredSigner;
 use pallet_evercity_filesign::file::FileId;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 /// State of annual report in project for annual report state machine.
 /// 
@@ -33,6 +35,7 @@ pub type AnnualReportStructOf<AccountId, T, Balance> = AnnualReportStruct<Accoun
 
 /// Main annual report implementation
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct AnnualReportStruct<AccountId, Moment, Balance> where Balance: Clone, AccountId: PartialEq {
     /// Id of annual report file (stored in filesign)
     pub file_id: FileId,
@@ -114,6 +117,7 @@ impl<AccountId, Moment, Balance> AnnualReportStruct<AccountId, Moment, Balance>
 
 /// Metadata for Carbon Credit
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct CarbonCreditsMeta {
     /// The user friendly name of Carbon Credit 
     pub name: Vec<u8>,
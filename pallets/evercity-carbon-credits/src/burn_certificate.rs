@@ -3,8 +3,11 @@ use frame_support::{
     sp_runtime::RuntimeDebug,
 };
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct CarbonCreditsBurnCertificate<AssetId, Balance> {
     /// Carbon Credit asset id
     pub asset_id: AssetId,
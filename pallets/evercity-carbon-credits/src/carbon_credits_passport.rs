@@ -4,9 +4,12 @@ use frame_support::{
     sp_runtime::RuntimeDebug,
 };
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 /// Passport, that prooves, that an asset is a carbon credit asset
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct CarbonCreditsPassport<AssetId>{
     /// Carbon Credit asset id
     asset_id: AssetId,
@@ -75,6 +78,7 @@ impl<AssetId> CarbonCreditsPassport<AssetId> {
 
 /// Carbon Credits source
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum CarbonCreditsOrigin {
     /// Project that releases Carbon Credits
     CarbonProject(ProjectId),
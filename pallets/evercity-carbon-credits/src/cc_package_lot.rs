@@ -2,10 +2,13 @@ use codec::{Encode, Decode};
 use frame_support::RuntimeDebug;
 use pallet_evercity_bonds::Expired;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 /// Struct representing pack of carbon credits for sale.
 /// Can include target bearer (to sell only to them)
 #[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct CarbonCreditsPackageLot<AccountId, Moment, CCBalance, EverUSDAmount> {
     /// If set - only targer bearer can buy a lot, if None - anyone can buy
     pub target_bearer: Option<AccountId>,
@@ -7,12 +7,15 @@ use frame_support::sp_std::{
     cmp::{PartialEq}, 
 };
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 /// Batch asset id to list in retirement details on external registry
 pub type BatchAssetId = [u8; 32];
 pub type ExternalProjectId = Vec<u8>;
 
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum RegistryType {
     Cercarbono
 }
@@ -32,6 +35,7 @@ impl RegistryType {
 }
 
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum BatchStatus {
     /// Initial creation
@@ -51,6 +55,7 @@ impl Default for BatchStatus {
 }
 
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct BatchAsset<AccountId> {
     pub owner: AccountId,
     pub registry_type: RegistryType,
@@ -9,7 +9,9 @@ pub mod carbon_credits_passport;
 pub mod burn_certificate;
 pub mod bond_carbon_release;
 pub mod external_carbon_units;
-mod cc_package_lot;
+pub mod cc_package_lot;
+pub mod portfolio;
+pub mod runtime_api;
 #[cfg(test)]    
 pub mod tests;
 
@@ -28,6 +30,7 @@ use pallet_evercity_filesign::file::{FileId};
 use pallet_evercity_accounts::accounts::RoleMask;
 use carbon_credits_passport::*;
 use burn_certificate::CarbonCreditsBurnCertificate;
+use portfolio::{AccountPortfolio, AccountPortfolioOf};
 use pallet_evercity_accounts as accounts;
 use crate::external_carbon_units::*;
 
@@ -41,16 +44,18 @@ pub type CarbonCreditsId<T> = AssetId<T>;
 pub type CarbonCreditsBalance<T> = Balance<T>;
 
 const MAX_CARBON_CREDITS_ZOMBIES: u32 = 5_000_000;
+/// Maximum number of items returned by one page of runtime api listings
+pub const MAX_PAGE_SIZE: u32 = 100;
 
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{
 		dispatch::DispatchResultWithPostInfo,
 		pallet_prelude::{*, OptionQuery}, Blake2_128Concat,
-        traits::Randomness
+        traits::{Randomness, tokens::fungible},
 	};
 	use frame_system::pallet_prelude::*;
-    use pallet_evercity_bonds::{bond::BondState, BondId, Expired};
+    use pallet_evercity_bonds::{bond::BondState, BondId, EverUSDBalance, Expired};
 	use crate::bond_carbon_release::CarbonCreditsBondRelease;
 	use sp_runtime::traits::{CheckedAdd};
 	use crate::cc_package_lot::{CarbonCreditsPackageLotOf};
@@ -73,6 +78,8 @@ pub mod pallet {
     {
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
         type Randomness: frame_support::traits::Randomness<Self::Hash, Self::BlockNumber>;
+        /// EverUSD currency, used to pay for carbon credits
+        type EverUSD: fungible::Transfer<Self::AccountId, Balance = EverUSDBalance>;
     }
 
     #[pallet::hooks]
@@ -1303,7 +1310,7 @@ pub mod pallet {
 			ensure!(!lot.is_expired(now), Error::<T>::LotExpired);
 			ensure!(amount <= lot.amount, Error::<T>::NotEnoughCarbonCreditsInLot);
 			let total_price = lot.price_per_item*Self::balance_to_u64(amount);
-			ensure!(total_price < pallet_evercity_bonds::Pallet::<T>::get_balance(&caller),
+			ensure!(total_price < <T::EverUSD as fungible::Inspect<T::AccountId>>::balance(&caller),
 				Error::<T>::InsufficientEverUSDBalance);
 			// check that target bearer is the same as caller if lot is private 
 			if let Some(account) = lot.target_bearer.clone() {
@@ -1332,10 +1339,11 @@ pub mod pallet {
                                         amount
                                 )?;
                                 // transfer everUSD then
-                                pallet_evercity_bonds::Pallet::<T>::transfer_everusd(
+                                <T::EverUSD as fungible::Transfer<T::AccountId>>::transfer(
                                     &caller, 
                                     &seller, 
-                                    total_price
+                                    total_price,
+                                    false
                                 )?;
                                 
                                 // purge expired lots
@@ -1763,20 +1771,88 @@ pub mod pallet {
         }
 
     
-        #[cfg(test)]
         pub fn get_proj_by_id(id: ProjectId) -> Option<ProjectStruct<T::AccountId, T::Moment, T::ABalance>> {
             ProjectById::<T>::get(id)
         }
     
-        #[cfg(test)]
         pub fn get_passport_by_assetid(asset_id: AssetId<T>) -> Option<CarbonCreditsPassport<AssetId<T>>> {
             CarbonCreditPassportRegistry::<T>::get(asset_id)
         }
     
-        #[cfg(test)]
         pub fn get_certificates_by_account(account: T::AccountId) -> Vec<CarbonCreditsBurnCertificate<AssetId<T>, T::ABalance>> {
             BurnCertificates::<T>::get(account)
         }
+
+        /// <pre>
+        /// Method: get_account_portfolio(account: T::AccountId)
+        /// Arguments: account: T::AccountId - account to get the statement for
+        ///
+        /// Returns EverUSD balances and bond positions of the account (see bonds pallet get_portfolio()),
+        /// Carbon Credits balances, sale lots and burn certificates.
+        /// Uses secondary indices of the account, so it doesn't iterate whole registries
+        /// </pre>
+        pub fn get_account_portfolio(account: T::AccountId) -> AccountPortfolioOf<T> {
+            AccountPortfolio {
+                bonds: pallet_evercity_bonds::Pallet::<T>::get_portfolio(&account),
+                carbon_credits: pallet_evercity_assets::Pallet::<T>::account_assets(&account),
+                carbon_credit_lots: CarbonCreditLotRegistry::<T>::iter_prefix(&account)
+                    .filter_map(|(asset_id, lots)| lots.map(|lots| (asset_id, lots)))
+                    .collect(),
+                burn_certificates: BurnCertificates::<T>::get(account),
+            }
+        }
+
+        /// <pre>
+        /// Method: get_projects(start: ProjectId, limit: u32)
+        /// Arguments: start: ProjectId - first project id of the page
+        ///            limit: u32 - maximum number of projects, capped by MAX_PAGE_SIZE
+        ///
+        /// Returns projects with id >= start in ascending id order.
+        /// Next page starts with the last returned id + 1
+        /// </pre>
+        pub fn get_projects(start: ProjectId, limit: u32) -> Vec<(ProjectId, ProjectStruct<T::AccountId, T::Moment, T::ABalance>)> {
+            let limit = limit.min(MAX_PAGE_SIZE) as usize;
+            let last_id = LastID::<T>::get();
+            if start > last_id {
+                return Vec::new();
+            }
+            (start..=last_id)
+                .filter_map(|id| ProjectById::<T>::get(id).map(|project| (id, project)))
+                .take(limit)
+                .collect()
+        }
+
+        /// <pre>
+        /// Method: get_carbon_credit_lots(offset: u32, limit: u32)
+        /// Arguments: offset: u32 - number of registry entries to skip
+        ///            limit: u32 - maximum number of entries, capped by MAX_PAGE_SIZE
+        ///
+        /// Returns a page of CarbonCreditLotRegistry entries as (seller, asset_id, lots).
+        /// Entries are ordered by storage key, so pages are stable for the same block
+        /// </pre>
+        pub fn get_carbon_credit_lots(offset: u32, limit: u32) -> Vec<(T::AccountId, AssetId<T>, Vec<CarbonCreditsPackageLotOf<T>>)> {
+            let limit = limit.min(MAX_PAGE_SIZE) as usize;
+            CarbonCreditLotRegistry::<T>::iter()
+                .filter_map(|(account, asset_id, lots)| lots.map(|lots| (account, asset_id, lots)))
+                .skip(offset as usize)
+                .take(limit)
+                .collect()
+        }
+
+        /// <pre>
+        /// Method: get_batch_assets(offset: u32, limit: u32)
+        /// Arguments: offset: u32 - number of registry entries to skip
+        ///            limit: u32 - maximum number of entries, capped by MAX_PAGE_SIZE
+        ///
+        /// Returns a page of BatchAssetRegistry entries ordered by storage key
+        /// </pre>
+        pub fn get_batch_assets(offset: u32, limit: u32) -> Vec<(BatchAssetId, BatchAsset<T::AccountId>)> {
+            let limit = limit.min(MAX_PAGE_SIZE) as usize;
+            BatchAssetRegistry::<T>::iter()
+                .skip(offset as usize)
+                .take(limit)
+                .collect()
+        }
     
         #[cfg(test)]
         pub fn create_test_carbon_credits(
@@ -0,0 +1,29 @@
+use codec::{Encode, Decode};
+use frame_support::{dispatch::Vec, RuntimeDebug};
+use pallet_evercity_bonds::{bond::BondPortfolioStruct, EverUSDBalance};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use crate::burn_certificate::CarbonCreditsBurnCertificate;
+use crate::cc_package_lot::CarbonCreditsPackageLot;
+
+/// Statement of the account holdings: EverUSD, bond positions,
+/// Carbon Credits balances, sale lots and burn certificates
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AccountPortfolio<AccountId, Moment, AssetId, Balance> {
+    /// EverUSD balances and bond positions
+    pub bonds: BondPortfolioStruct<AccountId, Moment>,
+    /// Balances of Carbon Credits assets, held by the account
+    pub carbon_credits: Vec<(AssetId, Balance)>,
+    /// Carbon Credits sale lots of the account, by asset
+    pub carbon_credit_lots: Vec<(AssetId, Vec<CarbonCreditsPackageLot<AccountId, Moment, Balance, EverUSDBalance>>)>,
+    pub burn_certificates: Vec<CarbonCreditsBurnCertificate<AssetId, Balance>>,
+}
+
+pub type AccountPortfolioOf<T> = AccountPortfolio<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_timestamp::Config>::Moment,
+    crate::AssetId<T>,
+    crate::Balance<T>,
+>;
@@ -13,6 +13,8 @@ use frame_support::sp_std::{
 };
 use crate::required_signers::RequiredSigner;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 /// Project states for project state machine.
 /// 
@@ -39,6 +41,7 @@ pub type ProjectId = u32;
 
 /// Main struct for projects
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ProjectStruct<AccountId, Moment, Balance> where AccountId: PartialEq + Clone, Balance: Clone {
     /// Owner of the project. Creates project, assigns auditor (and other) signers for the project.
     /// Creates annual reports and assigns auditor (and other) signers for it.
@@ -135,6 +138,7 @@ impl<AccountId, Moment, Balance> ProjectStruct<AccountId, Moment, Balance> where
 }
 
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum ProjectStatus {
     /// When project is created
@@ -0,0 +1,35 @@
+use codec::Codec;
+use frame_support::dispatch::Vec;
+use pallet_evercity_bonds::EverUSDBalance;
+use crate::{
+    burn_certificate::CarbonCreditsBurnCertificate,
+    carbon_credits_passport::CarbonCreditsPassport,
+    cc_package_lot::CarbonCreditsPackageLot,
+    external_carbon_units::{BatchAsset, BatchAssetId},
+    portfolio::AccountPortfolio,
+    project::{ProjectId, ProjectStruct},
+};
+
+sp_api::decl_runtime_apis! {
+    pub trait CarbonCreditsApi<AccountId, Moment, AssetId, Balance> where
+        AccountId: Codec + PartialEq + Clone,
+        Moment: Codec,
+        AssetId: Codec,
+        Balance: Codec + Clone,
+    {
+        /// delegate call to the pallet get_proj_by_id()
+        fn get_project(id: ProjectId)->Option<ProjectStruct<AccountId, Moment, Balance>>;
+        /// delegate call to the pallet get_projects()
+        fn get_projects(start: ProjectId, limit: u32)->Vec<(ProjectId, ProjectStruct<AccountId, Moment, Balance>)>;
+        /// delegate call to the pallet get_passport_by_assetid()
+        fn get_passport(asset_id: AssetId)->Option<CarbonCreditsPassport<AssetId>>;
+        /// delegate call to the pallet get_certificates_by_account()
+        fn get_burn_certificates(account: AccountId)->Vec<CarbonCreditsBurnCertificate<AssetId, Balance>>;
+        /// delegate call to the pallet get_carbon_credit_lots()
+        fn get_carbon_credit_lots(offset: u32, limit: u32)->Vec<(AccountId, AssetId, Vec<CarbonCreditsPackageLot<AccountId, Moment, Balance, EverUSDBalance>>)>;
+        /// delegate call to the pallet get_batch_assets()
+        fn get_batch_assets(offset: u32, limit: u32)->Vec<(BatchAssetId, BatchAsset<AccountId>)>;
+        /// delegate call to the pallet get_account_portfolio()
+        fn get_account_portfolio(account: AccountId)->AccountPortfolio<AccountId, Moment, AssetId, Balance>;
+    }
+}
@@ -3,9 +3,12 @@ use frame_support::{
     sp_runtime::RuntimeDebug,
 };
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 /// Carbon Credits industry standard
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum Standard {
     /// Variant for the Gold Standard for bond projects
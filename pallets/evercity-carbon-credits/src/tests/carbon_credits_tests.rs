@@ -234,4 +234,38 @@ fn it_fails_for_burn_cc_not_enough() {
         assert_noop!(burn_result, RuntimeError::InsufficientCarbonCredits);
         assert_eq!(Assets::balance(asset_id, owner), TEST_CARBON_CREDITS_COUNT);
     });
-}
\ No newline at end of file
+}
+#[test]
+fn it_works_for_account_portfolio() {
+    new_test_ext().execute_with(|| {
+        let (_, project_id, owner) = full_sign_annual_report_gold_standard();
+        let asset_id = 1;
+        let investor = ROLES[4].0;
+        let _ = CarbonCredits::release_carbon_credits(Origin::signed(owner), project_id, asset_id, owner, 1);
+        assert_ok!(CarbonCredits::transfer_carbon_credits(Origin::signed(owner), asset_id, investor, 300));
+        assert_ok!(CarbonCredits::burn_carbon_credits(Origin::signed(investor), asset_id, 20));
+        let new_lot = crate::cc_package_lot::CarbonCreditsPackageLot {
+            target_bearer: None,
+            deadline: 100_000,
+            amount: 500,
+            price_per_item: 10_000,
+        };
+        assert_ok!(CarbonCredits::create_carbon_credit_lot(Origin::signed(owner), asset_id, new_lot.clone()));
+
+        let portfolio = CarbonCredits::get_account_portfolio(owner);
+        assert_eq!(portfolio.carbon_credits, vec![(asset_id, TEST_CARBON_CREDITS_COUNT - 300)]);
+        assert_eq!(portfolio.carbon_credit_lots, vec![(asset_id, vec![new_lot])]);
+        assert!(portfolio.burn_certificates.is_empty());
+        assert!(portfolio.bonds.positions.is_empty());
+
+        let portfolio = CarbonCredits::get_account_portfolio(investor);
+        assert_eq!(portfolio.carbon_credits, vec![(asset_id, 280)]);
+        assert_eq!(portfolio.burn_certificates[0].burn_amount, 20);
+
+        // asset is removed from the index, when all carbon credits are transferred
+        assert_ok!(CarbonCredits::transfer_carbon_credits(Origin::signed(investor), asset_id, owner, 280));
+        let portfolio = CarbonCredits::get_account_portfolio(investor);
+        assert!(portfolio.carbon_credits.is_empty());
+        assert_eq!(portfolio.burn_certificates.len(), 1);
+    });
+}
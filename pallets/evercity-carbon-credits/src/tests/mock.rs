@@ -43,6 +43,8 @@ parameter_types! {
     pub const MintRequestTtl: u32 = DEFAULT_DAY_DURATION as u32 * 7 * 1000;
     pub const MaxMintAmount: pallet_evercity_bonds::EverUSDBalance = 60_000_000_000_000_000;
     pub const TimeStep: pallet_evercity_bonds::BondPeriod = DEFAULT_DAY_DURATION;
+    pub const MaxLifecycleOpsPerBlock: u32 = 50;
+    pub const ReserveAttestationTtl: u32 = DEFAULT_DAY_DURATION as u32 * 45 * 1000;
 }
 
 impl pallet_evercity_bonds::Config for TestRuntime {
@@ -51,8 +53,11 @@ impl pallet_evercity_bonds::Config for TestRuntime {
     type MintRequestTtl = MintRequestTtl;
     type MaxMintAmount = MaxMintAmount;
     type TimeStep = TimeStep;
+    type ReserveAttestationTtl = ReserveAttestationTtl;
+    type MaxLifecycleOpsPerBlock = MaxLifecycleOpsPerBlock;
     type WeightInfo = ();
     type OnAddBond = ();
+    type BondTransferGuard = EvercityBonds;
 }
 
 type AccountId = u64;
@@ -87,6 +92,7 @@ impl frame_system::Config for TestRuntime {
 impl pallet_carbon_credits::Config for TestRuntime {
 	type Event = Event;
     type Randomness = RandomnessCollectiveFlip;
+    type EverUSD = EvercityBonds;
 }
 
 impl pallet_evercity_accounts::Config for TestRuntime {
@@ -266,6 +272,14 @@ pub fn get_test_bond(carbon_metadata: pallet_evercity_bonds::bond::CarbonUnitsMe
             bond_units_maxcap_amount: 1800,
             bond_units_base_price: 4_000_000_000_000,
             carbon_metadata: Some(carbon_metadata),
+            call_schedule: None,
+            principal_schedule: None,
+            discount: None,
+            impact_kpis: Vec::new(),
+            interest_rate_curve: Default::default(),
+            bookbuilding: None,
+            fees: Vec::new(),
+            use_of_proceeds: Vec::new(),
         },
 
         issuer: 0,
@@ -657,4 +657,24 @@ fn it_works_sign_project_deposit_events_gold_standard() {
             assert_eq!(*check_event, last_event);
         });
     });
-}
\ No newline at end of file
+}
+
+#[test]
+fn it_works_get_projects_paged() {
+    new_test_ext().execute_with(|| {
+        let owner = ROLES[1].0;
+        for _ in 0..5 {
+            let _ = CarbonCredits::create_project(Origin::signed(owner), Standard::default(), None);
+        }
+
+        let first_page = CarbonCredits::get_projects(1, 2);
+        assert_eq!(vec![1, 2], first_page.iter().map(|(id, _)| *id).collect::<Vec<_>>());
+        assert!(first_page.iter().all(|(id, p)| p.id == *id && p.owner == owner));
+
+        let last_page = CarbonCredits::get_projects(5, 2);
+        assert_eq!(vec![5], last_page.iter().map(|(id, _)| *id).collect::<Vec<_>>());
+
+        assert!(CarbonCredits::get_projects(6, 2).is_empty());
+        assert_eq!(5, CarbonCredits::get_projects(0, u32::MAX).len());
+    });
+}
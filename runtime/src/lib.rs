@@ -63,6 +63,15 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
+/// A timestamp: milliseconds since the unix epoch.
+pub type Moment = u64;
+
+/// Identifier of an evercity asset (carbon credits).
+pub type AssetId = u64;
+
+/// Balance of an evercity asset (carbon credits).
+pub type AssetBalance = u64;
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -99,7 +108,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -237,7 +246,7 @@ impl pallet_grandpa::Config for Runtime {
 
 impl pallet_timestamp::Config for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
+	type Moment = Moment;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = ConstU64<{ SLOT_DURATION / 2 }>;
 	type WeightInfo = ();
@@ -283,8 +292,8 @@ parameter_types! {
 use pallet_evercity_assets;
 impl pallet_evercity_assets::Config for Runtime {
     type Event = Event;
-    type ABalance = u64;
-    type AssetId = u64;
+    type ABalance = AssetBalance;
+    type AssetId = AssetId;
     type Currency = Balances;
     type ForceOrigin = frame_system::EnsureRoot<AccountId>;
     type AssetDepositBase = AssetDepositBase;
@@ -313,6 +322,8 @@ parameter_types! {
     pub const MintRequestTtl: u32 = DEFAULT_DAY_DURATION as u32 * 7 * 1000;
     pub const MaxMintAmount: pallet_evercity_bonds::EverUSDBalance = 60_000_000_000_000_000;
     pub const TimeStep: pallet_evercity_bonds::BondPeriod = DEFAULT_DAY_DURATION;
+    pub const MaxLifecycleOpsPerBlock: u32 = 50;
+    pub const ReserveAttestationTtl: u32 = DEFAULT_DAY_DURATION as u32 * 45 * 1000;
 }
 
 impl pallet_evercity_bonds::Config for Runtime {
@@ -321,14 +332,18 @@ impl pallet_evercity_bonds::Config for Runtime {
     type MintRequestTtl = MintRequestTtl;
     type MaxMintAmount = MaxMintAmount;
     type TimeStep = TimeStep;
+    type ReserveAttestationTtl = ReserveAttestationTtl;
+    type MaxLifecycleOpsPerBlock = MaxLifecycleOpsPerBlock;
     type WeightInfo = ();
     type OnAddBond = ();
+    type BondTransferGuard = Evercity;
 }
 
 pub use pallet_evercity_carbon_credits;
 impl pallet_evercity_carbon_credits::Config for Runtime {
     type Event = Event;
     type Randomness = RandomnessCollectiveFlip;
+    type EverUSD = Evercity;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -529,6 +544,142 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_evercity_bonds::runtime_api::BondApi<Block, AccountId, Moment, Hash> for Runtime {
+		fn get_impact_reports(bond: pallet_evercity_bonds::BondId) -> Vec<pallet_evercity_bonds::PeriodDataStruct> {
+			Evercity::get_impact_reports(bond)
+		}
+
+		fn get_bond(bond: pallet_evercity_bonds::BondId) -> Option<pallet_evercity_bonds::BondStructOf<Runtime>> {
+			Evercity::bond_registry(bond)
+		}
+
+		fn get_coupon_yields(bond: pallet_evercity_bonds::BondId) -> Vec<pallet_evercity_bonds::PeriodYield> {
+			Evercity::get_coupon_yields(&bond)
+		}
+
+		fn get_bond_unit_packages(
+			bond: pallet_evercity_bonds::BondId,
+			bondholder: AccountId,
+		) -> Vec<pallet_evercity_bonds::BondUnitPackage> {
+			Evercity::bond_unit_registry(bond, bondholder)
+		}
+
+		fn get_bond_unit_lots(
+			bond: pallet_evercity_bonds::BondId,
+		) -> Vec<(AccountId, Vec<pallet_evercity_bonds::bond::BondUnitSaleLotStructOf<Runtime>>)> {
+			Evercity::get_bond_unit_lots(&bond)
+		}
+
+		fn get_bond_unit_bids(
+			bond: pallet_evercity_bonds::BondId,
+		) -> Vec<(AccountId, Vec<pallet_evercity_bonds::bond::BondUnitPurchaseBidStructOf<Runtime>>)> {
+			Evercity::get_bond_unit_bids(&bond)
+		}
+
+		fn get_impact_report_history(
+			bond: pallet_evercity_bonds::BondId,
+			period: pallet_evercity_bonds::bond::BondPeriodNumber,
+		) -> Vec<pallet_evercity_bonds::bond::BondImpactReportHistoryItemOf<Runtime>> {
+			Evercity::impact_report_history(bond, period)
+		}
+
+		fn get_proceeds_report(bond: pallet_evercity_bonds::BondId) -> pallet_evercity_bonds::bond::BondProceedsReport<Moment> {
+			Evercity::get_proceeds_report(&bond)
+		}
+
+		fn get_bond_calendar(bond: pallet_evercity_bonds::BondId) -> Vec<pallet_evercity_bonds::BondDeadline<Moment>> {
+			Evercity::get_bond_calendar(&bond)
+		}
+
+		fn get_account_calendar(account: AccountId) -> Vec<pallet_evercity_bonds::BondDeadline<Moment>> {
+			Evercity::get_account_calendar(&account)
+		}
+
+		fn get_bond_unit_price(
+			bond: pallet_evercity_bonds::BondId,
+			bond_units: pallet_evercity_bonds::bond::BondUnitAmount,
+		) -> Option<pallet_evercity_bonds::bond::BondUnitPriceStruct> {
+			Evercity::get_bond_unit_price(&bond, bond_units)
+		}
+
+		fn get_yield_to_maturity(
+			bond: pallet_evercity_bonds::BondId,
+			bond_units: pallet_evercity_bonds::bond::BondUnitAmount,
+			price: pallet_evercity_bonds::bond::EverUSDBalance,
+		) -> Option<pallet_evercity_bonds::bond::BondInterest> {
+			Evercity::get_yield_to_maturity(&bond, bond_units, price)
+		}
+
+		fn get_implied_yield(bond: pallet_evercity_bonds::BondId) -> Option<pallet_evercity_bonds::bond::BondInterest> {
+			Evercity::get_implied_yield(&bond)
+		}
+
+		fn get_portfolio(account: AccountId) -> pallet_evercity_bonds::bond::BondPortfolioStructOf<Runtime> {
+			Evercity::get_portfolio(&account)
+		}
+
+		fn get_everusd_reconciliation() -> pallet_evercity_bonds::token::EverUSDReconciliationStruct {
+			Evercity::get_everusd_reconciliation()
+		}
+
+		fn get_latest_reserve_attestation() -> Option<pallet_evercity_bonds::token::ReserveAttestationStructOf<Runtime>> {
+			Evercity::get_latest_reserve_attestation()
+		}
+	}
+
+	impl pallet_evercity_carbon_credits::runtime_api::CarbonCreditsApi<Block, AccountId, Moment, AssetId, AssetBalance> for Runtime {
+		fn get_project(
+			id: pallet_evercity_carbon_credits::project::ProjectId,
+		) -> Option<pallet_evercity_carbon_credits::project::ProjectStruct<AccountId, Moment, AssetBalance>> {
+			EvercityCarbonCredits::get_proj_by_id(id)
+		}
+
+		fn get_projects(
+			start: pallet_evercity_carbon_credits::project::ProjectId,
+			limit: u32,
+		) -> Vec<(
+			pallet_evercity_carbon_credits::project::ProjectId,
+			pallet_evercity_carbon_credits::project::ProjectStruct<AccountId, Moment, AssetBalance>,
+		)> {
+			EvercityCarbonCredits::get_projects(start, limit)
+		}
+
+		fn get_passport(
+			asset_id: AssetId,
+		) -> Option<pallet_evercity_carbon_credits::carbon_credits_passport::CarbonCreditsPassport<AssetId>> {
+			EvercityCarbonCredits::get_passport_by_assetid(asset_id)
+		}
+
+		fn get_burn_certificates(
+			account: AccountId,
+		) -> Vec<pallet_evercity_carbon_credits::burn_certificate::CarbonCreditsBurnCertificate<AssetId, AssetBalance>> {
+			EvercityCarbonCredits::get_certificates_by_account(account)
+		}
+
+		fn get_carbon_credit_lots(
+			offset: u32,
+			limit: u32,
+		) -> Vec<(AccountId, AssetId, Vec<pallet_evercity_carbon_credits::cc_package_lot::CarbonCreditsPackageLotOf<Runtime>>)> {
+			EvercityCarbonCredits::get_carbon_credit_lots(offset, limit)
+		}
+
+		fn get_batch_assets(
+			offset: u32,
+			limit: u32,
+		) -> Vec<(
+			pallet_evercity_carbon_credits::external_carbon_units::BatchAssetId,
+			pallet_evercity_carbon_credits::external_carbon_units::BatchAsset<AccountId>,
+		)> {
+			EvercityCarbonCredits::get_batch_assets(offset, limit)
+		}
+
+		fn get_account_portfolio(
+			account: AccountId,
+		) -> pallet_evercity_carbon_credits::portfolio::AccountPortfolioOf<Runtime> {
+			EvercityCarbonCredits::get_account_portfolio(account)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (